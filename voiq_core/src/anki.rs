@@ -0,0 +1,320 @@
+//! Export to Anki packages (.apkg).
+//!
+//! A package is a zip archive holding an Anki collection database (`collection.anki2`,
+//! in the schema-11 layout every Anki version can import) and a media map. Each word
+//! becomes a note of a "VoIQ" note type with one card, filed in a deck named after its
+//! category. Scheduling carries over: reviewed words become review cards with their
+//! interval, ease, lapses and due date; words never reviewed stay new. With
+//! `include_history`, attempts become review log entries so Anki's statistics show them.
+//! Notes keep a stable guid per word, so exporting again updates notes instead of
+//! duplicating them.
+
+use pyo3::prelude::*;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use zip::write::SimpleFileOptions;
+use crate::db::{get_words, open_database, Word};
+
+/// Fields of the exported note type, in order; the first is the sort field
+const NOTE_FIELDS: &[&str] = &["Word", "Meaning", "Reading", "Synonyms", "Antonyms", "Notes"];
+
+const QUESTION_TEMPLATE: &str = "<div class=word>{{Word}}</div>";
+const ANSWER_TEMPLATE: &str = "{{FrontSide}}<hr id=answer>{{Meaning}}\
+{{#Reading}}<div class=reading>{{Reading}}</div>{{/Reading}}\
+{{#Synonyms}}<div>Synonyms: {{Synonyms}}</div>{{/Synonyms}}\
+{{#Antonyms}}<div>Antonyms: {{Antonyms}}</div>{{/Antonyms}}\
+{{#Notes}}<div class=notes>{{Notes}}</div>{{/Notes}}";
+const CARD_CSS: &str = ".card { font-family: arial; font-size: 20px; text-align: center; }\n\
+.word { font-size: 32px; }\n.reading, .notes { color: grey; }";
+
+/// Longest answer time Anki records, in milliseconds
+const MAX_ANSWER_MS: i64 = 60_000;
+
+/// Tables of an Anki schema-11 collection
+const COLLECTION_SCHEMA: &str = "
+    CREATE TABLE col (id integer primary key, crt integer not null, mod integer not null, scm integer not null,
+        ver integer not null, dty integer not null, usn integer not null, ls integer not null, conf text not null,
+        models text not null, decks text not null, dconf text not null, tags text not null);
+    CREATE TABLE notes (id integer primary key, guid text not null, mid integer not null, mod integer not null,
+        usn integer not null, tags text not null, flds text not null, sfld integer not null, csum integer not null,
+        flags integer not null, data text not null);
+    CREATE TABLE cards (id integer primary key, nid integer not null, did integer not null, ord integer not null,
+        mod integer not null, usn integer not null, type integer not null, queue integer not null, due integer not null,
+        ivl integer not null, factor integer not null, reps integer not null, lapses integer not null, left integer not null,
+        odue integer not null, odid integer not null, flags integer not null, data text not null);
+    CREATE TABLE revlog (id integer primary key, cid integer not null, usn integer not null, ease integer not null,
+        ivl integer not null, lastIvl integer not null, factor integer not null, time integer not null, type integer not null);
+    CREATE TABLE graves (usn integer not null, oid integer not null, type integer not null);
+    CREATE INDEX ix_notes_usn on notes (usn);
+    CREATE INDEX ix_cards_usn on cards (usn);
+    CREATE INDEX ix_revlog_usn on revlog (usn);
+    CREATE INDEX ix_cards_nid on cards (nid);
+    CREATE INDEX ix_cards_sched on cards (did, queue, due);
+    CREATE INDEX ix_revlog_cid on revlog (cid);
+    CREATE INDEX ix_notes_csum on notes (csum);";
+
+/// SHA-1 digest, which Anki uses for note checksums
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+    
+    for chunk in message.chunks(64) {
+        let mut w = [0u32; 80];
+        for (i, word) in chunk.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, &wi) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let temp = a.rotate_left(5).wrapping_add(f).wrapping_add(e).wrapping_add(k).wrapping_add(wi);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+        for (state, value) in h.iter_mut().zip([a, b, c, d, e]) {
+            *state = state.wrapping_add(value);
+        }
+    }
+    
+    let mut digest = [0u8; 20];
+    for (i, state) in h.iter().enumerate() {
+        digest[i * 4..i * 4 + 4].copy_from_slice(&state.to_be_bytes());
+    }
+    digest
+}
+
+/// Anki's duplicate-check checksum of a note's first field: the first 32 bits of its SHA-1
+fn field_checksum(field: &str) -> i64 {
+    let digest = sha1(field.as_bytes());
+    u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]) as i64
+}
+
+/// Id derived from a name, so re-exports reuse the same note type and decks instead of
+/// creating copies on import. Kept within 48 bits and clear of the default deck's id 1.
+fn stable_id(kind: &str, name: &str) -> i64 {
+    let digest = sha1(format!("{}:{}", kind, name).as_bytes());
+    let id = digest[..6].iter().fold(0i64, |id, b| (id << 8) | *b as i64);
+    id.max(2)
+}
+
+/// Anki fields hold HTML
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('\n', "<br>")
+}
+
+/// Anki tags can't contain spaces
+fn anki_tag(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join("_")
+}
+
+/// A word's schedule and history as Anki counts them
+struct CardSchedule {
+    /// None for a word never reviewed
+    interval_days: Option<f64>,
+    ease: f64,
+    lapses: i64,
+    due_at: Option<chrono::NaiveDateTime>,
+    /// (answered at, correct, time taken in ms)
+    attempts: Vec<(chrono::NaiveDateTime, bool, i64)>,
+}
+
+fn parse_timestamp(text: &str) -> Option<chrono::NaiveDateTime> {
+    chrono::NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S").ok()
+}
+
+fn read_schedule(conn: &Connection, word_id: i64) -> rusqlite::Result<CardSchedule> {
+    let state: Option<(f64, f64, i64, String)> = conn.query_row(
+        "SELECT interval_days, ease, lapses, due_at FROM srs_state WHERE word_id = ?1",
+        params![word_id],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+    ).optional()?;
+    let mut stmt = conn.prepare_cached(
+        "SELECT attempted_at, is_correct, COALESCE(time_taken_ms, 0) FROM attempts
+         WHERE word_id = ?1 AND attempted_at IS NOT NULL ORDER BY attempted_at, id",
+    )?;
+    let attempts = stmt.query_map(params![word_id], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, bool>(1)?, row.get::<_, i64>(2)?))
+    })?
+        .filter_map(|r| r.ok())
+        .filter_map(|(at, correct, ms)| parse_timestamp(&at).map(|at| (at, correct, ms)))
+        .collect();
+    Ok(match state {
+        Some((interval_days, ease, lapses, due_at)) => CardSchedule {
+            interval_days: Some(interval_days),
+            ease,
+            lapses,
+            due_at: parse_timestamp(&due_at),
+            attempts,
+        },
+        None => CardSchedule { interval_days: None, ease: 2.5, lapses: 0, due_at: None, attempts },
+    })
+}
+
+/// Write the words of `categories` (all when None) with their schedules to an Anki
+/// package at `out_path`. Returns the number of notes written.
+pub fn export_to_anki(db_path: &str, categories: Option<Vec<String>>, out_path: &str, include_history: bool) -> Result<usize, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let words = get_words(&conn, "a_to_z", None, categories.filter(|c| !c.is_empty()), None, None, None, false)
+        .map_err(|e| format!("Failed to get words: {}", e))?;
+    if words.is_empty() {
+        return Err("No words to export".to_string());
+    }
+    let schedules: Vec<CardSchedule> = words.iter()
+        .map(|w| read_schedule(&conn, w.id))
+        .collect::<rusqlite::Result<_>>()
+        .map_err(|e| format!("Failed to read progress: {}", e))?;
+    
+    let collection_path = format!("{}.collection.tmp", out_path);
+    let _ = std::fs::remove_file(&collection_path);
+    let written = write_collection(&collection_path, &words, &schedules, include_history)
+        .map_err(|e| format!("Failed to write Anki collection: {}", e))
+        .and_then(|_| std::fs::read(&collection_path).map_err(|e| format!("Failed to read Anki collection: {}", e)));
+    let _ = std::fs::remove_file(&collection_path);
+    let collection = written?;
+    
+    let file = std::fs::File::create(out_path)
+        .map_err(|e| format!("Failed to create file: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    for (name, content) in [("collection.anki2", collection.as_slice()), ("media", b"{}".as_slice())] {
+        zip.start_file(name, SimpleFileOptions::default())
+            .and_then(|_| zip.write_all(content).map_err(Into::into))
+            .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+    }
+    zip.finish().map_err(|e| format!("Failed to finish package: {}", e))?;
+    Ok(words.len())
+}
+
+fn write_collection(path: &str, words: &[Word], schedules: &[CardSchedule], include_history: bool) -> rusqlite::Result<()> {
+    let mut conn = Connection::open(path)?;
+    conn.execute_batch(COLLECTION_SCHEMA)?;
+    let tx = conn.transaction()?;
+    
+    let now = chrono::Utc::now();
+    let now_s = now.timestamp();
+    let now_ms = now.timestamp_millis();
+    // Day numbers count from the collection's creation, placed before the oldest answer
+    let first_day = schedules.iter()
+        .flat_map(|s| s.attempts.first().map(|a| a.0.date()))
+        .chain(std::iter::once(now.date_naive()))
+        .min()
+        .unwrap_or(now.date_naive());
+    let crt = first_day.and_hms_opt(0, 0, 0).map_or(now_s, |t| t.and_utc().timestamp());
+    let day_number = |at: chrono::NaiveDate| (at - first_day).num_days().max(0);
+    let today = day_number(now.date_naive());
+    
+    let model_id = stable_id("model", "VoIQ");
+    let mut deck_ids: HashMap<String, i64> = HashMap::new();
+    for word in words {
+        deck_ids.entry(word.category.clone()).or_insert_with(|| stable_id("deck", &word.category));
+    }
+    let mut decks = serde_json::Map::new();
+    let deck_json = |id: i64, name: &str| json!({
+        "id": id, "name": name, "mod": now_s, "usn": -1, "desc": "", "dyn": 0, "conf": 1, "collapsed": false,
+        "extendNew": 0, "extendRev": 50, "newToday": [0, 0], "revToday": [0, 0], "lrnToday": [0, 0], "timeToday": [0, 0],
+    });
+    decks.insert("1".to_string(), deck_json(1, "Default"));
+    for (name, id) in &deck_ids {
+        decks.insert(id.to_string(), deck_json(*id, name));
+    }
+    
+    let fields: Vec<Value> = NOTE_FIELDS.iter().enumerate().map(|(ord, name)| json!({
+        "name": name, "ord": ord, "sticky": false, "rtl": false, "font": "Arial", "size": 20, "media": [],
+    })).collect();
+    let model = json!({
+        "id": model_id, "name": "VoIQ", "type": 0, "mod": now_s, "usn": -1, "sortf": 0, "did": 1,
+        "tmpls": [{
+            "name": "Card 1", "ord": 0, "qfmt": QUESTION_TEMPLATE, "afmt": ANSWER_TEMPLATE,
+            "did": null, "bqfmt": "", "bafmt": "",
+        }],
+        "flds": fields, "css": CARD_CSS, "tags": [], "vers": [], "req": [[0, "any", [0]]],
+        "latexPre": "\\documentclass[12pt]{article}\n\\special{papersize=3in,5in}\n\\usepackage[utf8]{inputenc}\n\\usepackage{amssymb,amsmath}\n\\pagestyle{empty}\n\\setlength{\\parindent}{0in}\n\\begin{document}\n",
+        "latexPost": "\\end{document}",
+    });
+    let conf = json!({
+        "activeDecks": [1], "curDeck": 1, "newSpread": 0, "collapseTime": 1200, "timeLim": 0, "estTimes": true,
+        "dueCounts": true, "curModel": model_id.to_string(), "nextPos": words.len() + 1, "sortType": "noteFld",
+        "sortBackwards": false, "addToCur": true,
+    });
+    let dconf = json!({"1": {
+        "id": 1, "name": "Default", "mod": 0, "usn": 0, "maxTaken": 60, "autoplay": true, "timer": 0, "replayq": true, "dyn": false,
+        "new": {"delays": [1, 10], "ints": [1, 4, 7], "initialFactor": 2500, "order": 1, "perDay": 20, "separate": true, "bury": true},
+        "rev": {"perDay": 200, "ease4": 1.3, "fuzz": 0.05, "minSpace": 1, "ivlFct": 1, "maxIvl": 36500, "bury": true},
+        "lapse": {"delays": [10], "mult": 0, "minInt": 1, "leechFails": 8, "leechAction": 0},
+    }});
+    tx.execute(
+        "INSERT INTO col VALUES (1, ?1, ?2, ?3, 11, 0, 0, 0, ?4, ?5, ?6, ?7, '{}')",
+        params![crt, now_ms, now_ms, conf.to_string(), json!({ model_id.to_string(): model }).to_string(),
+                Value::Object(decks).to_string(), dconf.to_string()],
+    )?;
+    
+    let mut revlog_ids: HashSet<i64> = HashSet::new();
+    for (position, (word, schedule)) in words.iter().zip(schedules).enumerate() {
+        let note_id = now_ms + 1_000_000 + position as i64;
+        let card_id = note_id;
+        let flds = [&word.word, &word.meaning, &word.reading, &word.synonyms, &word.antonyms, &word.notes]
+            .iter().map(|f| escape_html(f)).collect::<Vec<_>>();
+        tx.execute(
+            "INSERT INTO notes VALUES (?1, ?2, ?3, ?4, -1, ?5, ?6, ?7, ?8, 0, '')",
+            params![note_id, format!("voiq-{}", word.id), model_id, now_s, format!(" {} ", anki_tag(&word.category)),
+                    flds.join("\u{1f}"), word.word, field_checksum(&word.word)],
+        )?;
+        
+        // (type, queue, due, interval, factor)
+        let (card_type, queue, due, interval, factor) = match schedule.interval_days {
+            None => (0, 0, position as i64 + 1, 0, 0),
+            Some(interval_days) => {
+                let due = schedule.due_at.map_or(today, |d| day_number(d.date()));
+                (2, 2, due, (interval_days.round() as i64).max(1), (schedule.ease * 1000.0).round() as i64)
+            }
+        };
+        tx.execute(
+            "INSERT INTO cards VALUES (?1, ?2, ?3, 0, ?4, -1, ?5, ?6, ?7, ?8, ?9, ?10, ?11, 0, 0, 0, 0, '')",
+            params![card_id, note_id, deck_ids[&word.category], now_s, card_type, queue, due, interval, factor,
+                    schedule.attempts.len() as i64, schedule.lapses],
+        )?;
+        
+        if include_history {
+            for (i, (at, correct, ms)) in schedule.attempts.iter().enumerate() {
+                // Log ids are answer times in milliseconds and must be unique; answers in the
+                // same millisecond move to the next free one
+                let mut revlog_id = at.and_utc().timestamp_millis();
+                while !revlog_ids.insert(revlog_id) {
+                    revlog_id += 1;
+                }
+                let (ease, log_type) = (if *correct { 3 } else { 1 }, if i == 0 { 0 } else { 1 });
+                tx.execute(
+                    "INSERT INTO revlog VALUES (?1, ?2, -1, ?3, 0, 0, ?4, ?5, ?6)",
+                    params![revlog_id, card_id, ease, factor, (*ms).clamp(0, MAX_ANSWER_MS), log_type],
+                )?;
+            }
+        }
+    }
+    tx.commit()
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "export_to_anki", signature = (db_path, categories, out_path, include_history=true))]
+pub fn py_export_to_anki(db_path: &str, categories: Option<Vec<String>>, out_path: &str, include_history: bool) -> PyResult<usize> {
+    export_to_anki(db_path, categories, out_path, include_history)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
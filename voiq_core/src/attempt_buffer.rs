@@ -0,0 +1,154 @@
+//! Buffered attempt logging for rapid-fire quiz modes.
+//!
+//! `AttemptBuffer` keeps answers in memory and writes them in one transaction when the
+//! batch is full, when `flush_interval_ms` has passed since the last write (checked as
+//! answers come in), on `flush()`, and when a `with` block ends.
+//!
+//! Crash safety: buffered attempts only reach the database on flush. If the process dies,
+//! at most one batch (or `flush_interval_ms` worth of answers) is lost; flushed attempts
+//! are never partially written. Call `flush()` at session end and before quitting.
+
+use pyo3::prelude::*;
+use std::time::{Duration, Instant};
+use crate::progress::{insert_attempt, AttemptRecord};
+use crate::db::open_database;
+
+/// Default batch size and flush interval
+pub const DEFAULT_MAX_BATCH: usize = 20;
+pub const DEFAULT_FLUSH_INTERVAL_MS: u64 = 5_000;
+
+/// In-memory queue of attempts written to the database in batches
+#[pyclass]
+pub struct AttemptBuffer {
+    db_path: String,
+    pending: Vec<AttemptRecord>,
+    #[pyo3(get)]
+    max_batch: usize,
+    #[pyo3(get)]
+    flush_interval_ms: u64,
+    last_flush: Instant,
+}
+
+impl AttemptBuffer {
+    pub fn new(db_path: &str, max_batch: usize, flush_interval_ms: u64) -> Self {
+        AttemptBuffer {
+            db_path: db_path.to_string(),
+            pending: Vec::new(),
+            max_batch: max_batch.max(1),
+            flush_interval_ms,
+            last_flush: Instant::now(),
+        }
+    }
+    
+    /// Queue an attempt, flushing if the batch is full or the interval has passed
+    pub fn push(&mut self, mut record: AttemptRecord) -> Result<usize, String> {
+        if record.attempted_at.is_none() {
+            record.attempted_at = Some(chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string());
+        }
+        self.pending.push(record);
+        
+        let due = self.last_flush.elapsed() >= Duration::from_millis(self.flush_interval_ms);
+        if self.pending.len() >= self.max_batch || due {
+            self.flush()
+        } else {
+            Ok(0)
+        }
+    }
+    
+    /// Write all pending attempts in one transaction; returns how many were written.
+    /// On failure the attempts stay queued for the next flush.
+    pub fn flush(&mut self) -> Result<usize, String> {
+        self.last_flush = Instant::now();
+        if self.pending.is_empty() {
+            return Ok(0);
+        }
+        
+        let mut conn = open_database(&self.db_path)
+            .map_err(|e| format!("Failed to open database: {}", e))?;
+        let tx = conn.transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        for record in &self.pending {
+            insert_attempt(&tx, record)
+                .map_err(|e| format!("Failed to save attempt: {}", e))?;
+        }
+        tx.commit().map_err(|e| format!("Failed to commit attempts: {}", e))?;
+        
+        let written = self.pending.len();
+        self.pending.clear();
+        Ok(written)
+    }
+}
+
+#[pymethods]
+impl AttemptBuffer {
+    #[new]
+    #[pyo3(signature = (db_path, max_batch=DEFAULT_MAX_BATCH, flush_interval_ms=DEFAULT_FLUSH_INTERVAL_MS))]
+    fn py_new(db_path: &str, max_batch: usize, flush_interval_ms: u64) -> Self {
+        AttemptBuffer::new(db_path, max_batch, flush_interval_ms)
+    }
+    
+    /// Queue an attempt (same arguments as `save_attempt`); returns the number of
+    /// attempts written if this triggered a flush
+    #[pyo3(name = "save_attempt", signature = (word_id, mode, question_type, is_correct, user_answer, expected_answer, time_taken_ms=None, client_context=None, session_id=None, first_answer=None, profile_id=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn py_save_attempt(
+        &mut self,
+        word_id: i64,
+        mode: &str,
+        question_type: &str,
+        is_correct: bool,
+        user_answer: &str,
+        expected_answer: &str,
+        time_taken_ms: Option<i64>,
+        client_context: Option<&Bound<'_, PyAny>>,
+        session_id: Option<&str>,
+        first_answer: Option<&str>,
+        profile_id: Option<i64>,
+    ) -> PyResult<usize> {
+        let client_context = client_context
+            .filter(|c| !c.is_none())
+            .map(crate::db::py_to_json)
+            .transpose()?
+            .map(|c| c.to_string());
+        let record = AttemptRecord {
+            word_id,
+            mode: mode.to_string(),
+            question_type: question_type.to_string(),
+            is_correct,
+            user_answer: user_answer.to_string(),
+            expected_answer: expected_answer.to_string(),
+            time_taken_ms,
+            client_context,
+            session_id: session_id.map(String::from),
+            first_answer: first_answer.map(String::from),
+            profile_id,
+            attempted_at: None,
+        };
+        self.push(record).map_err(pyo3::exceptions::PyRuntimeError::new_err)
+    }
+    
+    #[pyo3(name = "flush")]
+    fn py_flush(&mut self) -> PyResult<usize> {
+        self.flush().map_err(pyo3::exceptions::PyRuntimeError::new_err)
+    }
+    
+    /// Attempts waiting to be written
+    #[getter]
+    fn pending(&self) -> usize {
+        self.pending.len()
+    }
+    
+    fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+    
+    #[pyo3(signature = (*_args))]
+    fn __exit__(&mut self, _args: &Bound<'_, pyo3::types::PyTuple>) -> PyResult<bool> {
+        self.py_flush()?;
+        Ok(false)
+    }
+    
+    fn __repr__(&self) -> String {
+        format!("AttemptBuffer(pending={}, max_batch={})", self.pending.len(), self.max_batch)
+    }
+}
@@ -0,0 +1,172 @@
+//! Statistical audit of question generation.
+//!
+//! `audit_generation` builds quizzes until it has a large sample of questions and reports
+//! how they are spread: over categories, question types, answer positions and words, and
+//! how often the same distractors come back. Comparing reports before and after a change
+//! to word selection or distractor drawing shows bias it introduced. Quizzes are built
+//! on an in-memory copy of the database, so pinned words stay pinned.
+
+use pyo3::prelude::*;
+use std::collections::{HashMap, HashSet};
+use crate::backup::backup_database;
+use crate::db::{close_memory_database, get_all_words, open_database, MEMORY_DATABASE};
+use crate::questions::MCQQuestion;
+use crate::quiz::{build_quiz, QuizOptions};
+
+/// Number of answer positions in a generated question
+const OPTION_COUNT: usize = 4;
+
+/// Most reused distractors listed in a report
+const TOP_DISTRACTORS: usize = 20;
+
+/// Distribution of a sample of generated questions
+#[pyclass]
+#[derive(Debug, Clone, Default)]
+pub struct GenerationAudit {
+    #[pyo3(get)]
+    pub questions: usize,
+    #[pyo3(get)]
+    pub quizzes: usize,
+    /// Questions per category of the word asked about
+    #[pyo3(get)]
+    pub category_counts: HashMap<String, usize>,
+    /// Words per category, for comparison with `category_counts`
+    #[pyo3(get)]
+    pub category_sizes: HashMap<String, usize>,
+    #[pyo3(get)]
+    pub question_type_counts: HashMap<String, usize>,
+    /// Questions with the answer at each option position
+    #[pyo3(get)]
+    pub correct_index_counts: Vec<usize>,
+    /// Different words asked about
+    #[pyo3(get)]
+    pub distinct_words: usize,
+    /// Questions about the most frequently asked word
+    #[pyo3(get)]
+    pub max_word_count: usize,
+    /// Distractors shown in total
+    #[pyo3(get)]
+    pub distractors: usize,
+    #[pyo3(get)]
+    pub distinct_distractors: usize,
+    /// Most reused distractors with the number of questions showing them
+    #[pyo3(get)]
+    pub top_distractors: Vec<(String, usize)>,
+}
+
+#[pymethods]
+impl GenerationAudit {
+    /// Pearson's chi-squared statistic of the answer positions against a uniform spread;
+    /// above 7.81 (3 degrees of freedom, p < 0.05) the positions are likely biased
+    #[getter]
+    pub fn correct_index_chi_square(&self) -> f64 {
+        let total: usize = self.correct_index_counts.iter().sum();
+        if total == 0 {
+            return 0.0;
+        }
+        let expected = total as f64 / self.correct_index_counts.len() as f64;
+        self.correct_index_counts.iter()
+            .map(|&observed| (observed as f64 - expected).powi(2) / expected)
+            .sum()
+    }
+    
+    /// Average number of questions each distractor appeared in
+    #[getter]
+    pub fn distractor_reuse(&self) -> f64 {
+        if self.distinct_distractors == 0 {
+            return 0.0;
+        }
+        self.distractors as f64 / self.distinct_distractors as f64
+    }
+    
+    fn __repr__(&self) -> String {
+        format!("GenerationAudit(questions={}, categories={}, correct_index_counts={:?}, distractor_reuse={:.2})",
+                self.questions, self.category_counts.len(), self.correct_index_counts, self.distractor_reuse())
+    }
+}
+
+/// Build quizzes with `options` until `samples` questions were generated (or quizzes come
+/// back empty) and summarize them
+pub fn audit_generation(db_path: &str, samples: usize, options: &QuizOptions) -> Result<GenerationAudit, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let words = get_all_words(&conn)
+        .map_err(|e| format!("Failed to get all words: {}", e))?;
+    drop(conn);
+    
+    let copy_path = format!("{}voiq-audit-{}", MEMORY_DATABASE, std::process::id());
+    backup_database(db_path, &copy_path)?;
+    let sample = sample_questions(&copy_path, samples, options);
+    close_memory_database(&copy_path);
+    let (questions, quizzes) = sample?;
+    
+    let category_of: HashMap<i64, &str> = words.iter().map(|w| (w.id, w.category.as_str())).collect();
+    let mut audit = GenerationAudit {
+        questions: questions.len(),
+        quizzes,
+        correct_index_counts: vec![0; OPTION_COUNT],
+        ..Default::default()
+    };
+    for word in &words {
+        *audit.category_sizes.entry(word.category.clone()).or_default() += 1;
+    }
+    
+    let mut word_counts: HashMap<i64, usize> = HashMap::new();
+    let mut distractor_counts: HashMap<&str, usize> = HashMap::new();
+    for question in &questions {
+        let category = category_of.get(&question.word_id).copied().unwrap_or_default();
+        *audit.category_counts.entry(category.to_string()).or_default() += 1;
+        *audit.question_type_counts.entry(question.question_type.clone()).or_default() += 1;
+        if question.correct_index >= audit.correct_index_counts.len() {
+            audit.correct_index_counts.resize(question.correct_index + 1, 0);
+        }
+        audit.correct_index_counts[question.correct_index] += 1;
+        *word_counts.entry(question.word_id).or_default() += 1;
+        
+        // An option repeated within one question counts once
+        let shown: HashSet<&str> = question.options.iter().enumerate()
+            .filter(|(i, _)| *i != question.correct_index)
+            .map(|(_, option)| option.as_str())
+            .collect();
+        audit.distractors += shown.len();
+        for distractor in shown {
+            *distractor_counts.entry(distractor).or_default() += 1;
+        }
+    }
+    
+    audit.distinct_words = word_counts.len();
+    audit.max_word_count = word_counts.values().copied().max().unwrap_or(0);
+    audit.distinct_distractors = distractor_counts.len();
+    let mut top: Vec<(String, usize)> = distractor_counts.into_iter()
+        .map(|(distractor, count)| (distractor.to_string(), count))
+        .collect();
+    top.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    top.truncate(TOP_DISTRACTORS);
+    audit.top_distractors = top;
+    Ok(audit)
+}
+
+/// Questions of quizzes built until there are `samples` of them, and the number of quizzes
+fn sample_questions(db_path: &str, samples: usize, options: &QuizOptions) -> Result<(Vec<MCQQuestion>, usize), String> {
+    let mut questions = Vec::with_capacity(samples);
+    let mut quizzes = 0;
+    while questions.len() < samples {
+        let quiz = build_quiz(db_path, options)?;
+        if quiz.is_empty() {
+            break;
+        }
+        quizzes += 1;
+        questions.extend(quiz);
+    }
+    questions.truncate(samples);
+    Ok((questions, quizzes))
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "audit_generation", signature = (db_path, samples=1000, options=None))]
+pub fn py_audit_generation(db_path: &str, samples: usize, options: Option<QuizOptions>) -> PyResult<GenerationAudit> {
+    audit_generation(db_path, samples, &options.unwrap_or_default())
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
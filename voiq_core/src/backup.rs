@@ -0,0 +1,105 @@
+//! Backups of a live database through SQLite's online backup API.
+//!
+//! Copying the file while the app has it open can capture a half-written page; the
+//! backup API copies a consistent snapshot instead, stepping through the pages while
+//! other connections keep working. Backups of an encrypted database are encrypted with
+//! the same passphrase.
+
+use pyo3::prelude::*;
+use rusqlite::backup::Backup;
+use rusqlite::{Connection, OpenFlags};
+use std::path::Path;
+use std::time::Duration;
+use crate::db::{init_database, is_memory_database, open_database, open_database_with_flags, passphrase_for, set_passphrase};
+
+/// Copy all pages of `src` into `dst`, a few at a time so writers aren't blocked for long
+fn copy_pages(src: &Connection, dst: &mut Connection) -> rusqlite::Result<()> {
+    Backup::new(src, dst)?.run_to_completion(5, Duration::from_millis(250), None)
+}
+
+/// Use the database's passphrase for a backup file that has none registered
+fn share_passphrase(db_path: &str, backup_path: &str) -> Result<(), String> {
+    match (passphrase_for(db_path), passphrase_for(backup_path)) {
+        (Some(passphrase), None) => set_passphrase(backup_path, Some(&passphrase)),
+        _ => Ok(()),
+    }
+}
+
+/// Check that a file is a readable VoIQ database before it replaces live data
+fn check_backup(backup_path: &str) -> Result<(), String> {
+    let conn = open_database_with_flags(backup_path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| format!("Failed to open backup: {}", e))?;
+    let integrity: String = conn.query_row("PRAGMA quick_check", [], |row| row.get(0))
+        .map_err(|e| format!("Backup is not a valid database: {}", e))?;
+    if integrity != "ok" {
+        return Err(format!("Backup is damaged: {}", integrity));
+    }
+    let has_vocabulary: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'vocabulary')",
+        [],
+        |row| row.get(0),
+    ).map_err(|e| format!("Failed to read backup: {}", e))?;
+    if !has_vocabulary {
+        return Err(format!("Not a VoIQ database: {}", backup_path));
+    }
+    Ok(())
+}
+
+/// Write a consistent copy of the database to `dest_path` (replacing any file there),
+/// safe to run while the app has the database open
+pub fn backup_database(db_path: &str, dest_path: &str) -> Result<(), String> {
+    if !is_memory_database(db_path) && !Path::new(db_path).exists() {
+        return Err(format!("Database not found: {}", db_path));
+    }
+    if Path::new(db_path) == Path::new(dest_path) {
+        return Err("Backup destination is the database itself".to_string());
+    }
+    share_passphrase(db_path, dest_path)?;
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let mut dest = open_database(dest_path)
+        .map_err(|e| format!("Failed to create backup: {}", e))?;
+    copy_pages(&conn, &mut dest)
+        .map_err(|e| format!("Failed to back up database: {}", e))
+}
+
+/// Replace the database's contents with a backup made by `backup_database`, then bring
+/// the restored schema up to date. The backup is checked before anything is overwritten.
+pub fn restore_database(backup_path: &str, db_path: &str) -> Result<(), String> {
+    if !Path::new(backup_path).exists() {
+        return Err(format!("Backup not found: {}", backup_path));
+    }
+    if Path::new(db_path) == Path::new(backup_path) {
+        return Err("Backup is the database itself".to_string());
+    }
+    share_passphrase(db_path, backup_path)?;
+    check_backup(backup_path)?;
+    
+    let backup = open_database(backup_path)
+        .map_err(|e| format!("Failed to open backup: {}", e))?;
+    let mut conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    copy_pages(&backup, &mut conn)
+        .map_err(|e| format!("Failed to restore database: {}", e))?;
+    drop(conn);
+    
+    init_database(db_path)
+        .map(|_| ())
+        .map_err(|e| format!("Failed to upgrade restored database: {}", e))
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "backup_database")]
+pub fn py_backup_database(db_path: &str, dest_path: &str) -> PyResult<()> {
+    backup_database(db_path, dest_path)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "restore_database")]
+pub fn py_restore_database(backup_path: &str, db_path: &str) -> PyResult<()> {
+    restore_database(backup_path, db_path)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
@@ -0,0 +1,230 @@
+//! SVG chart rendering so lightweight frontends don't need a plotting library
+
+use pyo3::prelude::*;
+use chrono::{Duration, Local, NaiveDate};
+use rusqlite::params;
+use crate::progress::get_daily_attempt_counts;
+use crate::db::open_database;
+
+const WIDTH: f64 = 600.0;
+const HEIGHT: f64 = 220.0;
+const MARGIN_TOP: f64 = 30.0;
+const MARGIN_BOTTOM: f64 = 30.0;
+const MARGIN_X: f64 = 20.0;
+const BAR_COLOR: &str = "#4f7cff";
+const LINE_COLOR: &str = "#2fa66b";
+const TEXT_COLOR: &str = "#333";
+
+/// Escape text for use in XML/HTML content and attributes
+pub fn escape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Chart header shared by all chart kinds
+fn svg_open(title: &str) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" \
+         font-family=\"sans-serif\" font-size=\"11\" role=\"img\" aria-label=\"{t}\">\
+         <title>{t}</title>\
+         <text x=\"{x}\" y=\"18\" font-size=\"14\" font-weight=\"bold\" fill=\"{c}\">{t}</text>\
+         <line x1=\"{x}\" y1=\"{b}\" x2=\"{x2}\" y2=\"{b}\" stroke=\"#ccc\"/>",
+        w = WIDTH, h = HEIGHT, t = escape_xml(title), x = MARGIN_X, x2 = WIDTH - MARGIN_X,
+        c = TEXT_COLOR, b = HEIGHT - MARGIN_BOTTOM,
+    )
+}
+
+/// Format a value for a chart label without needless decimals
+fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 { format!("{}", value as i64) } else { format!("{:.1}", value) }
+}
+
+/// Render a titled bar chart. `max_value` fixes the scale (e.g. 100 for percentages);
+/// otherwise the largest value fills the chart height.
+pub fn bar_chart_svg(title: &str, labels: &[String], values: &[f64], max_value: Option<f64>, unit: &str) -> String {
+    let n = labels.len().min(values.len());
+    let scale_max = max_value
+        .unwrap_or_else(|| values.iter().copied().fold(0.0, f64::max))
+        .max(f64::EPSILON);
+    
+    let plot_height = HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+    let slot = (WIDTH - 2.0 * MARGIN_X) / n.max(1) as f64;
+    let bar_width = slot * 0.6;
+    
+    let baseline = HEIGHT - MARGIN_BOTTOM;
+    let mut svg = svg_open(title);
+    
+    for i in 0..n {
+        let value = values[i].max(0.0);
+        let bar_height = (value / scale_max).min(1.0) * plot_height;
+        let x = MARGIN_X + slot * i as f64 + (slot - bar_width) / 2.0;
+        let center = x + bar_width / 2.0;
+        
+        svg.push_str(&format!(
+            "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"{}\" rx=\"2\"/>",
+            x, baseline - bar_height, bar_width, bar_height, BAR_COLOR,
+        ));
+        svg.push_str(&format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" fill=\"{}\">{}{}</text>",
+            center, baseline - bar_height - 4.0, TEXT_COLOR, format_value(values[i]), escape_xml(unit),
+        ));
+        svg.push_str(&format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" fill=\"{}\">{}</text>",
+            center, baseline + 16.0, TEXT_COLOR, escape_xml(&labels[i]),
+        ));
+    }
+    
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Render a titled line chart. `None` values leave a gap in the line (e.g. days without
+/// attempts). With many points only every few x-axis labels are drawn.
+pub fn line_chart_svg(title: &str, labels: &[String], values: &[Option<f64>], max_value: Option<f64>, unit: &str) -> String {
+    let n = labels.len().min(values.len());
+    let scale_max = max_value
+        .unwrap_or_else(|| values.iter().flatten().copied().fold(0.0, f64::max))
+        .max(f64::EPSILON);
+    
+    let plot_height = HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+    let baseline = HEIGHT - MARGIN_BOTTOM;
+    let step = (WIDTH - 2.0 * MARGIN_X) / n.max(1) as f64;
+    let label_every = n.div_ceil(10).max(1);
+    
+    let mut svg = svg_open(title);
+    let mut segment: Vec<String> = Vec::new();
+    let mut segments: Vec<Vec<String>> = Vec::new();
+    
+    for i in 0..n {
+        let x = MARGIN_X + step * i as f64 + step / 2.0;
+        match values[i] {
+            Some(value) => {
+                let y = baseline - (value.max(0.0) / scale_max).min(1.0) * plot_height;
+                segment.push(format!("{:.1},{:.1}", x, y));
+                svg.push_str(&format!(
+                    "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"3\" fill=\"{}\"><title>{}: {}{}</title></circle>",
+                    x, y, LINE_COLOR, escape_xml(&labels[i]), format_value(value), escape_xml(unit),
+                ));
+            }
+            None => {
+                if !segment.is_empty() {
+                    segments.push(std::mem::take(&mut segment));
+                }
+            }
+        }
+        if i.is_multiple_of(label_every) {
+            svg.push_str(&format!(
+                "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" fill=\"{}\">{}</text>",
+                x, baseline + 16.0, TEXT_COLOR, escape_xml(&labels[i]),
+            ));
+        }
+    }
+    if !segment.is_empty() {
+        segments.push(segment);
+    }
+    
+    for points in segments {
+        svg.push_str(&format!(
+            "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>",
+            points.join(" "), LINE_COLOR,
+        ));
+    }
+    
+    svg.push_str(&format!(
+        "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\" fill=\"#888\">{}{}</text>",
+        WIDTH - MARGIN_X, MARGIN_TOP - 2.0, format_value(scale_max), escape_xml(unit),
+    ));
+    svg.push_str("</svg>");
+    svg
+}
+
+/// `days` consecutive dates starting at `start`
+fn date_range(start: NaiveDate, days: i64) -> Vec<NaiveDate> {
+    (0..days.max(1)).map(|i| start + Duration::days(i)).collect()
+}
+
+/// Daily accuracy over the last `days` days as a line chart; days without attempts are gaps
+pub fn render_accuracy_chart_svg(db_path: &str, days: i64) -> Result<String, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    
+    let today = Local::now().date_naive();
+    let dates = date_range(today - Duration::days(days.max(1) - 1), days);
+    let since = dates[0].format("%Y-%m-%d").to_string();
+    let counts = get_daily_attempt_counts(&conn, &since)
+        .map_err(|e| format!("Failed to get attempts: {}", e))?;
+    
+    let labels: Vec<String> = dates.iter().map(|d| d.format("%m-%d").to_string()).collect();
+    let values: Vec<Option<f64>> = dates.iter()
+        .map(|d| match counts.get(&d.format("%Y-%m-%d").to_string()) {
+            Some(&(total, correct)) if total > 0 => Some((correct as f64 / total as f64 * 100.0).round()),
+            _ => None,
+        })
+        .collect();
+    
+    Ok(line_chart_svg("Accuracy over time", &labels, &values, Some(100.0), "%"))
+}
+
+/// Number of words coming due on each of the next `days` days as a bar chart.
+/// Overdue words are counted on today. Empty when the database has no SRS data.
+pub fn render_due_forecast_svg(db_path: &str, days: i64) -> Result<String, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    
+    let today = Local::now().date_naive();
+    let dates = date_range(today, days);
+    let mut counts = vec![0.0; dates.len()];
+    
+    let has_srs: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = 'srs_state'",
+        [],
+        |row| row.get(0),
+    ).map_err(|e| format!("Failed to read schema: {}", e))?;
+    
+    if has_srs {
+        let last = dates[dates.len() - 1].format("%Y-%m-%d").to_string();
+        let mut stmt = conn.prepare(
+            "SELECT date(due_at, 'localtime') AS day, COUNT(*) FROM srs_state
+             WHERE day <= ?1 GROUP BY day"
+        ).map_err(|e| format!("Failed to query due dates: {}", e))?;
+        let rows = stmt.query_map(params![last], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))
+            .map_err(|e| format!("Failed to query due dates: {}", e))?;
+        
+        for (day, count) in rows.filter_map(|r| r.ok()) {
+            let Ok(date) = NaiveDate::parse_from_str(&day, "%Y-%m-%d") else { continue };
+            let index = (date - today).num_days().max(0) as usize;
+            if let Some(slot) = counts.get_mut(index) {
+                *slot += count as f64;
+            }
+        }
+    }
+    
+    let labels: Vec<String> = dates.iter().map(|d| d.format("%m-%d").to_string()).collect();
+    Ok(bar_chart_svg("Reviews due", &labels, &counts, None, ""))
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "render_accuracy_chart_svg", signature = (db_path, days=30))]
+pub fn py_render_accuracy_chart_svg(db_path: &str, days: i64) -> PyResult<String> {
+    render_accuracy_chart_svg(db_path, days)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "render_due_forecast_svg", signature = (db_path, days=14))]
+pub fn py_render_due_forecast_svg(db_path: &str, days: i64) -> PyResult<String> {
+    render_due_forecast_svg(db_path, days)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
@@ -0,0 +1,185 @@
+//! Cloze (fill-in-the-blank) cards authored from pasted text
+
+use pyo3::prelude::*;
+use rand::seq::SliceRandom;
+use rusqlite::params;
+use crate::db::{add_word, default_category_for, get_all_words, init_database};
+use crate::question_bank::{add_custom_question, find_word_id};
+
+/// Placeholder replacing the target word in a cloze stem
+pub const CLOZE_BLANK: &str = "_____";
+
+/// Source recorded for example sentences taken from pasted text
+const CLOZE_SOURCE: &str = "cloze";
+
+/// Outcome of `create_cloze_cards`
+#[pyclass]
+#[derive(Debug, Clone, Default)]
+pub struct ClozeImport {
+    #[pyo3(get)]
+    pub questions_created: usize,
+    #[pyo3(get)]
+    pub examples_added: usize,
+    /// Target words that weren't in the vocabulary and were added to the category
+    #[pyo3(get)]
+    pub words_added: usize,
+    /// Target words that don't appear in the text
+    #[pyo3(get)]
+    pub not_found: Vec<String>,
+}
+
+#[pymethods]
+impl ClozeImport {
+    fn __repr__(&self) -> String {
+        format!("ClozeImport(questions={}, examples={}, words_added={}, not_found={})",
+                self.questions_created, self.examples_added, self.words_added, self.not_found.len())
+    }
+}
+
+/// Split a paragraph into sentences at ./!/? followed by whitespace, CJK full stops and newlines
+pub fn split_sentences(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '\n' || c == '\r' {
+            sentences.push(std::mem::take(&mut current));
+            continue;
+        }
+        current.push(c);
+        
+        let next_is_break = chars.get(i + 1).is_none_or(|n| n.is_whitespace());
+        let ends = matches!(c, '。' | '！' | '？') || (matches!(c, '.' | '!' | '?' | '…') && next_is_break);
+        if ends {
+            sentences.push(std::mem::take(&mut current));
+        }
+    }
+    sentences.push(current);
+    
+    sentences.into_iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Letters/digits of alphabetic scripts, where a match must not continue into a longer word.
+/// CJK text has no spaces, so it is matched anywhere.
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() && (c as u32) < 0x2E80
+}
+
+/// Char range of the first whole-word, case-insensitive occurrence of `word` in `sentence`
+fn find_word(sentence: &[char], word: &str) -> Option<(usize, usize)> {
+    let target: Vec<char> = word.chars().collect();
+    if target.is_empty() || target.len() > sentence.len() {
+        return None;
+    }
+    let same = |a: char, b: char| a.to_lowercase().eq(b.to_lowercase());
+    
+    (0..=sentence.len() - target.len()).find_map(|start| {
+        let end = start + target.len();
+        let matches = sentence[start..end].iter().zip(&target).all(|(a, b)| same(*a, *b));
+        let bounded_left = start == 0 || !(is_word_char(sentence[start - 1]) && is_word_char(target[0]));
+        let bounded_right = end == sentence.len() || !(is_word_char(sentence[end]) && is_word_char(target[target.len() - 1]));
+        (matches && bounded_left && bounded_right).then_some((start, end))
+    })
+}
+
+/// Find each target word in a pasted paragraph, store the sentences it appears in as
+/// examples, and create an MCQ cloze question per sentence (the word blanked out, with
+/// other vocabulary words as distractors). Targets missing from the vocabulary are added
+/// to `category`. Questions are only created when at least 3 distractors are available.
+pub fn create_cloze_cards(text: &str, target_words: &[String], db_path: &str, category: &str) -> Result<ClozeImport, String> {
+    let mut conn = init_database(db_path)
+        .map_err(|e| format!("Failed to init database: {}", e))?;
+    let tx = conn.transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    
+    let sentences: Vec<Vec<char>> = split_sentences(text).iter().map(|s| s.chars().collect()).collect();
+    let mut result = ClozeImport::default();
+    let mut cards: Vec<(i64, String, String)> = Vec::new();
+    let mut seen: Vec<String> = Vec::new();
+    
+    for target in target_words {
+        let target = target.trim();
+        if target.is_empty() || seen.contains(&target.to_lowercase()) {
+            continue;
+        }
+        seen.push(target.to_lowercase());
+        
+        let found: Vec<(String, String)> = sentences.iter()
+            .filter_map(|sentence| {
+                let (start, end) = find_word(sentence, target)?;
+                let mut stem: String = sentence[..start].iter().collect();
+                stem.push_str(CLOZE_BLANK);
+                stem.extend(&sentence[end..]);
+                Some((sentence.iter().collect(), stem))
+            })
+            .collect();
+        if found.is_empty() {
+            result.not_found.push(target.to_string());
+            continue;
+        }
+        
+        let word_id = match find_word_id(&tx, target, category).map_err(|e| format!("Failed to look up word: {}", e))? {
+            Some(id) => id,
+            None => {
+                result.words_added += 1;
+                add_word(&tx, target, "", "", "", category, None)
+                    .map_err(|e| format!("Failed to add word: {}", e))?
+            }
+        };
+        
+        for (sentence, stem) in found {
+            tx.execute(
+                "INSERT INTO examples (word_id, sentence, source) VALUES (?1, ?2, ?3)",
+                params![word_id, sentence, CLOZE_SOURCE],
+            ).map_err(|e| format!("Failed to save example: {}", e))?;
+            result.examples_added += 1;
+            cards.push((word_id, target.to_string(), stem));
+        }
+    }
+    
+    // Distractors come from the same category first, then the rest of the vocabulary
+    let mut pool = get_all_words(&tx).map_err(|e| format!("Failed to get all words: {}", e))?;
+    pool.sort_by_key(|w| w.category != category);
+    let mut rng = rand::thread_rng();
+    
+    for (word_id, answer, stem) in cards {
+        let mut distractors: Vec<String> = Vec::new();
+        for word in &pool {
+            if distractors.len() == 3 {
+                break;
+            }
+            let lower = word.word.to_lowercase();
+            if lower != answer.to_lowercase() && !distractors.iter().any(|d| d.to_lowercase() == lower) {
+                distractors.push(word.word.clone());
+            }
+        }
+        if distractors.len() < 3 {
+            continue;
+        }
+        
+        let mut options = distractors;
+        options.push(answer.clone());
+        options.shuffle(&mut rng);
+        let correct_index = options.iter().position(|o| *o == answer).unwrap_or(0);
+        
+        add_custom_question(&tx, &stem, &options, correct_index, Some(word_id), category)?;
+        result.questions_created += 1;
+    }
+    
+    tx.commit().map_err(|e| format!("Failed to commit cloze cards: {}", e))?;
+    Ok(result)
+}
+
+// ============= Python Binding =============
+
+#[pyfunction]
+#[pyo3(name = "create_cloze_cards", signature = (text, target_words, db_path, category=None))]
+pub fn py_create_cloze_cards(text: &str, target_words: Vec<String>, db_path: &str, category: Option<String>) -> PyResult<ClozeImport> {
+    let category = category.unwrap_or_else(|| default_category_for(db_path));
+    create_cloze_cards(text, &target_words, db_path, &category)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
@@ -0,0 +1,90 @@
+//! Unicode-aware alphabetical ordering.
+//!
+//! SQLite's default collation compares bytes, which puts "Äpfel" after "Zebra" and
+//! "apple" after "Zebra". The `UNICODE` collation registered on every connection sorts
+//! like a dictionary instead, in the spirit of ICU's root collation for Latin text:
+//! letters compare without case and accents first, then unaccented before accented,
+//! then lowercase before uppercase. Other scripts keep their code point order within
+//! the script, compared case-insensitively.
+
+use rusqlite::Connection;
+use std::cmp::Ordering;
+
+/// Name of the collation, for `ORDER BY ... COLLATE UNICODE`
+pub const UNICODE_COLLATION: &str = "UNICODE";
+
+/// Base letters of U+00C0..=U+00FF ('\0' where the character has no single base letter)
+const LATIN_1_BASE: &str = "AAAAAA\0CEEEEIIIIDNOOOOO\0OUUUUY\0\0aaaaaa\0ceeeeiiiidnooooo\0ouuuuy\0y";
+
+/// Base letters of Latin Extended-A, U+0100..=U+017F
+const LATIN_EXTENDED_A_BASE: &str = "AaAaAaCcCcCcCcDdDdEeEeEeEeEeGgGgGgGgHhHhIiIiIiIiIiIiJjKkkLlLlLlLlLlNnNnNnnNnOoOoOoOoRrRrRrSsSsSsSsTtTtTtUuUuUuUuUuUuWwYyYZzZzZzs";
+
+/// Combining diacritical marks, which decomposed text carries as separate characters
+fn is_combining_mark(c: char) -> bool {
+    ('\u{0300}'..='\u{036F}').contains(&c)
+}
+
+/// The letters a character sorts as, ignoring accents: "A" for 'Ä', "ss" for 'ß'.
+/// None for characters that are their own base.
+fn base_letters(c: char) -> Option<&'static str> {
+    let table_entry = |table: &'static str, index: usize| {
+        table.get(index..index + 1).filter(|base| *base != "\0")
+    };
+    match c {
+        'Æ' => Some("AE"),
+        'æ' => Some("ae"),
+        'Œ' => Some("OE"),
+        'œ' => Some("oe"),
+        'ß' => Some("ss"),
+        '\u{00C0}'..='\u{00FF}' => table_entry(LATIN_1_BASE, c as usize - 0xC0),
+        '\u{0100}'..='\u{017F}' => table_entry(LATIN_EXTENDED_A_BASE, c as usize - 0x100),
+        _ => None,
+    }
+}
+
+/// Fold a character to the letter it's filed under in an index: 'ä' and 'Ä' become 'A'.
+/// Characters without a single base letter are only uppercased.
+pub fn base_initial(c: char) -> char {
+    let base = base_letters(c).and_then(|b| b.chars().next()).unwrap_or(c);
+    let mut upper = base.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(u), None) => u,
+        _ => base,
+    }
+}
+
+/// Characters compared at the first level: accents and case removed
+fn primary(text: &str) -> impl Iterator<Item = char> + '_ {
+    text.chars()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(|c| {
+            let base = base_letters(c);
+            base.unwrap_or("").chars().chain(base.is_none().then_some(c))
+        })
+        .flat_map(char::to_lowercase)
+}
+
+/// Accent weights compared at the second level: 0 for a plain letter, 1 for an accented one
+fn accents(text: &str) -> impl Iterator<Item = u8> + '_ {
+    text.chars().map(|c| (base_letters(c).is_some() || is_combining_mark(c)) as u8)
+}
+
+/// Dictionary comparison of two strings (see the module docs). Falls back to byte order
+/// so distinct strings never compare equal.
+pub fn unicode_compare(a: &str, b: &str) -> Ordering {
+    primary(a).cmp(primary(b))
+        .then_with(|| accents(a).cmp(accents(b)))
+        .then_with(|| a.chars().map(char::is_uppercase).cmp(b.chars().map(char::is_uppercase)))
+        .then_with(|| a.cmp(b))
+}
+
+/// Key under which words count as the same entry in normalized uniqueness mode: case,
+/// accents and surrounding or repeated whitespace are ignored ("Café " and "cafe" match)
+pub fn word_key(word: &str) -> String {
+    primary(&word.split_whitespace().collect::<Vec<_>>().join(" ")).collect()
+}
+
+/// Make `COLLATE UNICODE` available on a connection
+pub fn register_collation(conn: &Connection) -> rusqlite::Result<()> {
+    conn.create_collation(UNICODE_COLLATION, unicode_compare)
+}
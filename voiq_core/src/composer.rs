@@ -0,0 +1,258 @@
+//! Quiz composer - simulated annealing over question selection and order
+//! for exam builds with several soft constraints at once
+
+use rand::Rng;
+use rand::seq::SliceRandom;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use strsim::jaro_winkler;
+use crate::questions::MCQQuestion;
+
+/// A candidate question with the metadata the composer scores on
+#[derive(Debug, Clone)]
+pub struct QuizItem {
+    pub question: MCQQuestion,
+    pub word: String,
+    pub category: String,
+    /// 0.0 (easy) to 1.0 (hard)
+    pub difficulty: f64,
+}
+
+/// Target shape of difficulty across the quiz
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DifficultyCurve {
+    /// No ordering constraint on difficulty
+    Flat,
+    /// Easy questions first, hardest at the end
+    Ascending,
+    /// Build up to the hardest questions in the middle, then ease off
+    Peak,
+}
+
+impl DifficultyCurve {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "" | "flat" | "none" => Ok(DifficultyCurve::Flat),
+            "ascending" => Ok(DifficultyCurve::Ascending),
+            "peak" => Ok(DifficultyCurve::Peak),
+            _ => Err(format!("Unknown difficulty curve: {}", name)),
+        }
+    }
+    
+    /// Target difficulty at a position, as a fraction of the difficulty range
+    fn target(self, position: usize, len: usize) -> Option<f64> {
+        let x = if len > 1 { position as f64 / (len - 1) as f64 } else { 0.0 };
+        match self {
+            DifficultyCurve::Flat => None,
+            DifficultyCurve::Ascending => Some(x),
+            DifficultyCurve::Peak => Some(1.0 - (2.0 * x - 1.0).abs()),
+        }
+    }
+}
+
+/// Constraints the composer tries to satisfy
+#[derive(Debug, Clone)]
+pub struct CompositionConstraints {
+    /// Spread questions evenly over the categories present in the pool
+    pub category_coverage: bool,
+    pub difficulty_curve: DifficultyCurve,
+    /// Penalize adjacent questions about confusable words
+    pub separate_confusables: bool,
+    /// Similarity above which two words count as confusable
+    pub confusable_threshold: f64,
+    /// Penalize adjacent questions from the same category (interleaved practice)
+    pub interleave_categories: bool,
+}
+
+impl Default for CompositionConstraints {
+    fn default() -> Self {
+        CompositionConstraints {
+            category_coverage: true,
+            difficulty_curve: DifficultyCurve::Flat,
+            separate_confusables: true,
+            confusable_threshold: 0.85,
+            interleave_categories: false,
+        }
+    }
+}
+
+/// Whether two words are easy to mix up (same text or very similar spelling)
+pub fn is_confusable(a: &str, b: &str, threshold: f64) -> bool {
+    let (a, b) = (a.trim().to_lowercase(), b.trim().to_lowercase());
+    !a.is_empty() && !b.is_empty() && (a == b || jaro_winkler(&a, &b) >= threshold)
+}
+
+struct Scorer<'a> {
+    pool: &'a [QuizItem],
+    constraints: &'a CompositionConstraints,
+    category_target: HashMap<&'a str, f64>,
+    difficulty_range: (f64, f64),
+    confusable: Vec<Vec<bool>>,
+}
+
+impl<'a> Scorer<'a> {
+    fn new(pool: &'a [QuizItem], count: usize, constraints: &'a CompositionConstraints) -> Self {
+        let mut categories: Vec<&str> = pool.iter().map(|i| i.category.as_str()).collect();
+        categories.sort_unstable();
+        categories.dedup();
+        let share = count as f64 / categories.len().max(1) as f64;
+        let category_target = categories.into_iter().map(|c| (c, share)).collect();
+        
+        let lo = pool.iter().map(|i| i.difficulty).fold(f64::INFINITY, f64::min);
+        let hi = pool.iter().map(|i| i.difficulty).fold(f64::NEG_INFINITY, f64::max);
+        
+        let confusable = if constraints.separate_confusables {
+            pool.iter()
+                .map(|a| pool.iter().map(|b| is_confusable(&a.word, &b.word, constraints.confusable_threshold)).collect())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        
+        Scorer { pool, constraints, category_target, difficulty_range: (lo, hi), confusable }
+    }
+    
+    /// Total penalty of a selection (0.0 means every constraint is met)
+    fn cost(&self, selection: &[usize]) -> f64 {
+        let mut cost = 0.0;
+        
+        if self.constraints.category_coverage {
+            let mut counts: HashMap<&str, f64> = HashMap::new();
+            for &i in selection {
+                *counts.entry(self.pool[i].category.as_str()).or_default() += 1.0;
+            }
+            // Fractional targets can't be hit exactly, so only penalize misses of a whole question
+            cost += self.category_target.iter()
+                .map(|(c, target)| ((counts.get(c).copied().unwrap_or(0.0) - target).abs() - 0.5).max(0.0))
+                .sum::<f64>();
+        }
+        
+        let (lo, hi) = self.difficulty_range;
+        if hi > lo {
+            for (pos, &i) in selection.iter().enumerate() {
+                if let Some(target) = self.constraints.difficulty_curve.target(pos, selection.len()) {
+                    let actual = (self.pool[i].difficulty - lo) / (hi - lo);
+                    cost += (actual - target).abs();
+                }
+            }
+        }
+        
+        if self.constraints.separate_confusables {
+            cost += 2.0 * selection.windows(2).filter(|w| self.confusable[w[0]][w[1]]).count() as f64;
+        }
+        
+        if self.constraints.interleave_categories {
+            cost += selection.windows(2)
+                .filter(|w| self.pool[w[0]].category == self.pool[w[1]].category)
+                .count() as f64;
+        }
+        
+        cost
+    }
+}
+
+/// Select and order `count` items from the pool to best satisfy the constraints within a time budget
+pub fn compose(pool: Vec<QuizItem>, count: usize, constraints: &CompositionConstraints, time_budget: Duration) -> Vec<QuizItem> {
+    let count = count.min(pool.len());
+    if count == 0 {
+        return Vec::new();
+    }
+    
+    let mut rng = rand::thread_rng();
+    let scorer = Scorer::new(&pool, count, constraints);
+    
+    let mut order: Vec<usize> = (0..pool.len()).collect();
+    order.shuffle(&mut rng);
+    let mut current: Vec<usize> = order[..count].to_vec();
+    let mut unused: Vec<usize> = order[count..].to_vec();
+    let mut current_cost = scorer.cost(&current);
+    
+    let mut best = current.clone();
+    let mut best_cost = current_cost;
+    
+    let deadline = Instant::now() + time_budget;
+    let mut temperature = 1.0;
+    let mut iteration: u64 = 0;
+    
+    while best_cost > 0.0 {
+        iteration += 1;
+        if iteration.is_multiple_of(64) && Instant::now() >= deadline {
+            break;
+        }
+        
+        // Neighbour: swap two positions, or replace one selected item with an unused one
+        let mut candidate = current.clone();
+        let mut swapped_in = None;
+        let can_swap = count >= 2;
+        if !can_swap && unused.is_empty() {
+            break;
+        }
+        if can_swap && (unused.is_empty() || rng.gen_bool(0.5)) {
+            let (a, b) = (rng.gen_range(0..count), rng.gen_range(0..count));
+            candidate.swap(a, b);
+        } else {
+            let pos = rng.gen_range(0..count);
+            let u = rng.gen_range(0..unused.len());
+            swapped_in = Some((pos, u));
+            candidate[pos] = unused[u];
+        }
+        
+        let candidate_cost = scorer.cost(&candidate);
+        let delta = candidate_cost - current_cost;
+        if delta <= 0.0 || rng.gen::<f64>() < (-delta / temperature).exp() {
+            if let Some((pos, u)) = swapped_in {
+                unused[u] = current[pos];
+            }
+            current = candidate;
+            current_cost = candidate_cost;
+            if current_cost < best_cost {
+                best = current.clone();
+                best_cost = current_cost;
+            }
+        }
+        
+        temperature = (temperature * 0.999_f64).max(0.01);
+    }
+    
+    let mut slots: Vec<Option<QuizItem>> = pool.into_iter().map(Some).collect();
+    best.into_iter().filter_map(|i| slots[i].take()).collect()
+}
+
+/// Reorder items so consecutive questions come from different categories and
+/// aren't about confusable words, as far as the mix allows (interleaved practice).
+/// Relative order within a category is preserved.
+pub fn interleave(items: Vec<QuizItem>, confusable_threshold: f64) -> Vec<QuizItem> {
+    let mut groups: Vec<(String, Vec<QuizItem>)> = Vec::new();
+    for item in items {
+        match groups.iter_mut().find(|(c, _)| *c == item.category) {
+            Some((_, group)) => group.push(item),
+            None => groups.push((item.category.clone(), vec![item])),
+        }
+    }
+    
+    let mut result: Vec<QuizItem> = Vec::new();
+    loop {
+        let last = result.last();
+        
+        // Largest remaining category that differs from the previous question's
+        let pick = groups.iter()
+            .enumerate()
+            .filter(|(_, (c, g))| !g.is_empty() && last.is_none_or(|l| l.category != *c))
+            .max_by_key(|(i, (_, g))| (g.len(), std::cmp::Reverse(*i)))
+            .map(|(i, _)| i)
+            .or_else(|| groups.iter().position(|(_, g)| !g.is_empty()));
+        
+        let Some(group_index) = pick else {
+            break;
+        };
+        
+        // Within the category, skip past words confusable with the previous one when possible
+        let group = &mut groups[group_index].1;
+        let item_index = last
+            .and_then(|l| group.iter().position(|i| !is_confusable(&i.word, &l.word, confusable_threshold)))
+            .unwrap_or(0);
+        result.push(group.remove(item_index));
+    }
+    
+    result
+}
@@ -0,0 +1,274 @@
+//! Classroom content filter: a configurable term list checked at import and question generation
+
+use pyo3::prelude::*;
+use rusqlite::{Connection, params};
+use crate::db::{open_database, get_setting, init_database, set_setting, Word};
+use crate::question_bank::{get_custom_questions, CustomQuestion};
+
+/// Setting holding the filter mode
+const MODE_SETTING: &str = "content_filter_mode";
+
+/// What happens to entries matching a filter term
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FilterMode {
+    /// Keep entries; they are only reported by `scan_deck_for_flagged`
+    #[default]
+    Flag,
+    /// Skip entries at import and keep them out of generated questions and options
+    Exclude,
+}
+
+impl FilterMode {
+    pub fn parse(mode: &str) -> Option<Self> {
+        match mode.to_lowercase().as_str() {
+            "flag" => Some(FilterMode::Flag),
+            "exclude" => Some(FilterMode::Exclude),
+            _ => None,
+        }
+    }
+    
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FilterMode::Flag => "flag",
+            FilterMode::Exclude => "exclude",
+        }
+    }
+}
+
+/// Loaded filter configuration. No terms are bundled; an empty list matches nothing.
+#[derive(Debug, Clone, Default)]
+pub struct ContentFilter {
+    /// Lowercased terms
+    pub terms: Vec<String>,
+    pub mode: FilterMode,
+}
+
+/// Letters/digits where a term match must not continue into a longer word
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() && (c as u32) < 0x2E80
+}
+
+/// Text fields of a word checked by the filter
+fn word_fields(word: &Word) -> [(&'static str, &String); 5] {
+    [
+        ("word", &word.word),
+        ("meaning", &word.meaning),
+        ("synonyms", &word.synonyms),
+        ("antonyms", &word.antonyms),
+        ("extra", &word.extra),
+    ]
+}
+
+impl ContentFilter {
+    /// Load the configured filter (missing tables count as an empty filter)
+    pub fn load(conn: &Connection) -> ContentFilter {
+        let terms = conn.prepare("SELECT term FROM content_filter_terms ORDER BY term")
+            .and_then(|mut stmt| {
+                let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+                Ok(rows.filter_map(|t| t.ok()).collect())
+            })
+            .unwrap_or_default();
+        let mode = get_setting(conn, MODE_SETTING).ok().flatten()
+            .and_then(|m| FilterMode::parse(&m))
+            .unwrap_or_default();
+        ContentFilter { terms, mode }
+    }
+    
+    /// First term found in the text as a whole word or phrase, case-insensitively
+    pub fn find_term(&self, text: &str) -> Option<&str> {
+        if self.terms.is_empty() || text.is_empty() {
+            return None;
+        }
+        let lower = text.to_lowercase();
+        self.terms.iter().map(String::as_str).find(|term| {
+            lower.match_indices(term).any(|(start, _)| {
+                let end = start + term.len();
+                let before = lower[..start].chars().next_back();
+                let after = lower[end..].chars().next();
+                let joins = |a: Option<char>, b: Option<char>| a.is_some_and(is_word_char) && b.is_some_and(is_word_char);
+                !joins(before, term.chars().next()) && !joins(after, term.chars().next_back())
+            })
+        })
+    }
+    
+    /// First (field, term) match in a word's text fields
+    pub fn find_in_word(&self, word: &Word) -> Option<(&'static str, &str)> {
+        word_fields(word)
+            .into_iter()
+            .find_map(|(field, text)| self.find_term(text).map(|term| (field, term)))
+    }
+    
+    /// True if the filter is in exclude mode and the text matches
+    pub fn excludes(&self, text: &str) -> bool {
+        self.mode == FilterMode::Exclude && self.find_term(text).is_some()
+    }
+    
+    /// True if the filter is in exclude mode and any of the word's fields match
+    pub fn excludes_word(&self, word: &Word) -> bool {
+        self.mode == FilterMode::Exclude && self.find_in_word(word).is_some()
+    }
+    
+    /// True if the filter is in exclude mode and the stem or an option matches
+    pub fn excludes_question(&self, question: &CustomQuestion) -> bool {
+        self.excludes(&question.stem) || question.options.iter().any(|o| self.excludes(o))
+    }
+}
+
+/// An entry matching a content filter term
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct FlaggedEntry {
+    #[pyo3(get)]
+    pub word_id: Option<i64>,
+    /// Authored question id when the match is in a custom question
+    #[pyo3(get)]
+    pub question_id: Option<i64>,
+    /// Field containing the match (word, meaning, synonyms, antonyms, extra, example, stem, option)
+    #[pyo3(get)]
+    pub field: String,
+    #[pyo3(get)]
+    pub term: String,
+    #[pyo3(get)]
+    pub text: String,
+}
+
+#[pymethods]
+impl FlaggedEntry {
+    fn __repr__(&self) -> String {
+        format!("FlaggedEntry(field='{}', term='{}', text='{}')", self.field, self.term, self.text)
+    }
+}
+
+/// Replace the filter term list and set the mode ("flag" or "exclude")
+pub fn set_content_filter(db_path: &str, terms: &[String], mode: &str) -> Result<usize, String> {
+    let mode = FilterMode::parse(mode)
+        .ok_or_else(|| format!("Unknown content filter mode '{}'. Expected 'flag' or 'exclude'", mode))?;
+    
+    let mut conn = init_database(db_path)
+        .map_err(|e| format!("Failed to init database: {}", e))?;
+    let tx = conn.transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    
+    tx.execute("DELETE FROM content_filter_terms", [])
+        .map_err(|e| format!("Failed to clear filter terms: {}", e))?;
+    let mut count = 0;
+    for term in terms {
+        let term = term.trim().to_lowercase();
+        if term.is_empty() {
+            continue;
+        }
+        count += tx.execute("INSERT OR IGNORE INTO content_filter_terms (term) VALUES (?1)", params![term])
+            .map_err(|e| format!("Failed to save filter term: {}", e))?;
+    }
+    set_setting(&tx, MODE_SETTING, Some(mode.as_str()))
+        .map_err(|e| format!("Failed to save filter mode: {}", e))?;
+    
+    tx.commit().map_err(|e| format!("Failed to commit filter: {}", e))?;
+    Ok(count)
+}
+
+/// Current filter terms and mode
+pub fn get_content_filter(db_path: &str) -> Result<(Vec<String>, String), String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let filter = ContentFilter::load(&conn);
+    Ok((filter.terms, filter.mode.as_str().to_string()))
+}
+
+/// Report every word, example sentence and authored question (optionally in one category)
+/// that contains a filter term, regardless of the filter mode
+pub fn scan_deck_for_flagged(db_path: &str, category: Option<&str>) -> Result<Vec<FlaggedEntry>, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let filter = ContentFilter::load(&conn);
+    if filter.terms.is_empty() {
+        return Ok(Vec::new());
+    }
+    
+    let categories = category.map(|c| vec![c.to_string()]);
+    let words = crate::db::get_words(&conn, "a_to_z", None, categories.clone(), None, None, None, false)
+        .map_err(|e| format!("Failed to get words: {}", e))?;
+    
+    let mut flagged = Vec::new();
+    for word in &words {
+        for (field, text) in word_fields(word) {
+            if let Some(term) = filter.find_term(text) {
+                flagged.push(FlaggedEntry {
+                    word_id: Some(word.id),
+                    question_id: None,
+                    field: field.to_string(),
+                    term: term.to_string(),
+                    text: text.clone(),
+                });
+            }
+        }
+    }
+    
+    let mut stmt = conn.prepare("SELECT word_id, sentence FROM examples ORDER BY id")
+        .map_err(|e| format!("Failed to get examples: {}", e))?;
+    let examples: Vec<(i64, String)> = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| format!("Failed to get examples: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    for (word_id, sentence) in examples {
+        if !words.iter().any(|w| w.id == word_id) {
+            continue;
+        }
+        if let Some(term) = filter.find_term(&sentence) {
+            flagged.push(FlaggedEntry {
+                word_id: Some(word_id),
+                question_id: None,
+                field: "example".to_string(),
+                term: term.to_string(),
+                text: sentence,
+            });
+        }
+    }
+    
+    let questions = get_custom_questions(&conn, categories.as_deref())
+        .map_err(|e| format!("Failed to get authored questions: {}", e))?;
+    for question in questions {
+        let texts = std::iter::once(("stem", &question.stem))
+            .chain(question.options.iter().map(|o| ("option", o)));
+        for (field, text) in texts {
+            if let Some(term) = filter.find_term(text) {
+                flagged.push(FlaggedEntry {
+                    word_id: question.word_id,
+                    question_id: Some(question.id),
+                    field: field.to_string(),
+                    term: term.to_string(),
+                    text: text.clone(),
+                });
+            }
+        }
+    }
+    
+    Ok(flagged)
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "set_content_filter", signature = (db_path, terms, mode="flag"))]
+pub fn py_set_content_filter(db_path: &str, terms: Vec<String>, mode: &str) -> PyResult<usize> {
+    if FilterMode::parse(mode).is_none() {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Unknown content filter mode '{}'. Expected 'flag' or 'exclude'", mode)));
+    }
+    set_content_filter(db_path, &terms, mode)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "get_content_filter")]
+pub fn py_get_content_filter(db_path: &str) -> PyResult<(Vec<String>, String)> {
+    get_content_filter(db_path)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "scan_deck_for_flagged", signature = (db_path, category=None))]
+pub fn py_scan_deck_for_flagged(db_path: &str, category: Option<&str>) -> PyResult<Vec<FlaggedEntry>> {
+    scan_deck_for_flagged(db_path, category)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
@@ -0,0 +1,208 @@
+//! Cram mode: last-minute study before an exam.
+//!
+//! A cram queue ignores review intervals and ranks every selected word by how likely
+//! it is to be missed at exam time. Attempts saved with `CRAM_MODE` don't reschedule
+//! their words, so cramming leaves the long-term schedule untouched until the session
+//! is folded in with `fold_cram_results`.
+
+use pyo3::prelude::*;
+use rusqlite::{Connection, OptionalExtension, params};
+use crate::db::{open_database, Word, WORD_COLUMNS, IGNORED_WORD_IDS, category_condition, word_from_row};
+use crate::srs::{read_state, word_srs_params, write_state_at, SrsState};
+
+/// Attempt mode for answers given while cramming; these attempts skip `record_review`
+pub const CRAM_MODE: &str = "cram";
+
+/// Share of words still recalled when they fall due (the retention SM-2 intervals aim at)
+const RETENTION_AT_DUE: f64 = 0.9;
+
+/// Shortest memory stability assumed for a reviewed word, in days
+const MIN_STABILITY_DAYS: f64 = 0.25;
+
+/// Share of a regular correct review's interval growth credited for a cram answer
+const CRAM_CREDIT: f64 = 0.5;
+
+/// A word in a cram queue with the estimates it was ranked by
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct CramItem {
+    #[pyo3(get)]
+    pub word: Word,
+    /// Share of wrong answers, smoothed so words with few attempts sit near 0.5
+    #[pyo3(get)]
+    pub error_rate: f64,
+    /// Estimated chance the word is still remembered at exam time (0.0 if never reviewed)
+    #[pyo3(get)]
+    pub recall_at_exam: f64,
+    /// Ranking score: chance of missing the word at the exam (higher comes first)
+    #[pyo3(get)]
+    pub priority: f64,
+}
+
+#[pymethods]
+impl CramItem {
+    fn __repr__(&self) -> String {
+        format!("CramItem(word='{}', priority={:.2})", self.word.word, self.priority)
+    }
+}
+
+/// Chance of recalling a word `days` after its last review, given its review interval:
+/// retention decays so that it reaches `RETENTION_AT_DUE` when the word falls due
+pub fn recall_probability(interval_days: f64, days: f64) -> f64 {
+    RETENTION_AT_DUE.powf(days.max(0.0) / interval_days.max(MIN_STABILITY_DAYS))
+}
+
+/// All selected words (except ignored ones) ordered by their risk of being missed in an
+/// exam `hours_until_exam` hours from now, weakest first. Reads but never changes SRS state.
+pub fn build_cram_queue(db_path: &str, categories: Option<Vec<String>>, hours_until_exam: f64) -> Result<Vec<CramItem>, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    
+    let category_clause = category_condition(categories.as_deref())
+        .map(|c| format!(" AND {}", c))
+        .unwrap_or_default();
+    
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {}, s.interval_days, julianday('now') - julianday(s.last_review_at) AS days_since,
+                (SELECT COUNT(*) FROM attempts a WHERE a.word_id = v.id) AS total,
+                (SELECT COALESCE(SUM(a.is_correct), 0) FROM attempts a WHERE a.word_id = v.id) AS correct
+         FROM vocabulary v LEFT JOIN srs_state s ON s.word_id = v.id
+         WHERE v.deleted_at IS NULL AND v.id NOT IN ({}){}",
+        WORD_COLUMNS, IGNORED_WORD_IDS, category_clause
+    )).map_err(|e| format!("Failed to prepare query: {}", e))?;
+    
+    let exam_in_days = hours_until_exam.max(0.0) / 24.0;
+    let mut queue: Vec<CramItem> = stmt.query_map([], |row| {
+        let word = word_from_row(row)?;
+        let interval_days: Option<f64> = row.get("interval_days")?;
+        let days_since: Option<f64> = row.get("days_since")?;
+        let total: i64 = row.get("total")?;
+        let correct: i64 = row.get("correct")?;
+        
+        let error_rate = (total - correct + 1) as f64 / (total + 2) as f64;
+        let recall_at_exam = match (interval_days, days_since) {
+            (Some(interval), Some(since)) => recall_probability(interval, since + exam_in_days),
+            _ => 0.0,
+        };
+        Ok(CramItem {
+            word,
+            error_rate,
+            recall_at_exam,
+            priority: 1.0 - recall_at_exam * (1.0 - error_rate),
+        })
+    })
+    .map_err(|e| format!("Failed to execute query: {}", e))?
+    .filter_map(|i| i.ok())
+    .collect();
+    
+    queue.sort_by(|a, b| b.priority.total_cmp(&a.priority).then(a.word.id.cmp(&b.word.id)));
+    Ok(queue)
+}
+
+/// What folding a cram session changed in the schedule
+#[pyclass]
+#[derive(Debug, Clone, Default)]
+pub struct CramFold {
+    /// Words first answered right: their interval grew by `CRAM_CREDIT` of a normal review
+    #[pyo3(get)]
+    pub credited: usize,
+    /// Words first answered wrong: reviewed as a lapse
+    #[pyo3(get)]
+    pub lapsed: usize,
+    /// Words left alone because a regular review came after the cram answer
+    #[pyo3(get)]
+    pub skipped: usize,
+}
+
+#[pymethods]
+impl CramFold {
+    fn __repr__(&self) -> String {
+        format!("CramFold(credited={}, lapsed={}, skipped={})", self.credited, self.lapsed, self.skipped)
+    }
+}
+
+/// Fold a cram session into the long-term schedule, once. Only each word's first cram
+/// answer counts (later ones were primed by the session): a miss is a lapse, a hit grows
+/// the interval by just `CRAM_CREDIT` of what a regular review would and counts as a rep,
+/// keeping the ease. Words are scheduled from the time of that answer.
+pub fn fold_cram_results(conn: &mut Connection, session_id: &str) -> Result<CramFold, String> {
+    let tx = conn.transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    
+    let folded = tx.query_row("SELECT 1 FROM cram_folds WHERE session_id = ?1", params![session_id], |_| Ok(()))
+        .optional()
+        .map_err(|e| format!("Failed to check cram session: {}", e))?;
+    if folded.is_some() {
+        return Err(format!("Cram session already folded: {}", session_id));
+    }
+    
+    let first_answers: Vec<(i64, bool, bool, String)> = {
+        let mut stmt = tx.prepare(
+            "SELECT a.word_id, a.is_correct,
+                    EXISTS(SELECT 1 FROM srs_state s WHERE s.word_id = a.word_id AND s.last_review_at > a.attempted_at),
+                    a.attempted_at
+             FROM attempts a JOIN vocabulary v ON v.id = a.word_id
+             WHERE a.id IN (SELECT MIN(id) FROM attempts WHERE session_id = ?1 AND mode = ?2 GROUP BY word_id)
+             ORDER BY a.id"
+        ).map_err(|e| format!("Failed to prepare query: {}", e))?;
+        let rows = stmt.query_map(params![session_id, CRAM_MODE], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
+            .map_err(|e| format!("Failed to get cram attempts: {}", e))?;
+        rows.filter_map(|r| r.ok()).collect()
+    };
+    if first_answers.is_empty() {
+        return Err(format!("No cram attempts in session: {}", session_id));
+    }
+    
+    let mut fold = CramFold::default();
+    for (word_id, correct, reviewed_since, attempted_at) in first_answers {
+        if reviewed_since {
+            fold.skipped += 1;
+            continue;
+        }
+        let srs_params = word_srs_params(&tx, word_id)
+            .map_err(|e| format!("Failed to get scheduler parameters: {}", e))?;
+        let state = read_state(&tx, word_id)
+            .map_err(|e| format!("Failed to read schedule: {}", e))?
+            .unwrap_or_else(|| SrsState::new(&srs_params));
+        let next = if correct {
+            fold.credited += 1;
+            let full = state.review(true, &srs_params);
+            SrsState {
+                interval_days: state.interval_days + (full.interval_days - state.interval_days) * CRAM_CREDIT,
+                reps: full.reps,
+                ..state
+            }
+        } else {
+            fold.lapsed += 1;
+            state.review(false, &srs_params)
+        };
+        write_state_at(&tx, word_id, &next, &attempted_at)
+            .map_err(|e| format!("Failed to write schedule: {}", e))?;
+    }
+    
+    tx.execute("INSERT INTO cram_folds (session_id) VALUES (?1)", params![session_id])
+        .map_err(|e| format!("Failed to record cram fold: {}", e))?;
+    tx.commit().map_err(|e| format!("Failed to commit cram fold: {}", e))?;
+    Ok(fold)
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "build_cram_queue", signature = (db_path, categories=None, hours_until_exam=24.0))]
+pub fn py_build_cram_queue(db_path: &str, categories: Option<Vec<String>>, hours_until_exam: f64) -> PyResult<Vec<CramItem>> {
+    if !(hours_until_exam.is_finite() && hours_until_exam >= 0.0) {
+        return Err(pyo3::exceptions::PyValueError::new_err("hours_until_exam must not be negative"));
+    }
+    build_cram_queue(db_path, categories, hours_until_exam)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "fold_cram_results_into_schedule")]
+pub fn py_fold_cram_results_into_schedule(db_path: &str, session_id: &str) -> PyResult<CramFold> {
+    let mut conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+    fold_cram_results(&mut conn, session_id)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
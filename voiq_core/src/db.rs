@@ -1,13 +1,19 @@
 //! Database operations for VoIQ vocabulary storage
 
 use pyo3::prelude::*;
-use rusqlite::{Connection, Result as SqliteResult, params};
+use pyo3::types::{PyBool, PyDict, PyList};
+use rusqlite::{Connection, OpenFlags, OptionalExtension, Result as SqliteResult, Row, params, params_from_iter};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use serde_json::{Map, Value};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::ops::RangeInclusive;
+use std::sync::{Mutex, OnceLock};
+use crate::collation::{base_initial, register_collation, unicode_compare, word_key};
+use crate::validation::{duplicate_issue, find_duplicate, validate_word, validation_error, ValidationIssue};
 
 /// Word entry from vocabulary database
 #[pyclass]
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Word {
     #[pyo3(get)]
     pub id: i64,
@@ -21,249 +27,2813 @@ pub struct Word {
     pub antonyms: String,
     #[pyo3(get)]
     pub category: String,
+    /// Extra per-word fields as a JSON object string (empty when unset)
+    #[pyo3(get)]
+    pub extra: String,
+    /// Pronunciation, plain ("かんじ") or in ruby bracket notation ("漢字[かんじ]"); empty when unset
+    #[pyo3(get)]
+    pub reading: String,
+    /// Learner's own notes, e.g. a mnemonic (empty when unset)
+    #[pyo3(get)]
+    pub notes: String,
+    /// Part of speech ("noun", "verb", "adj", ...); empty when unset
+    #[pyo3(get)]
+    pub pos: String,
+    /// Difficulty level within `DIFFICULTY_LEVELS` (None when unset)
+    #[pyo3(get)]
+    pub difficulty: Option<i64>,
+    /// Rank in a corpus frequency list, 1 = most frequent (None when unset)
+    #[pyo3(get)]
+    pub frequency_rank: Option<i64>,
+    /// Path of a pronunciation recording managed by the app (empty when unset)
+    #[pyo3(get)]
+    pub audio_path: String,
+    /// Path of a picture managed by the app (empty when unset)
+    #[pyo3(get)]
+    pub image_path: String,
+    /// Marked as a favorite by the learner
+    #[pyo3(get)]
+    #[serde(default)]
+    pub is_starred: bool,
+    /// Example sentence from an import's "Example" column, saved to `examples` on load.
+    /// Not filled when reading words back; see `get_examples`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub example: String,
+}
+
+/// Valid difficulty levels, easiest first
+pub const DIFFICULTY_LEVELS: RangeInclusive<i64> = 1..=5;
+
+/// Category used when none is configured
+pub const FALLBACK_CATEGORY: &str = "Default";
+
+/// Setting holding the name of the default category
+pub const DEFAULT_CATEGORY_KEY: &str = "default_category";
+
+/// SQL expression for a category column, with unset values shown as the default category
+macro_rules! category_or_default {
+    ($column:literal) => {
+        concat!("COALESCE(NULLIF(", $column, ", ''), (SELECT value FROM settings WHERE key = 'default_category'), 'Default')")
+    };
+}
+pub(crate) use category_or_default;
+
+/// Columns selected for a `Word`; queries must alias `vocabulary` as `v`
+pub(crate) const WORD_COLUMNS: &str = concat!(
+    "v.id, v.word, v.meaning, v.synonyms, v.antonyms, ", category_or_default!("v.category"), " AS category, v.extra, v.reading, v.notes, v.pos, v.difficulty, v.frequency_rank, v.audio_path, v.image_path, v.starred_at IS NOT NULL AS is_starred"
+);
+
+/// Build a `Word` from a row selected with `WORD_COLUMNS`
+pub(crate) fn word_from_row(row: &Row) -> SqliteResult<Word> {
+    Ok(Word {
+        id: row.get("id")?,
+        word: row.get("word")?,
+        meaning: row.get("meaning")?,
+        synonyms: row.get::<_, Option<String>>("synonyms")?.unwrap_or_default(),
+        antonyms: row.get::<_, Option<String>>("antonyms")?.unwrap_or_default(),
+        category: row.get::<_, Option<String>>("category")?.unwrap_or_else(|| FALLBACK_CATEGORY.to_string()),
+        extra: row.get::<_, Option<String>>("extra")?.unwrap_or_default(),
+        reading: row.get::<_, Option<String>>("reading")?.unwrap_or_default(),
+        notes: row.get::<_, Option<String>>("notes")?.unwrap_or_default(),
+        pos: row.get::<_, Option<String>>("pos")?.unwrap_or_default(),
+        difficulty: row.get("difficulty")?,
+        frequency_rank: row.get("frequency_rank")?,
+        audio_path: row.get::<_, Option<String>>("audio_path")?.unwrap_or_default(),
+        image_path: row.get::<_, Option<String>>("image_path")?.unwrap_or_default(),
+        is_starred: row.get("is_starred")?,
+        example: String::new(),
+    })
+}
+
+#[pymethods]
+impl Word {
+    /// Extra per-word fields as a dict (empty when unset)
+    #[getter]
+    fn fields(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let extra = if self.extra.is_empty() { None } else { Some(self.extra.clone()) };
+        json_to_py(py, &Value::Object(parse_extra(extra)))
+    }
+    
+    fn __repr__(&self) -> String {
+        format!("Word(id={}, word='{}', category='{}')", 
+                self.id, self.word, self.category)
+    }
+}
+
+/// Outcome of `merge_categories`
+#[pyclass]
+#[derive(Debug, Clone, Default)]
+pub struct MergeResult {
+    /// Words moved into the target category
+    #[pyo3(get)]
+    pub moved: usize,
+    /// Duplicate words folded into an identical word of the target
+    #[pyo3(get)]
+    pub duplicates_removed: usize,
+}
+
+#[pymethods]
+impl MergeResult {
+    fn __repr__(&self) -> String {
+        format!("MergeResult(moved={}, duplicates_removed={})", self.moved, self.duplicates_removed)
+    }
+}
+
+/// Tag with the number of words carrying it
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct TagInfo {
+    #[pyo3(get)]
+    pub name: String,
+    #[pyo3(get)]
+    pub word_count: i64,
+}
+
+#[pymethods]
+impl TagInfo {
+    fn __repr__(&self) -> String {
+        format!("TagInfo(name='{}', word_count={})", self.name, self.word_count)
+    }
+}
+
+/// Example sentence for a word
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct Example {
+    #[pyo3(get)]
+    pub id: i64,
+    #[pyo3(get)]
+    pub word_id: i64,
+    #[pyo3(get)]
+    pub sentence: String,
+    /// Where the sentence came from ("import", "cloze", ...), if known
+    #[pyo3(get)]
+    pub source: Option<String>,
+    #[pyo3(get)]
+    pub created_at: String,
+}
+
+#[pymethods]
+impl Example {
+    fn __repr__(&self) -> String {
+        format!("Example(word_id={}, sentence='{}')", self.word_id, self.sentence)
+    }
+}
+
+/// Source recorded for examples read from an import's "Example" column
+pub const IMPORT_EXAMPLE_SOURCE: &str = "import";
+
+/// Category info with word count
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct CategoryInfo {
+    #[pyo3(get)]
+    pub name: String,
+    #[pyo3(get)]
+    pub word_count: i64,
+}
+
+/// Passphrases of encrypted databases, by path, for the lifetime of the process
+fn passphrases() -> &'static Mutex<HashMap<String, String>> {
+    static PASSPHRASES: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+    PASSPHRASES.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Remember the passphrase of an encrypted database so every later open of `db_path`
+/// unlocks it (None forgets it). Needs a build with the `sqlcipher` feature.
+pub fn set_passphrase(db_path: &str, passphrase: Option<&str>) -> Result<(), String> {
+    let mut registry = passphrases().lock().map_err(|e| e.to_string())?;
+    match passphrase {
+        Some(passphrase) => {
+            if !cfg!(feature = "sqlcipher") {
+                return Err("Database encryption needs a build with the sqlcipher feature".to_string());
+            }
+            registry.insert(db_path.to_string(), passphrase.to_string());
+        }
+        None => {
+            registry.remove(db_path);
+        }
+    }
+    Ok(())
+}
+
+/// Passphrase registered for a database path, if any
+pub fn passphrase_for(db_path: &str) -> Option<String> {
+    passphrases().lock().ok()?.get(db_path).cloned()
+}
+
+/// SQLite journal modes accepted by `DatabaseOptions`
+pub const JOURNAL_MODES: &[&str] = &["delete", "truncate", "persist", "memory", "wal", "off"];
+
+/// SQLite synchronous levels accepted by `DatabaseOptions`
+pub const SYNCHRONOUS_LEVELS: &[&str] = &["off", "normal", "full", "extra"];
+
+/// Connection settings applied whenever a configured database is opened
+#[pyclass]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatabaseOptions {
+    /// One of `JOURNAL_MODES`; WAL lets readers and a writer work at the same time
+    #[pyo3(get, set)]
+    pub journal_mode: String,
+    /// How long a connection waits for a lock before failing with "database is locked"
+    #[pyo3(get, set)]
+    pub busy_timeout_ms: u64,
+    /// One of `SYNCHRONOUS_LEVELS`; "normal" is safe with WAL and much faster than "full"
+    #[pyo3(get, set)]
+    pub synchronous: String,
+}
+
+impl Default for DatabaseOptions {
+    fn default() -> Self {
+        DatabaseOptions {
+            journal_mode: "wal".to_string(),
+            busy_timeout_ms: 5_000,
+            synchronous: "normal".to_string(),
+        }
+    }
+}
+
+impl DatabaseOptions {
+    /// Check that the options name real SQLite settings
+    pub fn validate(&self) -> Result<(), String> {
+        if !JOURNAL_MODES.contains(&self.journal_mode.to_lowercase().as_str()) {
+            return Err(format!("Unknown journal mode: {}", self.journal_mode));
+        }
+        if !SYNCHRONOUS_LEVELS.contains(&self.synchronous.to_lowercase().as_str()) {
+            return Err(format!("Unknown synchronous level: {}", self.synchronous));
+        }
+        Ok(())
+    }
+    
+    /// Apply the options to an open connection; returns the journal mode in effect,
+    /// which may differ from the requested one (in-memory databases can't use WAL)
+    pub fn apply(&self, conn: &Connection) -> SqliteResult<String> {
+        conn.busy_timeout(std::time::Duration::from_millis(self.busy_timeout_ms))?;
+        let mode = conn.pragma_update_and_check(None, "journal_mode", self.journal_mode.to_lowercase(), |row| row.get(0))?;
+        conn.pragma_update(None, "synchronous", self.synchronous.to_lowercase())?;
+        Ok(mode)
+    }
+}
+
+#[pymethods]
+impl DatabaseOptions {
+    #[new]
+    #[pyo3(signature = (journal_mode="wal".to_string(), busy_timeout_ms=5_000, synchronous="normal".to_string()))]
+    fn py_new(journal_mode: String, busy_timeout_ms: u64, synchronous: String) -> Self {
+        DatabaseOptions { journal_mode, busy_timeout_ms, synchronous }
+    }
+    
+    fn __repr__(&self) -> String {
+        format!("DatabaseOptions(journal_mode='{}', busy_timeout_ms={}, synchronous='{}')",
+                self.journal_mode, self.busy_timeout_ms, self.synchronous)
+    }
+}
+
+/// Options given to `configure_database`, by path, for the lifetime of the process
+fn database_options() -> &'static Mutex<HashMap<String, DatabaseOptions>> {
+    static OPTIONS: OnceLock<Mutex<HashMap<String, DatabaseOptions>>> = OnceLock::new();
+    OPTIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Use `options` for every later connection to `db_path` and apply them right away.
+/// Returns the journal mode in effect.
+pub fn configure_database(db_path: &str, options: &DatabaseOptions) -> Result<String, String> {
+    options.validate()?;
+    database_options().lock().map_err(|e| e.to_string())?
+        .insert(db_path.to_string(), options.clone());
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let mode = options.apply(&conn)
+        .map_err(|e| format!("Failed to configure database: {}", e))?;
+    Ok(mode)
+}
+
+/// Path of an in-memory database; ":memory:<name>" paths are separate in-memory databases
+pub const MEMORY_DATABASE: &str = ":memory:";
+
+/// Whether a path names an in-memory database rather than a file
+pub fn is_memory_database(db_path: &str) -> bool {
+    db_path.starts_with(MEMORY_DATABASE)
+}
+
+/// One idle connection per in-memory database, keeping its contents alive between the
+/// short-lived connections each call opens
+fn memory_databases() -> &'static Mutex<HashMap<String, Connection>> {
+    static MEMORY: OnceLock<Mutex<HashMap<String, Connection>>> = OnceLock::new();
+    MEMORY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// URI of an in-memory database in SQLite's memdb VFS, where every connection in the
+/// process opening the same name sees the same data
+fn memory_uri(db_path: &str) -> String {
+    let name: String = db_path[MEMORY_DATABASE.len()..].bytes()
+        .map(|b| if b.is_ascii_alphanumeric() || b == b'-' || b == b'_' { (b as char).to_string() } else { format!("%{:02X}", b) })
+        .collect();
+    format!("file:/voiq-memory-{}?vfs=memdb", name)
+}
+
+fn open_memory_database(db_path: &str, flags: OpenFlags) -> SqliteResult<Connection> {
+    let uri = memory_uri(db_path);
+    let conn = Connection::open_with_flags(&uri, flags | OpenFlags::SQLITE_OPEN_URI)?;
+    let mut databases = memory_databases().lock()
+        .map_err(|_| rusqlite::Error::InvalidPath(db_path.into()))?;
+    if !databases.contains_key(db_path) {
+        let keeper = Connection::open_with_flags(&uri, OpenFlags::default() | OpenFlags::SQLITE_OPEN_URI)?;
+        databases.insert(db_path.to_string(), keeper);
+    }
+    Ok(conn)
+}
+
+/// Discard an in-memory database's contents (once no other connection is open).
+/// Returns false if it wasn't open.
+pub fn close_memory_database(db_path: &str) -> bool {
+    memory_databases().lock()
+        .map(|mut databases| databases.remove(db_path).is_some())
+        .unwrap_or(false)
+}
+
+/// Open a database with the given flags, unlocking it if a passphrase is registered and
+/// applying the options given to `configure_database`. `MEMORY_DATABASE` paths open an
+/// in-memory database that lives until `close_memory_database`.
+pub fn open_database_with_flags(db_path: &str, flags: OpenFlags) -> SqliteResult<Connection> {
+    let conn = if is_memory_database(db_path) {
+        open_memory_database(db_path, flags)?
+    } else {
+        Connection::open_with_flags(db_path, flags)?
+    };
+    register_collation(&conn)?;
+    if let Some(passphrase) = passphrase_for(db_path) {
+        conn.pragma_update(None, "key", passphrase)?;
+    }
+    let options = database_options().lock().ok().and_then(|registry| registry.get(db_path).cloned());
+    match options {
+        // Read-only connections can't switch the journal mode; a WAL file stays in WAL anyway
+        Some(options) if flags.contains(OpenFlags::SQLITE_OPEN_READ_ONLY) => {
+            conn.busy_timeout(std::time::Duration::from_millis(options.busy_timeout_ms))?;
+            conn.pragma_update(None, "synchronous", options.synchronous.to_lowercase())?;
+        }
+        Some(options) => {
+            options.apply(&conn)?;
+        }
+        None => {}
+    }
+    Ok(conn)
+}
+
+/// Open a database for reading and writing; all path-based functions go through here
+pub fn open_database(db_path: &str) -> SqliteResult<Connection> {
+    open_database_with_flags(db_path, OpenFlags::default())
+}
+
+/// A schema change applied once to databases created before it
+pub struct Migration {
+    pub version: i64,
+    pub description: &'static str,
+    pub apply: fn(&Connection) -> SqliteResult<()>,
+}
+
+/// Schema changes in the order they were made. `init_database` creates tables in their
+/// latest form, so migrations must also be harmless on a fresh database (see `add_column`).
+/// Append new entries with the next version number; never edit or reorder applied ones.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Vocabulary columns added before schema versioning",
+        apply: |conn| {
+            for (column, definition) in [
+                ("category", "TEXT DEFAULT 'Default'"),
+                ("extra", "TEXT"),
+                ("reading", "TEXT"),
+                ("updated_at", "TIMESTAMP"),
+                ("import_id", "INTEGER"),
+                ("pinned_at", "TIMESTAMP"),
+                ("snoozed_until", "TIMESTAMP"),
+                ("ignored", "INTEGER NOT NULL DEFAULT 0"),
+                ("deleted_at", "TIMESTAMP"),
+                ("notes", "TEXT"),
+                ("pos", "TEXT"),
+                ("difficulty", "INTEGER"),
+                ("frequency_rank", "INTEGER"),
+                ("audio_path", "TEXT"),
+                ("image_path", "TEXT"),
+            ] {
+                add_column(conn, "vocabulary", column, definition)?;
+            }
+            Ok(())
+        },
+    },
+    Migration {
+        version: 2,
+        description: "Attempt columns added before schema versioning",
+        apply: |conn| {
+            // Client context (device, app version, input method) as JSON
+            add_column(conn, "attempts", "client_context", "TEXT")?;
+            add_column(conn, "attempts", "session_id", "TEXT")?;
+            add_column(conn, "attempts", "first_answer", "TEXT")
+        },
+    },
+    Migration {
+        version: 3,
+        description: "Deck metadata columns added before schema versioning",
+        apply: |conn| {
+            add_column(conn, "category_meta", "recognitions_to_graduate", "INTEGER")?;
+            add_column(conn, "category_meta", "srs_params", "TEXT")
+        },
+    },
+    Migration {
+        version: 4,
+        description: "Index vocabulary by word for keyset paging",
+        apply: |conn| conn.execute("CREATE INDEX IF NOT EXISTS idx_vocabulary_word ON vocabulary(word, id)", []).map(|_| ()),
+    },
+    Migration {
+        version: 5,
+        description: "Normalized word keys for case- and accent-insensitive uniqueness",
+        apply: |conn| {
+            add_column(conn, "vocabulary", "word_key", "TEXT")?;
+            fill_word_keys(conn).map(|_| ())
+        },
+    },
+    Migration {
+        version: 6,
+        description: "Index vocabulary by category and attempts by word and outcome",
+        apply: |conn| conn.execute_batch(
+            "CREATE INDEX IF NOT EXISTS idx_vocabulary_category ON vocabulary(category);
+             CREATE INDEX IF NOT EXISTS idx_attempts_word_outcome ON attempts(word_id, is_correct, attempted_at);"
+        ),
+    },
+    Migration {
+        version: 7,
+        description: "Profile of each attempt",
+        apply: |conn| {
+            add_column(conn, "attempts", "profile_id", "INTEGER REFERENCES profiles(id)")?;
+            conn.execute("CREATE INDEX IF NOT EXISTS idx_attempts_profile ON attempts(profile_id)", []).map(|_| ())
+        },
+    },
+    Migration {
+        version: 8,
+        description: "Starred words",
+        apply: |conn| add_column(conn, "vocabulary", "starred_at", "TIMESTAMP"),
+    },
+    Migration {
+        version: 9,
+        description: "Row counts of journaled imports and vocabulary indexed by import",
+        apply: |conn| {
+            add_column(conn, "import_journal", "row_count", "INTEGER")?;
+            conn.execute("CREATE INDEX IF NOT EXISTS idx_vocabulary_import ON vocabulary(import_id)", []).map(|_| ())
+        },
+    },
+    Migration {
+        version: 10,
+        description: "Index vocabulary by word in Unicode order for keyset paging",
+        // The byte-order index from version 4 can't serve `ORDER BY word COLLATE UNICODE`.
+        // Writing to vocabulary now needs the collation, which `open_database` registers.
+        apply: |conn| conn.execute_batch(
+            "DROP INDEX IF EXISTS idx_vocabulary_word;
+             CREATE INDEX IF NOT EXISTS idx_vocabulary_word_unicode ON vocabulary(word COLLATE UNICODE, id);"
+        ),
+    },
+];
+
+/// Schema version a database is brought up to by this build
+pub fn latest_schema_version() -> i64 {
+    MIGRATIONS.last().map_or(0, |m| m.version)
+}
+
+/// Add a column unless the table already has it
+pub fn add_column(conn: &Connection, table: &str, column: &str, definition: &str) -> SqliteResult<()> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM pragma_table_info(?1) WHERE name = ?2)",
+        params![table, column],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition), [])?;
+    }
+    Ok(())
+}
+
+/// Highest migration version applied to a database (0 before any)
+pub fn schema_version(conn: &Connection) -> SqliteResult<i64> {
+    conn.query_row("SELECT COALESCE(MAX(version), 0) FROM schema_version", [], |row| row.get(0))
+}
+
+/// Apply pending migrations in order, each in its own transaction together with its
+/// `schema_version` row. Returns the versions applied.
+pub fn run_migrations(conn: &mut Connection) -> SqliteResult<Vec<i64>> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY,
+            description TEXT,
+            applied_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+    
+    let current = schema_version(conn)?;
+    let mut applied = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+        let tx = conn.transaction()?;
+        (migration.apply)(&tx)?;
+        tx.execute(
+            "INSERT INTO schema_version (version, description) VALUES (?1, ?2)",
+            params![migration.version, migration.description],
+        )?;
+        tx.commit()?;
+        applied.push(migration.version);
+    }
+    Ok(applied)
+}
+
+/// Initialize database with schema
+pub fn init_database(db_path: &str) -> SqliteResult<Connection> {
+    let mut conn = open_database(db_path)?;
+    
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS vocabulary (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            word TEXT NOT NULL,
+            meaning TEXT NOT NULL,
+            synonyms TEXT,
+            antonyms TEXT,
+            category TEXT DEFAULT 'Default',
+            extra TEXT,
+            reading TEXT,
+            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+            updated_at TIMESTAMP,
+            import_id INTEGER,
+            pinned_at TIMESTAMP,
+            snoozed_until TIMESTAMP,
+            ignored INTEGER NOT NULL DEFAULT 0,
+            deleted_at TIMESTAMP,
+            notes TEXT,
+            pos TEXT,
+            difficulty INTEGER,
+            frequency_rank INTEGER,
+            audio_path TEXT,
+            image_path TEXT,
+            starred_at TIMESTAMP,
+            word_key TEXT
+        )",
+        [],
+    )?;
+    
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS attempts (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            word_id INTEGER REFERENCES vocabulary(id),
+            mode TEXT NOT NULL,
+            question_type TEXT NOT NULL,
+            is_correct INTEGER NOT NULL,
+            user_answer TEXT,
+            expected_answer TEXT,
+            time_taken_ms INTEGER,
+            client_context TEXT,
+            session_id TEXT,
+            first_answer TEXT,
+            profile_id INTEGER REFERENCES profiles(id),
+            attempted_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+    
+    // Learners sharing the database; attempts without a profile belong to no one in particular
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS profiles (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+    
+    // Teacher-authored MCQs (options stored as a JSON array)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS custom_questions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            word_id INTEGER REFERENCES vocabulary(id),
+            category TEXT DEFAULT 'Default',
+            stem TEXT NOT NULL,
+            options TEXT NOT NULL,
+            correct_index INTEGER NOT NULL,
+            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+    
+    // Passive study activities that don't produce attempts
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS study_time (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id TEXT,
+            activity TEXT NOT NULL,
+            seconds INTEGER NOT NULL,
+            logged_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+    
+    // Example sentences for words
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS examples (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            word_id INTEGER REFERENCES vocabulary(id),
+            sentence TEXT NOT NULL,
+            source TEXT,
+            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+    
+    // Per-category (deck) metadata such as attribution
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS category_meta (
+            category TEXT PRIMARY KEY,
+            author TEXT,
+            license TEXT,
+            source_url TEXT,
+            version TEXT,
+            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+            recognitions_to_graduate INTEGER,
+            srs_params TEXT
+        )",
+        [],
+    )?;
+    
+    // Resumable enrichment jobs and their per-word state
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS enrichment_jobs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            category TEXT,
+            status TEXT NOT NULL DEFAULT 'pending',
+            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS enrichment_items (
+            job_id INTEGER NOT NULL REFERENCES enrichment_jobs(id),
+            word_id INTEGER NOT NULL REFERENCES vocabulary(id),
+            status TEXT NOT NULL DEFAULT 'pending',
+            attempts INTEGER NOT NULL DEFAULT 0,
+            last_error TEXT,
+            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+            PRIMARY KEY (job_id, word_id)
+        )",
+        [],
+    )?;
+    
+    // Persistent queue of background maintenance jobs
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS jobs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            kind TEXT NOT NULL,
+            params TEXT,
+            status TEXT NOT NULL DEFAULT 'queued',
+            result TEXT,
+            error TEXT,
+            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+            started_at TIMESTAMP,
+            finished_at TIMESTAMP
+        )",
+        [],
+    )?;
+    
+    // Journal of import operations, so imports cut short by a crash can be undone
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS import_journal (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            operation TEXT NOT NULL,
+            category TEXT,
+            source TEXT,
+            status TEXT NOT NULL DEFAULT 'started',
+            started_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+            finished_at TIMESTAMP,
+            row_count INTEGER
+        )",
+        [],
+    )?;
+    
+    // Spaced repetition state of reviewed words
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS srs_state (
+            word_id INTEGER PRIMARY KEY REFERENCES vocabulary(id),
+            interval_days REAL NOT NULL DEFAULT 0,
+            ease REAL NOT NULL DEFAULT 2.5,
+            reps INTEGER NOT NULL DEFAULT 0,
+            lapses INTEGER NOT NULL DEFAULT 0,
+            due_at TIMESTAMP NOT NULL,
+            last_review_at TIMESTAMP
+        )",
+        [],
+    )?;
+    
+    // Cram sessions whose results were folded into the schedule
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS cram_folds (
+            session_id TEXT PRIMARY KEY,
+            folded_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+    
+    // Key/value application settings
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS settings (
+            key TEXT PRIMARY KEY,
+            value TEXT
+        )",
+        [],
+    )?;
+    
+    // Terms checked by the classroom content filter
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS content_filter_terms (
+            term TEXT PRIMARY KEY
+        )",
+        [],
+    )?;
+    
+    // User ratings of shown questions (distractors stored as a JSON array)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS question_ratings (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            question_id INTEGER REFERENCES custom_questions(id),
+            word_id INTEGER,
+            question_type TEXT NOT NULL,
+            distractors TEXT,
+            rating INTEGER NOT NULL,
+            reason TEXT,
+            rated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+    
+    // Tags: free-form lists a word can belong to in addition to its category
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS tags (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        )",
+        [],
+    )?;
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS word_tags (
+            word_id INTEGER NOT NULL REFERENCES vocabulary(id),
+            tag_id INTEGER NOT NULL REFERENCES tags(id),
+            PRIMARY KEY (word_id, tag_id)
+        )",
+        [],
+    )?;
+    
+    run_migrations(&mut conn)?;
+    
+    crate::fts::create_fts(&conn)?;
+    crate::history::create_history(&conn)?;
+    crate::views::create_views(&conn)?;
+    
+    Ok(conn)
+}
+
+/// Name of the optional unique index on (word, category)
+pub const UNIQUE_WORD_INDEX: &str = "idx_vocabulary_word_category";
+
+/// Name of the unique index on (word_key, category) used in normalized uniqueness mode
+pub const NORMALIZED_WORD_INDEX: &str = "idx_vocabulary_word_key_category";
+
+/// Setting holding the `UniquenessMode`
+pub const UNIQUENESS_MODE_SETTING: &str = "uniqueness_mode";
+
+/// When two words in a category count as the same entry, for import upserts, duplicate
+/// checks and the unique index
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum UniquenessMode {
+    /// Same text (duplicate checks also ignore case and surrounding whitespace)
+    #[default]
+    Exact,
+    /// Same `word_key`: case, accents and extra whitespace are ignored
+    Normalized,
+}
+
+impl UniquenessMode {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "" | "exact" => Ok(UniquenessMode::Exact),
+            "normalized" => Ok(UniquenessMode::Normalized),
+            _ => Err(format!("Unknown uniqueness mode: {}", name)),
+        }
+    }
+    
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UniquenessMode::Exact => "exact",
+            UniquenessMode::Normalized => "normalized",
+        }
+    }
+}
+
+/// The database's uniqueness mode (exact unless set)
+pub fn uniqueness_mode(conn: &Connection) -> SqliteResult<UniquenessMode> {
+    let stored = get_setting(conn, UNIQUENESS_MODE_SETTING)?;
+    Ok(stored.and_then(|name| UniquenessMode::parse(&name).ok()).unwrap_or_default())
+}
+
+/// Fill in `word_key` for rows written without one (e.g. by other tools). Returns how many.
+pub fn fill_word_keys(conn: &Connection) -> SqliteResult<usize> {
+    let missing: Vec<(i64, String)> = {
+        let mut stmt = conn.prepare("SELECT id, word FROM vocabulary WHERE word_key IS NULL")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.filter_map(|r| r.ok()).collect()
+    };
+    let mut stmt = conn.prepare("UPDATE vocabulary SET word_key = ?2 WHERE id = ?1")?;
+    for (id, word) in &missing {
+        stmt.execute(params![id, word_key(word)])?;
+    }
+    Ok(missing.len())
+}
+
+/// Switch the uniqueness mode, replacing the unique index if one is in use. Normalized
+/// mode fails while words that only differ in case, accents or spacing share a category.
+pub fn set_uniqueness_mode(conn: &mut Connection, mode: UniquenessMode) -> Result<(), String> {
+    let tx = conn.transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let had_index: bool = tx.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'index' AND name IN (?1, ?2))",
+        params![UNIQUE_WORD_INDEX, NORMALIZED_WORD_INDEX],
+        |row| row.get(0),
+    ).map_err(|e| format!("Failed to read indexes: {}", e))?;
+    
+    if mode == UniquenessMode::Normalized {
+        fill_word_keys(&tx).map_err(|e| format!("Failed to compute word keys: {}", e))?;
+        let clashes: i64 = tx.query_row(
+            "SELECT COUNT(*) FROM (SELECT 1 FROM vocabulary GROUP BY word_key, category HAVING COUNT(*) > 1)",
+            [],
+            |row| row.get(0),
+        ).map_err(|e| format!("Failed to check duplicates: {}", e))?;
+        if clashes > 0 {
+            return Err(format!("{} sets of words in the same category differ only in case, accents or spacing; merge them first", clashes));
+        }
+    }
+    set_setting(&tx, UNIQUENESS_MODE_SETTING, Some(mode.as_str()))
+        .map_err(|e| format!("Failed to save uniqueness mode: {}", e))?;
+    if had_index {
+        tx.execute_batch(&format!("DROP INDEX IF EXISTS {}; DROP INDEX IF EXISTS {};", UNIQUE_WORD_INDEX, NORMALIZED_WORD_INDEX))
+            .map_err(|e| format!("Failed to drop unique index: {}", e))?;
+        enable_unique_words(&tx)
+            .map_err(|e| format!("Failed to create unique index: {}", e))?;
+    }
+    tx.commit().map_err(|e| format!("Failed to commit uniqueness mode: {}", e))
+}
+
+/// How loaded words are written
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LoadMode {
+    /// Always insert a new row
+    #[default]
+    Append,
+    /// Update the word with the same text in the category, insert if there is none
+    Upsert,
+}
+
+impl LoadMode {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "" | "append" => Ok(LoadMode::Append),
+            "upsert" => Ok(LoadMode::Upsert),
+            _ => Err(format!("Unknown load mode: {}", name)),
+        }
+    }
+}
+
+/// Make words unique within their category, as the uniqueness mode defines them. Fails
+/// while duplicates exist (see `find_duplicates`); once created, inserting an existing
+/// word into its category fails instead of appending. Returns the mode.
+pub fn enable_unique_words(conn: &Connection) -> SqliteResult<UniquenessMode> {
+    let mode = uniqueness_mode(conn)?;
+    let sql = match mode {
+        UniquenessMode::Exact => format!("CREATE UNIQUE INDEX IF NOT EXISTS {} ON vocabulary(word, category)", UNIQUE_WORD_INDEX),
+        UniquenessMode::Normalized => format!("CREATE UNIQUE INDEX IF NOT EXISTS {} ON vocabulary(word_key, category)", NORMALIZED_WORD_INDEX),
+    };
+    conn.execute(&sql, [])?;
+    Ok(mode)
+}
+
+/// Mode of the unique index in use (None while words aren't unique)
+pub fn unique_index_mode(conn: &Connection) -> SqliteResult<Option<UniquenessMode>> {
+    let index: Option<String> = conn.query_row(
+        "SELECT name FROM sqlite_master WHERE type = 'index' AND name IN (?1, ?2)",
+        params![UNIQUE_WORD_INDEX, NORMALIZED_WORD_INDEX],
+        |row| row.get(0),
+    ).optional()?;
+    Ok(index.map(|name| if name == NORMALIZED_WORD_INDEX { UniquenessMode::Normalized } else { UniquenessMode::Exact }))
+}
+
+/// Move a word to a category. While words are unique, a word the category already has
+/// (in the trash or not) is folded together with the moved one instead of clashing:
+/// the moved word goes into a live match, a match in the trash goes into the moved word.
+/// Returns whether the moved word was folded away.
+fn move_word(conn: &Connection, word_id: i64, category: &str, unique: Option<UniquenessMode>) -> SqliteResult<bool> {
+    if let Some(mode) = unique {
+        let word: String = conn.query_row("SELECT word FROM vocabulary WHERE id = ?1", params![word_id], |row| row.get(0))?;
+        match unique_clash(conn, mode, &word, category, word_id)? {
+            Some((existing, true)) => fold_word_into(conn, existing, word_id)?,
+            Some((existing, false)) => {
+                fold_word_into(conn, word_id, existing)?;
+                return Ok(true);
+            }
+            None => {}
+        }
+    }
+    conn.execute(
+        "UPDATE vocabulary SET category = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+        params![category, word_id],
+    )?;
+    Ok(false)
+}
+
+/// Word other than `word_id` that `word` would clash with in `category` under the unique
+/// index, with whether it is in the trash
+fn unique_clash(conn: &Connection, mode: UniquenessMode, word: &str, category: &str, word_id: i64) -> SqliteResult<Option<(i64, bool)>> {
+    let (column, value) = match mode {
+        UniquenessMode::Exact => ("word", word.to_string()),
+        UniquenessMode::Normalized => ("word_key", word_key(word)),
+    };
+    conn.query_row(
+        &format!("SELECT id, deleted_at IS NOT NULL FROM vocabulary WHERE {} = ?1 AND category = ?2 AND id != ?3", column),
+        params![value, category, word_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    ).optional()
+}
+
+/// Ids of a category's words, including those in the trash
+fn category_word_ids(conn: &Connection, category: &str) -> SqliteResult<Vec<i64>> {
+    let mut stmt = conn.prepare(
+        concat!("SELECT id FROM vocabulary WHERE ", category_or_default!("category"), " = ?1 ORDER BY id")
+    )?;
+    let ids = stmt.query_map(params![category], |row| row.get(0))?
+        .filter_map(|id| id.ok())
+        .collect();
+    Ok(ids)
+}
+
+/// Upsert statement for one word; existing rows keep their id, import, history and
+/// spelling (and come back out of the trash)
+fn upsert_word_sql(mode: UniquenessMode) -> String {
+    let target = match mode {
+        UniquenessMode::Exact => "word, category",
+        UniquenessMode::Normalized => "word_key, category",
+    };
+    format!("{}{}{}", UPSERT_WORD_INSERT, target, UPSERT_WORD_UPDATE)
+}
+
+const UPSERT_WORD_INSERT: &str =
+    "INSERT INTO vocabulary (word, meaning, synonyms, antonyms, category, extra, reading, import_id, notes, pos, difficulty, frequency_rank, word_key)
+     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+     ON CONFLICT(";
+
+const UPSERT_WORD_UPDATE: &str = ") DO UPDATE SET
+        meaning = excluded.meaning, synonyms = excluded.synonyms, antonyms = excluded.antonyms,
+        extra = COALESCE(excluded.extra, extra), reading = COALESCE(excluded.reading, reading),
+        notes = COALESCE(excluded.notes, notes), pos = COALESCE(excluded.pos, pos),
+        difficulty = COALESCE(excluded.difficulty, difficulty),
+        frequency_rank = COALESCE(excluded.frequency_rank, frequency_rank),
+        updated_at = CURRENT_TIMESTAMP, deleted_at = NULL
+     RETURNING id";
+
+/// Load vocabulary from parsed Excel data with category
+pub fn load_vocabulary(conn: &Connection, words: Vec<Word>, category: &str) -> SqliteResult<usize> {
+    load_vocabulary_for_import(conn, words, category, None, LoadMode::Append)
+}
+
+/// Load vocabulary, tagging each inserted row with the journal entry of the import adding it.
+/// Upserting creates the unique index first. All words are written in one transaction
+/// (the caller's, if one is open), so a failed load adds nothing.
+pub fn load_vocabulary_for_import(conn: &Connection, words: Vec<Word>, category: &str, import_id: Option<i64>, mode: LoadMode) -> SqliteResult<usize> {
+    // One commit instead of one per row; this is what makes large imports fast
+    let tx = if conn.is_autocommit() { Some(conn.unchecked_transaction()?) } else { None };
+    let sql = match mode {
+        LoadMode::Upsert => upsert_word_sql(enable_unique_words(conn)?),
+        LoadMode::Append => "INSERT INTO vocabulary (word, meaning, synonyms, antonyms, category, extra, reading, import_id, notes, pos, difficulty, frequency_rank, word_key)
+                             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)".to_string(),
+    };
+    let mut insert = conn.prepare_cached(&sql)?;
+    // Re-importing the same sheet in upsert mode must not repeat the sentence
+    let mut insert_example = conn.prepare_cached(
+        "INSERT INTO examples (word_id, sentence, source)
+         SELECT ?1, ?2, ?3 WHERE NOT EXISTS (SELECT 1 FROM examples WHERE word_id = ?1 AND sentence = ?2)",
+    )?;
+    
+    let mut count = 0;
+    for word in words {
+        let extra = if word.extra.is_empty() { None } else { Some(word.extra) };
+        let reading = if word.reading.is_empty() { None } else { Some(word.reading) };
+        let notes = if word.notes.is_empty() { None } else { Some(word.notes) };
+        let pos = if word.pos.is_empty() { None } else { Some(word.pos) };
+        let key = word_key(&word.word);
+        let values = params![word.word, word.meaning, word.synonyms, word.antonyms, category, extra, reading, import_id, notes, pos, word.difficulty, word.frequency_rank, key];
+        let word_id = match mode {
+            LoadMode::Append => insert.insert(values)?,
+            LoadMode::Upsert => insert.query_row(values, |row| row.get::<_, i64>(0))?,
+        };
+        if !word.example.is_empty() {
+            insert_example.execute(params![word_id, word.example, IMPORT_EXAMPLE_SOURCE])?;
+        }
+        count += 1;
+    }
+    drop((insert, insert_example));
+    if let Some(tx) = tx {
+        tx.commit()?;
+    }
+    Ok(count)
+}
+
+/// Get words with ordering and optional category, tag, part-of-speech and difficulty
+/// filters (a difficulty range leaves out words without a difficulty), or only starred words
+#[allow(clippy::too_many_arguments)]
+pub fn get_words(
+    conn: &Connection,
+    order: &str,
+    letter: Option<char>,
+    categories: Option<Vec<String>>,
+    tags: Option<&[String]>,
+    pos: Option<&[String]>,
+    difficulty: Option<RangeInclusive<i64>>,
+    starred_only: bool,
+) -> SqliteResult<Vec<Word>> {
+    let base_query = format!("SELECT {} FROM vocabulary v WHERE v.deleted_at IS NULL", WORD_COLUMNS);
+    let where_clause = word_filter(categories.as_deref(), tags, pos, difficulty, starred_only);
+    
+    let order_clause = match order.to_lowercase().as_str() {
+        "a_to_z" => " ORDER BY v.word COLLATE UNICODE ASC",
+        "z_to_a" => " ORDER BY v.word COLLATE UNICODE DESC",
+        "frequency" => " ORDER BY v.frequency_rank IS NULL, v.frequency_rank, v.word COLLATE UNICODE",
+        "random" => " ORDER BY RANDOM()",
+        _ => " ORDER BY v.word COLLATE UNICODE ASC",
+    };
+    
+    let query = format!("{}{}{}", base_query, where_clause, order_clause);
+    
+    let mut stmt = conn.prepare(&query)?;
+    let word_iter = stmt.query_map([], word_from_row)?;
+    
+    // Letter filter runs in Rust: SQLite's LOWER/LIKE only fold ASCII, which breaks
+    // Cyrillic, Greek and accented initials
+    let wanted = letter.map(base_initial);
+    let words: Vec<Word> = word_iter
+        .filter_map(|w| w.ok())
+        .filter(|w| wanted.is_none() || initial_of(&w.word) == wanted)
+        .collect();
+    Ok(words)
+}
+
+/// Word list filters as " AND ..." conditions on `v` (empty when unfiltered)
+fn word_filter(categories: Option<&[String]>, tags: Option<&[String]>, pos: Option<&[String]>, difficulty: Option<RangeInclusive<i64>>, starred_only: bool) -> String {
+    category_condition(categories).into_iter()
+        .chain(tag_condition(tags))
+        .chain(pos_condition(pos))
+        .chain(difficulty.map(|d| format!("v.difficulty BETWEEN {} AND {}", d.start(), d.end())))
+        .chain(starred_only.then(|| "v.starred_at IS NOT NULL".to_string()))
+        .map(|condition| format!(" AND {}", condition))
+        .collect()
+}
+
+/// Orders `get_words_page` supports; both end on the word id so ties have a stable order
+pub const PAGE_ORDERS: &[&str] = &["a_to_z", "z_to_a"];
+
+/// One page of words in alphabetical order ("z_to_a" for reverse, anything else A to Z),
+/// starting after the word `after` = (word, id) when given. Takes the same filters as
+/// `get_words`. Seeks with an index instead of skipping rows, so a page near the end
+/// of a large deck costs the same as the first one.
+#[allow(clippy::too_many_arguments)]
+pub fn get_words_page(
+    conn: &Connection,
+    order: &str,
+    after: Option<(&str, i64)>,
+    limit: usize,
+    letter: Option<char>,
+    categories: Option<&[String]>,
+    tags: Option<&[String]>,
+    pos: Option<&[String]>,
+    difficulty: Option<RangeInclusive<i64>>,
+    starred_only: bool,
+) -> SqliteResult<Vec<Word>> {
+    let (comparison, direction) = if order.eq_ignore_ascii_case("z_to_a") { ("<", "DESC") } else { (">", "ASC") };
+    let filter = word_filter(categories, tags, pos, difficulty, starred_only);
+    let wanted = letter.map(base_initial);
+    
+    let mut cursor = after.map(|(word, id)| (word.to_string(), id));
+    let mut page = Vec::new();
+    while page.len() < limit {
+        // Collating the bound value (not the column) lets the seek use idx_vocabulary_word_unicode
+        let keyset = if cursor.is_some() { format!(" AND (v.word, v.id) {} (?2 COLLATE UNICODE, ?3)", comparison) } else { String::new() };
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM vocabulary v WHERE v.deleted_at IS NULL{}{} ORDER BY v.word COLLATE UNICODE {dir}, v.id {dir} LIMIT ?1",
+            WORD_COLUMNS, filter, keyset, dir = direction
+        ))?;
+        let batch: Vec<Word> = match &cursor {
+            Some((word, id)) => stmt.query_map(params![limit as i64, word, id], word_from_row)?,
+            None => stmt.query_map(params![limit as i64], word_from_row)?,
+        }
+        .filter_map(|w| w.ok())
+        .collect();
+        
+        let exhausted = batch.len() < limit;
+        cursor = batch.last().map(|w| (w.word.clone(), w.id)).or(cursor);
+        // The letter filter runs in Rust (see `get_words`), so keep reading until the page fills
+        page.extend(batch.into_iter().filter(|w| wanted.is_none() || initial_of(&w.word) == wanted));
+        if exhausted {
+            break;
+        }
+    }
+    page.truncate(limit);
+    Ok(page)
+}
+
+/// SQL condition restricting `v.category` to the given categories (None when unfiltered)
+pub(crate) fn category_condition(categories: Option<&[String]>) -> Option<String> {
+    let cats = categories.filter(|c| !c.is_empty())?;
+    let cat_list: Vec<String> = cats.iter().map(|c| format!("'{}'", c.replace("'", "''"))).collect();
+    let cat_list = cat_list.join(", ");
+    // Same as category_or_default!("v.category") IN (...), spelled so each branch can use
+    // the category index
+    let default = "COALESCE((SELECT value FROM settings WHERE key = 'default_category'), 'Default')";
+    Some(format!(
+        "(v.category IN ({list}) OR v.category IS NULL AND {default} IN ({list}) OR v.category = '' AND {default} IN ({list}))",
+        list = cat_list,
+        default = default,
+    ))
+}
+
+/// SQL condition restricting `v.id` to words carrying any of the given tags (None when unfiltered)
+pub(crate) fn tag_condition(tags: Option<&[String]>) -> Option<String> {
+    let tags = tags.filter(|t| !t.is_empty())?;
+    let tag_list: Vec<String> = tags.iter().map(|t| format!("'{}'", t.replace("'", "''"))).collect();
+    Some(format!(
+        "v.id IN (SELECT wt.word_id FROM word_tags wt JOIN tags t ON t.id = wt.tag_id WHERE t.name IN ({}))",
+        tag_list.join(", ")
+    ))
+}
+
+/// SQL condition restricting `v.pos` to the given parts of speech, ignoring case (None when unfiltered)
+pub(crate) fn pos_condition(pos: Option<&[String]>) -> Option<String> {
+    let pos = pos.filter(|p| !p.is_empty())?;
+    let pos_list: Vec<String> = pos.iter()
+        .map(|p| format!("'{}'", p.trim().to_lowercase().replace("'", "''")))
+        .collect();
+    Some(format!("LOWER(TRIM(v.pos)) IN ({})", pos_list.join(", ")))
+}
+
+/// Index letter of a word, ignoring leading whitespace: case and accents are folded,
+/// so "Äpfel" is listed under "A"
+pub(crate) fn initial_of(word: &str) -> Option<char> {
+    word.trim_start().chars().next().map(base_initial)
+}
+
+/// Distinct index letters of the words present (any script), in dictionary order
+pub fn get_initials(conn: &Connection, categories: Option<&[String]>) -> SqliteResult<Vec<String>> {
+    let query = match category_condition(categories) {
+        Some(condition) => format!("SELECT v.word FROM vocabulary v WHERE v.deleted_at IS NULL AND {}", condition),
+        None => "SELECT v.word FROM vocabulary v WHERE v.deleted_at IS NULL".to_string(),
+    };
+    let mut stmt = conn.prepare(&query)?;
+    let words = stmt.query_map([], |row| row.get::<_, String>(0))?;
+    
+    let initials: BTreeSet<char> = words
+        .filter_map(|w| w.ok())
+        .filter_map(|w| initial_of(&w))
+        .collect();
+    let mut initials: Vec<String> = initials.into_iter().map(String::from).collect();
+    initials.sort_by(|a, b| unicode_compare(a, b));
+    Ok(initials)
+}
+
+/// Get single word by ID
+pub fn get_word_by_id(conn: &Connection, word_id: i64) -> SqliteResult<Option<Word>> {
+    let mut stmt = conn.prepare(
+        &format!("SELECT {} FROM vocabulary v WHERE v.id = ?1 AND v.deleted_at IS NULL", WORD_COLUMNS)
+    )?;
+    
+    let result = stmt.query_row(params![word_id], word_from_row);
+    
+    match result {
+        Ok(word) => Ok(Some(word)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Fields `search_words` can look in, in relevance order
+pub const SEARCH_FIELDS: &[&str] = &["word", "reading", "synonyms", "antonyms", "meaning"];
+
+/// Words whose `fields` contain `query` (case-insensitive substring). Results are ranked:
+/// exact word, word prefix, word substring, then matches in the other fields in
+/// `SEARCH_FIELDS` order; shorter words first within a rank. Unknown fields are ignored.
+pub fn search_words(conn: &Connection, query: &str, fields: &[String], limit: Option<usize>) -> SqliteResult<Vec<Word>> {
+    let query = query.trim();
+    let fields: Vec<&str> = SEARCH_FIELDS.iter().copied().filter(|f| fields.iter().any(|g| g == f)).collect();
+    if query.is_empty() || fields.is_empty() {
+        return Ok(Vec::new());
+    }
+    
+    let escaped = query.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    let like = |field: &str, pattern: &str| format!("v.{} LIKE {} ESCAPE '\\'", field, pattern);
+    
+    let conditions: Vec<String> = fields.iter().map(|f| like(f, "'%' || ?1 || '%'")).collect();
+    let mut ranks = Vec::new();
+    if fields.contains(&"word") {
+        ranks.push("WHEN LOWER(v.word) = LOWER(?2) THEN 0".to_string());
+        ranks.push(format!("WHEN {} THEN 1", like("word", "?1 || '%'")));
+    }
+    for (i, field) in fields.iter().enumerate() {
+        ranks.push(format!("WHEN {} THEN {}", like(field, "'%' || ?1 || '%'"), i + 2));
+    }
+    
+    let limit_clause = limit.map(|l| format!(" LIMIT {}", l)).unwrap_or_default();
+    let sql = format!(
+        "SELECT {} FROM vocabulary v WHERE v.deleted_at IS NULL AND ({}) ORDER BY CASE {} END, LENGTH(v.word), v.word{}",
+        WORD_COLUMNS, conditions.join(" OR "), ranks.join(" "), limit_clause
+    );
+    
+    let mut stmt = conn.prepare(&sql)?;
+    let values = if fields.contains(&"word") { vec![escaped, query.to_string()] } else { vec![escaped] };
+    let words = stmt.query_map(params_from_iter(values), word_from_row)?;
+    Ok(words.filter_map(|w| w.ok()).collect())
+}
+
+/// Get several words in one query, in the order of `ids`: None for unknown or deleted
+/// ids, and repeated ids repeat the word
+pub fn get_words_by_ids(conn: &Connection, ids: &[i64]) -> SqliteResult<Vec<Option<Word>>> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM vocabulary v WHERE v.id IN (SELECT value FROM json_each(?1)) AND v.deleted_at IS NULL",
+        WORD_COLUMNS
+    ))?;
+    let id_list = Value::from(ids.to_vec()).to_string();
+    let by_id: HashMap<i64, Word> = stmt.query_map(params![id_list], word_from_row)?
+        .filter_map(|w| w.ok())
+        .map(|w| (w.id, w))
+        .collect();
+    
+    Ok(ids.iter().map(|id| by_id.get(id).cloned()).collect())
+}
+
+/// Get all words (for MCQ option generation)
+pub fn get_all_words(conn: &Connection) -> SqliteResult<Vec<Word>> {
+    get_words(conn, "random", None, None, None, None, None, false)
+}
+
+/// Words added in the last `since_days` days, newest first
+pub fn get_recently_added_words(conn: &Connection, since_days: f64, limit: Option<usize>) -> SqliteResult<Vec<Word>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM vocabulary v
+         WHERE v.deleted_at IS NULL AND v.created_at >= datetime('now', ?1)
+         ORDER BY v.created_at DESC, v.id DESC LIMIT ?2",
+        WORD_COLUMNS
+    ))?;
+    let limit = limit.map_or(-1, |l| l as i64);
+    let words = stmt.query_map(params![format!("-{} days", since_days), limit], word_from_row)?
+        .filter_map(|w| w.ok())
+        .collect();
+    Ok(words)
+}
+
+/// Add a single word to the database
+pub fn add_word(conn: &Connection, word: &str, meaning: &str, synonyms: &str, antonyms: &str, category: &str, reading: Option<&str>) -> SqliteResult<i64> {
+    conn.execute(
+        "INSERT INTO vocabulary (word, meaning, synonyms, antonyms, category, reading, word_key) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![word, meaning, synonyms, antonyms, category, reading.filter(|r| !r.is_empty()), word_key(word)],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Add a word, or update the word with the same text in the category. Returns its id.
+pub fn add_word_upsert(conn: &Connection, word: &str, meaning: &str, synonyms: &str, antonyms: &str, category: &str, reading: Option<&str>) -> SqliteResult<i64> {
+    let mode = enable_unique_words(conn)?;
+    let reading = reading.filter(|r| !r.is_empty());
+    conn.query_row(
+        &upsert_word_sql(mode),
+        params![word, meaning, synonyms, antonyms, category, None::<String>, reading, None::<i64>, None::<String>, None::<String>, None::<i64>, None::<i64>, word_key(word)],
+        |row| row.get(0),
+    )
+}
+
+/// Outcome of one entry of `add_words`
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct AddWordResult {
+    /// Position of the entry in the submitted list
+    #[pyo3(get)]
+    pub index: usize,
+    /// Id of the new word (None if the entry was rejected)
+    #[pyo3(get)]
+    pub id: Option<i64>,
+    #[pyo3(get)]
+    pub issues: Vec<ValidationIssue>,
+}
+
+#[pymethods]
+impl AddWordResult {
+    fn __repr__(&self) -> String {
+        match self.id {
+            Some(id) => format!("AddWordResult(index={}, id={})", self.index, id),
+            None => format!("AddWordResult(index={}, issues={})", self.index, self.issues.len()),
+        }
+    }
+}
+
+/// Add many words in one transaction. Entries failing validation (or duplicating a word
+/// already in their category, with `check_duplicates`) are skipped and reported; the
+/// others are added. Entries without a category go to `default_category`.
+pub fn add_words(conn: &mut Connection, entries: &[WordUpdate], default_category: &str, check_duplicates: bool) -> SqliteResult<Vec<AddWordResult>> {
+    let tx = conn.transaction()?;
+    let mut results = Vec::with_capacity(entries.len());
+    
+    for (index, entry) in entries.iter().enumerate() {
+        let entry = WordUpdate {
+            category: entry.category.clone().or_else(|| Some(default_category.to_string())),
+            ..entry.clone()
+        };
+        let fields = match validate_word(&entry, true) {
+            Ok(fields) => fields,
+            Err(issues) => {
+                results.push(AddWordResult { index, id: None, issues });
+                continue;
+            }
+        };
+        let (word, category) = (fields.word.unwrap_or_default(), fields.category.unwrap_or_default());
+        
+        if check_duplicates && find_duplicate(&tx, &word, &category, None)?.is_some() {
+            results.push(AddWordResult { index, id: None, issues: vec![duplicate_issue(&word, &category)] });
+            continue;
+        }
+        
+        let id = add_word(
+            &tx, &word, &fields.meaning.unwrap_or_default(), &fields.synonyms.unwrap_or_default(),
+            &fields.antonyms.unwrap_or_default(), &category, fields.reading.as_deref(),
+        )?;
+        let details = WordUpdate { notes: fields.notes, pos: fields.pos, ..Default::default() };
+        if !details.is_empty() {
+            update_word(&tx, id, &details)?;
+        }
+        results.push(AddWordResult { index, id: Some(id), issues: Vec::new() });
+    }
+    
+    tx.commit()?;
+    Ok(results)
+}
+
+/// Fields to change in `update_word`; None leaves a field unchanged
+#[derive(Debug, Clone, Default)]
+pub struct WordUpdate {
+    pub word: Option<String>,
+    pub meaning: Option<String>,
+    pub synonyms: Option<String>,
+    pub antonyms: Option<String>,
+    pub category: Option<String>,
+    /// An empty reading clears it
+    pub reading: Option<String>,
+    /// Empty notes clear them
+    pub notes: Option<String>,
+    /// An empty part of speech clears it
+    pub pos: Option<String>,
+}
+
+impl WordUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.word.is_none() && self.meaning.is_none() && self.synonyms.is_none()
+            && self.antonyms.is_none() && self.category.is_none() && self.reading.is_none()
+            && self.notes.is_none() && self.pos.is_none()
+    }
+}
+
+/// Edit fields of an existing word and bump its `updated_at`. A new category takes the
+/// word's authored questions along; with the word text unchanged it goes through
+/// `move_word`. While words are unique, new text the target category already has in the
+/// trash is folded into the word, and text it has outside the trash is refused.
+/// Returns false if the word doesn't exist.
+pub fn update_word(conn: &Connection, word_id: i64, update: &WordUpdate) -> SqliteResult<bool> {
+    if update.is_empty() {
+        return Ok(get_word_by_id(conn, word_id)?.is_some());
+    }
+    let current: Option<(String, Option<String>)> = conn.query_row(
+        "SELECT word, category FROM vocabulary WHERE id = ?1",
+        params![word_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    ).optional()?;
+    let Some((current_word, current_category)) = current else {
+        return Ok(false);
+    };
+    let new_word = update.word.as_deref().filter(|w| *w != current_word);
+    let new_category = update.category.as_deref().filter(|c| Some(*c) != current_category.as_deref());
+    let unique = unique_index_mode(conn)?;
+    
+    if let (Some(word), Some(mode)) = (new_word, unique) {
+        let category = new_category.map(String::from).or(current_category).unwrap_or_default();
+        match unique_clash(conn, mode, word, &category, word_id)? {
+            Some((_, false)) => {
+                return Err(rusqlite::Error::SqliteFailure(
+                    rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_CONSTRAINT_UNIQUE),
+                    Some(duplicate_issue(word, &category).message),
+                ));
+            }
+            Some((existing, true)) => fold_word_into(conn, existing, word_id)?,
+            None => {}
+        }
+    }
+    
+    // Text and category change together so the unique index never sees a half-done edit
+    let moved_directly = new_category.filter(|_| new_word.is_some());
+    let updated = conn.execute(
+        "UPDATE vocabulary SET
+            word = COALESCE(?1, word),
+            meaning = COALESCE(?2, meaning),
+            synonyms = COALESCE(?3, synonyms),
+            antonyms = COALESCE(?4, antonyms),
+            category = COALESCE(?5, category),
+            reading = CASE WHEN ?6 IS NULL THEN reading ELSE NULLIF(?6, '') END,
+            notes = CASE WHEN ?8 IS NULL THEN notes ELSE NULLIF(?8, '') END,
+            pos = CASE WHEN ?9 IS NULL THEN pos ELSE NULLIF(?9, '') END,
+            word_key = COALESCE(?10, word_key),
+            updated_at = CURRENT_TIMESTAMP
+         WHERE id = ?7",
+        params![update.word, update.meaning, update.synonyms, update.antonyms, moved_directly, update.reading, word_id, update.notes, update.pos, update.word.as_deref().map(word_key)],
+    )?;
+    if let Some(category) = new_category {
+        conn.execute(
+            "UPDATE custom_questions SET category = ?1 WHERE word_id = ?2",
+            params![category, word_id],
+        )?;
+        if moved_directly.is_none() {
+            move_word(conn, word_id, category, unique)?;
+        }
+    }
+    Ok(updated > 0)
+}
+
+/// Set or clear a word's difficulty. Returns false if the word doesn't exist.
+pub fn set_difficulty(conn: &Connection, word_id: i64, difficulty: Option<i64>) -> SqliteResult<bool> {
+    let updated = conn.execute(
+        "UPDATE vocabulary SET difficulty = ?2, updated_at = CURRENT_TIMESTAMP WHERE id = ?1",
+        params![word_id, difficulty],
+    )?;
+    Ok(updated > 0)
+}
+
+/// Set a word's media paths: None leaves a path unchanged, an empty path clears it.
+/// Returns false if the word doesn't exist.
+pub fn set_media(conn: &Connection, word_id: i64, audio_path: Option<&str>, image_path: Option<&str>) -> SqliteResult<bool> {
+    let updated = conn.execute(
+        "UPDATE vocabulary SET
+            audio_path = CASE WHEN ?2 IS NULL THEN audio_path ELSE NULLIF(?2, '') END,
+            image_path = CASE WHEN ?3 IS NULL THEN image_path ELSE NULLIF(?3, '') END,
+            updated_at = CURRENT_TIMESTAMP
+         WHERE id = ?1",
+        params![word_id, audio_path, image_path],
+    )?;
+    Ok(updated > 0)
+}
+
+/// Set frequency ranks by word text (ignoring case, in every category). Returns how many
+/// words got a rank.
+pub fn set_frequency_ranks(conn: &mut Connection, ranks: &[(String, i64)]) -> SqliteResult<usize> {
+    let tx = conn.transaction()?;
+    let mut updated = 0;
+    {
+        let mut stmt = tx.prepare("UPDATE vocabulary SET frequency_rank = ?2 WHERE word = ?1 COLLATE NOCASE")?;
+        for (word, rank) in ranks {
+            updated += stmt.execute(params![word, rank])?;
+        }
+    }
+    tx.commit()?;
+    Ok(updated)
+}
+
+/// Set a word's notes (an empty text clears them). Returns false if the word doesn't exist.
+pub fn set_note(conn: &Connection, word_id: i64, text: &str) -> SqliteResult<bool> {
+    update_word(conn, word_id, &WordUpdate { notes: Some(text.to_string()), ..Default::default() })
+}
+
+/// Get all categories with word counts
+pub fn get_categories(conn: &Connection) -> SqliteResult<Vec<CategoryInfo>> {
+    let mut stmt = conn.prepare(concat!(
+        "SELECT ", category_or_default!("category"), " as cat, COUNT(*) FROM vocabulary WHERE deleted_at IS NULL GROUP BY cat ORDER BY cat"
+    ))?;
+    
+    let cat_iter = stmt.query_map([], |row| {
+        Ok(CategoryInfo {
+            name: row.get(0)?,
+            word_count: row.get(1)?,
+        })
+    })?;
+    
+    let categories: Vec<CategoryInfo> = cat_iter.filter_map(|c| c.ok()).collect();
+    Ok(categories)
+}
+
+/// Tables with rows belonging to a word (by `word_id`), deleted along with it
+const WORD_DEPENDENT_TABLES: &[&str] = &[
+    "attempts", "custom_questions", "examples", "question_ratings", "enrichment_items", "srs_state", "word_tags",
+];
+
+/// Delete a single word together with its attempts, authored questions, examples,
+/// ratings and enrichment state. Returns false if the word doesn't exist.
+pub fn delete_word(conn: &Connection, word_id: i64) -> SqliteResult<bool> {
+    for table in WORD_DEPENDENT_TABLES {
+        conn.execute(&format!("DELETE FROM {} WHERE word_id = ?1", table), params![word_id])?;
+    }
+    
+    let deleted = conn.execute("DELETE FROM vocabulary WHERE id = ?1", params![word_id])?;
+    Ok(deleted > 0)
+}
+
+/// Delete several words in one transaction; returns how many existed and were deleted
+pub fn delete_words(conn: &mut Connection, word_ids: &[i64]) -> SqliteResult<usize> {
+    let tx = conn.transaction()?;
+    let mut deleted = 0;
+    for &word_id in word_ids {
+        if delete_word(&tx, word_id)? {
+            deleted += 1;
+        }
+    }
+    tx.commit()?;
+    Ok(deleted)
+}
+
+/// Ids of words the user chose to ignore
+pub(crate) const IGNORED_WORD_IDS: &str = "SELECT id FROM vocabulary WHERE ignored = 1";
+
+/// Ignore words for good: they never appear in quizzes, review queues or statistics.
+/// Returns how many words were newly ignored.
+pub fn ignore_words(conn: &Connection, word_ids: &[i64]) -> SqliteResult<usize> {
+    set_ignored(conn, word_ids, true)
+}
+
+/// Bring ignored words back; returns how many were ignored before
+pub fn unignore_words(conn: &Connection, word_ids: &[i64]) -> SqliteResult<usize> {
+    set_ignored(conn, word_ids, false)
+}
+
+fn set_ignored(conn: &Connection, word_ids: &[i64], ignored: bool) -> SqliteResult<usize> {
+    conn.execute(
+        "UPDATE vocabulary SET ignored = ?2 WHERE id IN (SELECT value FROM json_each(?1)) AND ignored != ?2",
+        params![Value::from(word_ids.to_vec()).to_string(), ignored],
+    )
+}
+
+/// Ids of all ignored words
+pub(crate) fn ignored_word_ids(conn: &Connection) -> SqliteResult<HashSet<i64>> {
+    let mut stmt = conn.prepare(IGNORED_WORD_IDS)?;
+    let ids = stmt.query_map([], |row| row.get(0))?
+        .filter_map(|id| id.ok())
+        .collect();
+    Ok(ids)
+}
+
+/// Ignored words in alphabetical order
+pub fn get_ignored_words(conn: &Connection) -> SqliteResult<Vec<Word>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM vocabulary v WHERE v.ignored = 1 AND v.deleted_at IS NULL ORDER BY v.word COLLATE UNICODE",
+        WORD_COLUMNS
+    ))?;
+    let words = stmt.query_map([], word_from_row)?
+        .filter_map(|w| w.ok())
+        .collect();
+    Ok(words)
+}
+
+/// Pin a word so the next quiz includes it. Returns false if the word doesn't exist.
+pub fn pin_word(conn: &Connection, word_id: i64) -> SqliteResult<bool> {
+    let updated = conn.execute(
+        "UPDATE vocabulary SET pinned_at = COALESCE(pinned_at, CURRENT_TIMESTAMP) WHERE id = ?1",
+        params![word_id],
+    )?;
+    Ok(updated > 0)
+}
+
+/// Unpin a word. Returns false if it wasn't pinned.
+pub fn unpin_word(conn: &Connection, word_id: i64) -> SqliteResult<bool> {
+    let updated = conn.execute(
+        "UPDATE vocabulary SET pinned_at = NULL WHERE id = ?1 AND pinned_at IS NOT NULL",
+        params![word_id],
+    )?;
+    Ok(updated > 0)
+}
+
+/// Star a word as a favorite. Returns false if the word doesn't exist.
+pub fn star_word(conn: &Connection, word_id: i64) -> SqliteResult<bool> {
+    let updated = conn.execute(
+        "UPDATE vocabulary SET starred_at = COALESCE(starred_at, CURRENT_TIMESTAMP) WHERE id = ?1",
+        params![word_id],
+    )?;
+    Ok(updated > 0)
+}
+
+/// Unstar a word. Returns false if it wasn't starred.
+pub fn unstar_word(conn: &Connection, word_id: i64) -> SqliteResult<bool> {
+    let updated = conn.execute(
+        "UPDATE vocabulary SET starred_at = NULL WHERE id = ?1 AND starred_at IS NOT NULL",
+        params![word_id],
+    )?;
+    Ok(updated > 0)
+}
+
+/// Pinned words, optionally limited to categories, in the order they were pinned
+pub fn get_pinned_words(conn: &Connection, categories: Option<&[String]>) -> SqliteResult<Vec<Word>> {
+    let category_filter = category_condition(categories)
+        .map(|c| format!(" AND {}", c))
+        .unwrap_or_default();
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM vocabulary v WHERE v.pinned_at IS NOT NULL AND v.deleted_at IS NULL{} ORDER BY v.pinned_at, v.id",
+        WORD_COLUMNS, category_filter
+    ))?;
+    let words = stmt.query_map([], word_from_row)?
+        .filter_map(|w| w.ok())
+        .collect();
+    Ok(words)
+}
+
+/// Tag words (the tag is created on first use); returns how many words were newly tagged
+pub fn tag_words(conn: &Connection, word_ids: &[i64], tag: &str) -> SqliteResult<usize> {
+    conn.execute("INSERT OR IGNORE INTO tags (name) VALUES (?1)", params![tag])?;
+    conn.execute(
+        "INSERT OR IGNORE INTO word_tags (word_id, tag_id)
+         SELECT v.id, t.id FROM vocabulary v, tags t
+         WHERE v.id IN (SELECT value FROM json_each(?1)) AND t.name = ?2",
+        params![Value::from(word_ids.to_vec()).to_string(), tag],
+    )
+}
+
+/// Remove a tag from words; returns how many carried it
+pub fn untag_words(conn: &Connection, word_ids: &[i64], tag: &str) -> SqliteResult<usize> {
+    conn.execute(
+        "DELETE FROM word_tags
+         WHERE word_id IN (SELECT value FROM json_each(?1)) AND tag_id = (SELECT id FROM tags WHERE name = ?2)",
+        params![Value::from(word_ids.to_vec()).to_string(), tag],
+    )
+}
+
+/// Delete a tag from every word. Returns false if there was no such tag.
+pub fn delete_tag(conn: &Connection, tag: &str) -> SqliteResult<bool> {
+    conn.execute("DELETE FROM word_tags WHERE tag_id = (SELECT id FROM tags WHERE name = ?1)", params![tag])?;
+    let deleted = conn.execute("DELETE FROM tags WHERE name = ?1", params![tag])?;
+    Ok(deleted > 0)
+}
+
+/// All tags with the number of (non-trashed) words carrying them
+pub fn get_tags(conn: &Connection) -> SqliteResult<Vec<TagInfo>> {
+    let mut stmt = conn.prepare(
+        "SELECT t.name, COUNT(v.id) FROM tags t
+         LEFT JOIN word_tags wt ON wt.tag_id = t.id
+         LEFT JOIN vocabulary v ON v.id = wt.word_id AND v.deleted_at IS NULL
+         GROUP BY t.id ORDER BY t.name COLLATE NOCASE"
+    )?;
+    let tags = stmt.query_map([], |row| Ok(TagInfo {
+        name: row.get(0)?,
+        word_count: row.get(1)?,
+    }))?
+    .filter_map(|t| t.ok())
+    .collect();
+    Ok(tags)
+}
+
+/// Tags of a word in alphabetical order
+pub fn get_word_tags(conn: &Connection, word_id: i64) -> SqliteResult<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT t.name FROM word_tags wt JOIN tags t ON t.id = wt.tag_id
+         WHERE wt.word_id = ?1 ORDER BY t.name COLLATE NOCASE"
+    )?;
+    let tags = stmt.query_map(params![word_id], |row| row.get(0))?
+        .filter_map(|t| t.ok())
+        .collect();
+    Ok(tags)
+}
+
+/// Add an example sentence to a word. Returns the new example's id.
+pub fn add_example(conn: &Connection, word_id: i64, sentence: &str, source: Option<&str>) -> SqliteResult<i64> {
+    conn.execute(
+        "INSERT INTO examples (word_id, sentence, source) VALUES (?1, ?2, ?3)",
+        params![word_id, sentence, source],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Example sentences of a word, oldest first
+pub fn get_examples(conn: &Connection, word_id: i64) -> SqliteResult<Vec<Example>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, word_id, sentence, source, created_at FROM examples WHERE word_id = ?1 ORDER BY id"
+    )?;
+    let examples = stmt.query_map(params![word_id], |row| {
+        Ok(Example {
+            id: row.get(0)?,
+            word_id: row.get(1)?,
+            sentence: row.get(2)?,
+            source: row.get(3)?,
+            created_at: row.get::<_, Option<String>>(4)?.unwrap_or_default(),
+        })
+    })?
+    .filter_map(|e| e.ok())
+    .collect();
+    Ok(examples)
+}
+
+/// Ids of words in the trash
+pub(crate) const DELETED_WORD_IDS: &str = "SELECT id FROM vocabulary WHERE deleted_at IS NOT NULL";
+
+/// A word in the trash
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct TrashedWord {
+    #[pyo3(get)]
+    pub word: Word,
+    /// UTC time the word was moved to the trash
+    #[pyo3(get)]
+    pub deleted_at: String,
+}
+
+#[pymethods]
+impl TrashedWord {
+    fn __repr__(&self) -> String {
+        format!("TrashedWord(word='{}', deleted_at='{}')", self.word.word, self.deleted_at)
+    }
+}
+
+/// Move words to the trash: they disappear from every listing, quiz and statistic but
+/// keep their history until purged. Returns how many words were moved.
+pub fn soft_delete_words(conn: &Connection, word_ids: &[i64]) -> SqliteResult<usize> {
+    conn.execute(
+        "UPDATE vocabulary SET deleted_at = CURRENT_TIMESTAMP
+         WHERE id IN (SELECT value FROM json_each(?1)) AND deleted_at IS NULL",
+        params![Value::from(word_ids.to_vec()).to_string()],
+    )
+}
+
+/// Move all words of a category to the trash
+pub fn soft_delete_category(conn: &Connection, category: &str) -> SqliteResult<usize> {
+    conn.execute(
+        concat!("UPDATE vocabulary SET deleted_at = CURRENT_TIMESTAMP WHERE deleted_at IS NULL AND ", category_or_default!("category"), " = ?1"),
+        params![category],
+    )
+}
+
+/// Take words back out of the trash
+pub fn restore_words(conn: &Connection, word_ids: &[i64]) -> SqliteResult<usize> {
+    conn.execute(
+        "UPDATE vocabulary SET deleted_at = NULL
+         WHERE id IN (SELECT value FROM json_each(?1)) AND deleted_at IS NOT NULL",
+        params![Value::from(word_ids.to_vec()).to_string()],
+    )
+}
+
+/// Take all trashed words of a category back out of the trash
+pub fn restore_category(conn: &Connection, category: &str) -> SqliteResult<usize> {
+    conn.execute(
+        concat!("UPDATE vocabulary SET deleted_at = NULL WHERE deleted_at IS NOT NULL AND ", category_or_default!("category"), " = ?1"),
+        params![category],
+    )
+}
+
+/// Words in the trash, most recently deleted first
+pub fn get_trash(conn: &Connection) -> SqliteResult<Vec<TrashedWord>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {}, v.deleted_at FROM vocabulary v WHERE v.deleted_at IS NOT NULL ORDER BY v.deleted_at DESC, v.word",
+        WORD_COLUMNS
+    ))?;
+    let words = stmt.query_map([], |row| Ok(TrashedWord {
+        word: word_from_row(row)?,
+        deleted_at: row.get("deleted_at")?,
+    }))?
+    .filter_map(|w| w.ok())
+    .collect();
+    Ok(words)
+}
+
+/// Permanently delete trashed words (only those trashed at least `older_than_days` ago
+/// if given), like `delete_word`. Returns how many were deleted.
+pub fn purge_trash(conn: &mut Connection, older_than_days: Option<f64>) -> SqliteResult<usize> {
+    let tx = conn.transaction()?;
+    let ids: Vec<i64> = {
+        let mut stmt = tx.prepare(
+            "SELECT id FROM vocabulary WHERE deleted_at IS NOT NULL
+             AND (?1 IS NULL OR deleted_at <= datetime('now', ?1))"
+        )?;
+        let cutoff = older_than_days.map(|d| format!("-{} days", d));
+        let rows = stmt.query_map(params![cutoff], |row| row.get(0))?;
+        rows.filter_map(|id| id.ok()).collect()
+    };
+    for &word_id in &ids {
+        delete_word(&tx, word_id)?;
+    }
+    tx.commit()?;
+    Ok(ids.len())
+}
+
+/// Delete a category and all its words for good, with everything that depends on them,
+/// in one transaction (see `soft_delete_category` for a reversible delete)
+pub fn delete_category(conn: &mut Connection, category: &str) -> SqliteResult<usize> {
+    let tx = conn.transaction()?;
+    // Words without a category belong to the default one, as `get_categories` lists them
+    let in_category = concat!(category_or_default!("category"), " = ?1");
+    for table in WORD_DEPENDENT_TABLES {
+        tx.execute(
+            &format!("DELETE FROM {} WHERE word_id IN (SELECT id FROM vocabulary WHERE {})", table, in_category),
+            params![category],
+        )?;
+    }
+    // Authored questions filed under the category without a word
+    tx.execute(&format!("DELETE FROM custom_questions WHERE {}", in_category), params![category])?;
+    tx.execute("DELETE FROM category_meta WHERE category = ?1", params![category])?;
+    
+    let deleted = tx.execute(&format!("DELETE FROM vocabulary WHERE {}", in_category), params![category])?;
+    tx.commit()?;
+    Ok(deleted)
+}
+
+/// Name of the default category (words without one, and imports without a category)
+pub fn get_default_category(conn: &Connection) -> String {
+    get_setting(conn, DEFAULT_CATEGORY_KEY)
+        .ok()
+        .flatten()
+        .filter(|c| !c.trim().is_empty())
+        .unwrap_or_else(|| FALLBACK_CATEGORY.to_string())
+}
+
+/// Default category of a database file (the fallback if it can't be read)
+pub(crate) fn default_category_for(db_path: &str) -> String {
+    open_database(db_path)
+        .map(|conn| get_default_category(&conn))
+        .unwrap_or_else(|_| FALLBACK_CATEGORY.to_string())
+}
+
+/// Change the default category. With `rename_existing`, words and questions filed under
+/// the previous default move to the new one; without it, those with no category are
+/// filed under the previous default so they stay where they are. Returns the number of
+/// words moved.
+pub fn set_default_category(conn: &mut Connection, name: &str, rename_existing: bool) -> SqliteResult<usize> {
+    let tx = conn.transaction()?;
+    let previous = get_default_category(&tx);
+    // Read while unset categories still resolve to the previous default
+    let word_ids = category_word_ids(&tx, &previous)?;
+    let unique = unique_index_mode(&tx)?;
+    if !rename_existing && previous != name {
+        let unset: Vec<i64> = {
+            let mut stmt = tx.prepare("SELECT id FROM vocabulary WHERE category IS NULL OR category = ''")?;
+            let rows = stmt.query_map([], |row| row.get(0))?;
+            rows.filter_map(|id| id.ok()).collect()
+        };
+        for word_id in unset {
+            move_word(&tx, word_id, &previous, unique)?;
+        }
+        tx.execute(
+            "UPDATE custom_questions SET category = ?1 WHERE category IS NULL OR category = ''",
+            params![previous],
+        )?;
+    }
+    set_setting(&tx, DEFAULT_CATEGORY_KEY, Some(name))?;
+    
+    let mut moved = 0;
+    if rename_existing && previous != name {
+        for &word_id in &word_ids {
+            move_word(&tx, word_id, name, unique)?;
+        }
+        moved = word_ids.len();
+        tx.execute(
+            "UPDATE custom_questions SET category = ?2 WHERE category = ?1 OR category IS NULL OR category = ''",
+            params![previous, name],
+        )?;
+    }
+    
+    tx.commit()?;
+    Ok(moved)
+}
+
+/// Check whether any word belongs to a category
+pub fn category_exists(conn: &Connection, category: &str) -> SqliteResult<bool> {
+    conn.query_row(
+        concat!("SELECT EXISTS(SELECT 1 FROM vocabulary WHERE deleted_at IS NULL AND ", category_or_default!("category"), " = ?1)"),
+        params![category],
+        |row| row.get(0),
+    )
+}
+
+/// Rename a category along with its authored questions and deck metadata, atomically.
+/// Fails if `new` is already in use (merge the categories instead). Returns the number
+/// of words moved.
+pub fn rename_category(conn: &mut Connection, old: &str, new: &str) -> Result<usize, String> {
+    let new = new.trim();
+    if new.is_empty() {
+        return Err("New category name cannot be empty".to_string());
+    }
+    if new == old {
+        return Ok(0);
+    }
+    
+    let tx = conn.transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let db_err = |e: rusqlite::Error| format!("Failed to rename category: {}", e);
+    
+    if !category_exists(&tx, old).map_err(db_err)? {
+        return Err(format!("Category '{}' not found", old));
+    }
+    if category_exists(&tx, new).map_err(db_err)? {
+        return Err(format!("Category '{}' already exists", new));
+    }
+    
+    // `new` has no live words, but may have some in the trash that clash with the moved ones
+    let unique = unique_index_mode(&tx).map_err(db_err)?;
+    let word_ids = category_word_ids(&tx, old).map_err(db_err)?;
+    for &word_id in &word_ids {
+        move_word(&tx, word_id, new, unique).map_err(db_err)?;
+    }
+    let renamed = word_ids.len();
+    tx.execute("UPDATE custom_questions SET category = ?2 WHERE category = ?1", params![old, new])
+        .map_err(db_err)?;
+    tx.execute("UPDATE enrichment_jobs SET category = ?2 WHERE category = ?1", params![old, new])
+        .map_err(db_err)?;
+    tx.execute("DELETE FROM category_meta WHERE category = ?1", params![new])
+        .map_err(db_err)?;
+    tx.execute("UPDATE category_meta SET category = ?2 WHERE category = ?1", params![old, new])
+        .map_err(db_err)?;
+    
+    tx.commit().map_err(|e| format!("Failed to commit rename: {}", e))?;
+    Ok(renamed)
+}
+
+/// Move words (and their authored questions) to a category, creating it implicitly.
+/// Words in the trash are left where they are. While words are unique, words the category already has are folded together (see
+/// `move_word`). Returns the number of words moved.
+pub fn move_words_to_category(conn: &mut Connection, word_ids: &[i64], category: &str) -> SqliteResult<usize> {
+    let tx = conn.transaction()?;
+    let unique = unique_index_mode(&tx)?;
+    let mut moved = 0;
+    for &word_id in word_ids {
+        let exists: bool = tx.query_row("SELECT EXISTS(SELECT 1 FROM vocabulary WHERE id = ?1 AND deleted_at IS NULL)", params![word_id], |row| row.get(0))?;
+        if !exists {
+            continue;
+        }
+        tx.execute(
+            "UPDATE custom_questions SET category = ?1 WHERE word_id = ?2",
+            params![category, word_id],
+        )?;
+        move_word(&tx, word_id, category, unique)?;
+        moved += 1;
+    }
+    tx.commit()?;
+    Ok(moved)
+}
+
+/// Fold word `from` into word `into`: attempts, questions, examples and ratings move
+/// over, empty fields of `into` are filled from `from`, and `from` is deleted. The
+/// review schedule of `into` is kept (or taken over from `from` if it has none).
+pub(crate) fn fold_word_into(conn: &Connection, from: i64, into: i64) -> SqliteResult<()> {
+    for table in ["attempts", "custom_questions", "examples", "question_ratings"] {
+        conn.execute(&format!("UPDATE {} SET word_id = ?2 WHERE word_id = ?1", table), params![from, into])?;
+    }
+    conn.execute(
+        "UPDATE srs_state SET word_id = ?2 WHERE word_id = ?1
+         AND NOT EXISTS (SELECT 1 FROM srs_state WHERE word_id = ?2)",
+        params![from, into],
+    )?;
+    conn.execute("UPDATE OR IGNORE word_tags SET word_id = ?2 WHERE word_id = ?1", params![from, into])?;
+    conn.execute(
+        "UPDATE vocabulary SET
+            meaning = CASE WHEN COALESCE(meaning, '') = '' THEN (SELECT meaning FROM vocabulary WHERE id = ?1) ELSE meaning END,
+            synonyms = CASE WHEN COALESCE(synonyms, '') = '' THEN (SELECT synonyms FROM vocabulary WHERE id = ?1) ELSE synonyms END,
+            antonyms = CASE WHEN COALESCE(antonyms, '') = '' THEN (SELECT antonyms FROM vocabulary WHERE id = ?1) ELSE antonyms END,
+            reading = COALESCE(reading, (SELECT reading FROM vocabulary WHERE id = ?1)),
+            notes = COALESCE(notes, (SELECT notes FROM vocabulary WHERE id = ?1)),
+            pos = COALESCE(pos, (SELECT pos FROM vocabulary WHERE id = ?1)),
+            difficulty = COALESCE(difficulty, (SELECT difficulty FROM vocabulary WHERE id = ?1)),
+            frequency_rank = COALESCE(frequency_rank, (SELECT frequency_rank FROM vocabulary WHERE id = ?1)),
+            audio_path = COALESCE(audio_path, (SELECT audio_path FROM vocabulary WHERE id = ?1)),
+            image_path = COALESCE(image_path, (SELECT image_path FROM vocabulary WHERE id = ?1)),
+            pinned_at = COALESCE(pinned_at, (SELECT pinned_at FROM vocabulary WHERE id = ?1)),
+            starred_at = COALESCE(starred_at, (SELECT starred_at FROM vocabulary WHERE id = ?1))
+         WHERE id = ?2",
+        params![from, into],
+    )?;
+    delete_word(conn, from)?;
+    Ok(())
+}
+
+/// Move every word of `sources` into `target` (created implicitly), keeping attempt
+/// history. With `dedupe`, words in the target with the same text (ignoring case and
+/// surrounding spaces) are folded into the oldest of them. While words are unique, words
+/// the target already has are always folded together (see `move_word`).
+pub fn merge_categories(conn: &mut Connection, sources: &[String], target: &str, dedupe: bool) -> SqliteResult<MergeResult> {
+    let tx = conn.transaction()?;
+    let mut result = MergeResult::default();
+    let unique = unique_index_mode(&tx)?;
+    
+    for source in sources.iter().filter(|s| s.as_str() != target) {
+        for word_id in category_word_ids(&tx, source)? {
+            if move_word(&tx, word_id, target, unique)? {
+                result.duplicates_removed += 1;
+            } else {
+                result.moved += 1;
+            }
+        }
+        tx.execute("UPDATE custom_questions SET category = ?2 WHERE category = ?1", params![source, target])?;
+        
+        // The target keeps its own metadata; otherwise it inherits the first source's
+        tx.execute(
+            "UPDATE category_meta SET category = ?2 WHERE category = ?1
+             AND NOT EXISTS (SELECT 1 FROM category_meta WHERE category = ?2)",
+            params![source, target],
+        )?;
+        tx.execute("DELETE FROM category_meta WHERE category = ?1", params![source])?;
+    }
+    
+    if dedupe {
+        let words: Vec<(i64, String)> = {
+            let mut stmt = tx.prepare(
+                concat!("SELECT id, LOWER(TRIM(word)) FROM vocabulary WHERE deleted_at IS NULL AND ", category_or_default!("category"), " = ?1 ORDER BY id")
+            )?;
+            let rows = stmt.query_map(params![target], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            rows.filter_map(|r| r.ok()).collect()
+        };
+        
+        let mut kept: HashMap<String, i64> = HashMap::new();
+        for (id, text) in words {
+            match kept.get(&text) {
+                Some(&into) => {
+                    fold_word_into(&tx, id, into)?;
+                    result.duplicates_removed += 1;
+                }
+                None => { kept.insert(text, id); }
+            }
+        }
+    }
+    
+    tx.commit()?;
+    Ok(result)
+}
+
+/// Read a setting (None when unset)
+pub fn get_setting(conn: &Connection, key: &str) -> SqliteResult<Option<String>> {
+    conn.query_row("SELECT value FROM settings WHERE key = ?1", params![key], |row| row.get(0))
+        .optional()
+        .map(Option::flatten)
+}
+
+/// Store a setting, or remove it when value is None
+pub fn set_setting(conn: &Connection, key: &str, value: Option<&str>) -> SqliteResult<()> {
+    match value {
+        Some(v) => conn.execute(
+            "INSERT INTO settings (key, value) VALUES (?1, ?2) ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![key, v],
+        )?,
+        None => conn.execute("DELETE FROM settings WHERE key = ?1", params![key])?,
+    };
+    Ok(())
+}
+
+/// Parse a word's `extra` column into a JSON object (empty when unset or malformed)
+fn parse_extra(extra: Option<String>) -> Map<String, Value> {
+    extra
+        .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+        .and_then(|v| match v {
+            Value::Object(map) => Some(map),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+/// Get a single extra field of a word (None if the word or key doesn't exist)
+pub fn get_extra(conn: &Connection, word_id: i64, key: &str) -> SqliteResult<Option<Value>> {
+    let extra: Option<Option<String>> = conn
+        .query_row("SELECT extra FROM vocabulary WHERE id = ?1", params![word_id], |row| row.get(0))
+        .optional()?;
+    
+    Ok(extra.and_then(|e| parse_extra(e).remove(key)))
+}
+
+/// Set (or remove, when value is None) a single extra field of a word.
+/// Returns false if the word doesn't exist.
+pub fn set_extra(conn: &Connection, word_id: i64, key: &str, value: Option<Value>) -> SqliteResult<bool> {
+    let extra: Option<Option<String>> = conn
+        .query_row("SELECT extra FROM vocabulary WHERE id = ?1", params![word_id], |row| row.get(0))
+        .optional()?;
+    
+    let Some(extra) = extra else {
+        return Ok(false);
+    };
+    
+    let mut map = parse_extra(extra);
+    match value {
+        Some(v) => { map.insert(key.to_string(), v); }
+        None => { map.remove(key); }
+    }
+    
+    let stored = if map.is_empty() { None } else { Some(Value::Object(map).to_string()) };
+    conn.execute("UPDATE vocabulary SET extra = ?1 WHERE id = ?2", params![stored, word_id])?;
+    Ok(true)
+}
+
+/// JSON path of a top-level extra field, quoted so any key works
+fn extra_path(key: &str) -> String {
+    format!("$.\"{}\"", key.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Words whose extra field `key` equals `value`, or that have the field at all when
+/// `value` is None. Filtering runs in SQLite with JSON1.
+pub fn find_words_by_field(conn: &Connection, key: &str, value: Option<&Value>, categories: Option<&[String]>) -> SqliteResult<Vec<Word>> {
+    let category_filter = category_condition(categories)
+        .map(|c| format!(" AND {}", c))
+        .unwrap_or_default();
+    let (condition, bound): (&str, rusqlite::types::Value) = match value {
+        None => ("json_type(v.extra, ?1) IS NOT NULL", rusqlite::types::Value::Null),
+        Some(Value::String(s)) => ("json_extract(v.extra, ?1) = ?2", s.clone().into()),
+        Some(Value::Bool(b)) => ("json_extract(v.extra, ?1) = ?2", (*b as i64).into()),
+        Some(Value::Number(n)) => match n.as_i64() {
+            Some(i) => ("json_extract(v.extra, ?1) = ?2", i.into()),
+            None => ("json_extract(v.extra, ?1) = ?2", n.as_f64().unwrap_or_default().into()),
+        },
+        Some(Value::Null) => ("json_type(v.extra, ?1) = 'null'", rusqlite::types::Value::Null),
+        // Objects and arrays compare as minified JSON text, the form `set_extra` stores
+        Some(other) => ("json_extract(v.extra, ?1) = ?2", other.to_string().into()),
+    };
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM vocabulary v
+         WHERE v.deleted_at IS NULL AND json_valid(v.extra) AND {}{} ORDER BY v.word COLLATE UNICODE",
+        WORD_COLUMNS, condition, category_filter
+    ))?;
+    let path = extra_path(key);
+    let rows = if condition.contains("?2") {
+        stmt.query_map(params![path, bound], word_from_row)?.filter_map(|w| w.ok()).collect()
+    } else {
+        stmt.query_map(params![path], word_from_row)?.filter_map(|w| w.ok()).collect()
+    };
+    Ok(rows)
+}
+
+// ============= Python Bindings =============
+
+/// Convert a JSON value into the equivalent Python object
+pub(crate) fn json_to_py(py: Python<'_>, value: &Value) -> PyResult<PyObject> {
+    Ok(match value {
+        Value::Null => py.None(),
+        Value::Bool(b) => b.into_pyobject(py)?.to_owned().into_any().unbind(),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => i.into_pyobject(py)?.into_any().unbind(),
+            None => n.as_f64().unwrap_or_default().into_pyobject(py)?.into_any().unbind(),
+        },
+        Value::String(s) => s.into_pyobject(py)?.into_any().unbind(),
+        Value::Array(items) => {
+            let list = PyList::empty(py);
+            for item in items {
+                list.append(json_to_py(py, item)?)?;
+            }
+            list.into_any().unbind()
+        }
+        Value::Object(map) => {
+            let dict = PyDict::new(py);
+            for (k, v) in map {
+                dict.set_item(k, json_to_py(py, v)?)?;
+            }
+            dict.into_any().unbind()
+        }
+    })
+}
+
+/// Convert a Python object (None/bool/int/float/str/list/dict) into a JSON value
+pub(crate) fn py_to_json(obj: &Bound<'_, PyAny>) -> PyResult<Value> {
+    if obj.is_none() {
+        Ok(Value::Null)
+    } else if let Ok(b) = obj.downcast::<PyBool>() {
+        Ok(Value::Bool(b.is_true()))
+    } else if let Ok(i) = obj.extract::<i64>() {
+        Ok(Value::from(i))
+    } else if let Ok(f) = obj.extract::<f64>() {
+        Ok(Value::from(f))
+    } else if let Ok(s) = obj.extract::<String>() {
+        Ok(Value::String(s))
+    } else if let Ok(list) = obj.downcast::<PyList>() {
+        list.iter().map(|item| py_to_json(&item)).collect::<PyResult<Vec<_>>>().map(Value::Array)
+    } else if let Ok(dict) = obj.downcast::<PyDict>() {
+        let mut map = Map::new();
+        for (k, v) in dict.iter() {
+            map.insert(k.extract::<String>()?, py_to_json(&v)?);
+        }
+        Ok(Value::Object(map))
+    } else {
+        Err(pyo3::exceptions::PyTypeError::new_err(
+            format!("Unsupported value type for JSON: {}", obj.get_type().name()?)
+        ))
+    }
+}
+
+#[pyfunction]
+#[pyo3(name = "init_database", signature = (db_path, passphrase=None))]
+pub fn py_init_database(db_path: &str, passphrase: Option<&str>) -> PyResult<()> {
+    if passphrase.is_some() {
+        set_passphrase(db_path, passphrase).map_err(pyo3::exceptions::PyValueError::new_err)?;
+    }
+    init_database(db_path)
+        .map(|_| ())
+        .map_err(|e| {
+            // A wrong passphrase shouldn't stick for later calls
+            if passphrase.is_some() {
+                let _ = set_passphrase(db_path, None);
+            }
+            pyo3::exceptions::PyRuntimeError::new_err(e.to_string())
+        })
+}
+
+#[pyfunction]
+#[pyo3(name = "configure_database", signature = (db_path, options=None))]
+pub fn py_configure_database(db_path: &str, options: Option<DatabaseOptions>) -> PyResult<String> {
+    let options = options.unwrap_or_default();
+    options.validate().map_err(pyo3::exceptions::PyValueError::new_err)?;
+    configure_database(db_path, &options)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "set_passphrase", signature = (db_path, passphrase=None))]
+pub fn py_set_passphrase(db_path: &str, passphrase: Option<&str>) -> PyResult<()> {
+    set_passphrase(db_path, passphrase)
+        .map_err(pyo3::exceptions::PyValueError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "close_memory_database")]
+pub fn py_close_memory_database(db_path: &str) -> bool {
+    close_memory_database(db_path)
+}
+
+#[pyfunction]
+#[pyo3(name = "get_schema_version")]
+pub fn py_get_schema_version(db_path: &str) -> PyResult<i64> {
+    let conn = init_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    schema_version(&conn)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "get_all_words")]
+pub fn py_get_all_words(db_path: &str) -> PyResult<Vec<Word>> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    get_all_words(&conn)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "get_recently_added_words", signature = (db_path, since=7.0, limit=None))]
+pub fn py_get_recently_added_words(db_path: &str, since: f64, limit: Option<usize>) -> PyResult<Vec<Word>> {
+    if since < 0.0 {
+        return Err(pyo3::exceptions::PyValueError::new_err("since must be a non-negative number of days"));
+    }
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    get_recently_added_words(&conn, since, limit)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "get_words_by_order", signature = (db_path, order, letter=None, categories=None, tags=None, pos=None, min_difficulty=None, max_difficulty=None, starred_only=false))]
+#[allow(clippy::too_many_arguments)]
+pub fn py_get_words_by_order(
+    db_path: &str,
+    order: &str,
+    letter: Option<char>,
+    categories: Option<Vec<String>>,
+    tags: Option<Vec<String>>,
+    pos: Option<Vec<String>>,
+    min_difficulty: Option<i64>,
+    max_difficulty: Option<i64>,
+    starred_only: bool,
+) -> PyResult<Vec<Word>> {
+    let difficulty = (min_difficulty.is_some() || max_difficulty.is_some()).then(|| {
+        min_difficulty.unwrap_or(*DIFFICULTY_LEVELS.start())..=max_difficulty.unwrap_or(*DIFFICULTY_LEVELS.end())
+    });
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    get_words(&conn, order, letter, categories, tags.as_deref(), pos.as_deref(), difficulty, starred_only)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "get_words_page", signature = (db_path, order="a_to_z", after_word=None, after_id=None, limit=100, letter=None, categories=None, tags=None, pos=None, min_difficulty=None, max_difficulty=None, starred_only=false))]
+#[allow(clippy::too_many_arguments)]
+pub fn py_get_words_page(
+    db_path: &str,
+    order: &str,
+    after_word: Option<&str>,
+    after_id: Option<i64>,
+    limit: usize,
+    letter: Option<char>,
+    categories: Option<Vec<String>>,
+    tags: Option<Vec<String>>,
+    pos: Option<Vec<String>>,
+    min_difficulty: Option<i64>,
+    max_difficulty: Option<i64>,
+    starred_only: bool,
+) -> PyResult<Vec<Word>> {
+    if !PAGE_ORDERS.contains(&order.to_lowercase().as_str()) {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!("Unsupported order for paging: {}", order)));
+    }
+    let after = match (after_word, after_id) {
+        (Some(word), Some(id)) => Some((word, id)),
+        (None, None) => None,
+        _ => return Err(pyo3::exceptions::PyValueError::new_err("after_word and after_id must be given together")),
+    };
+    let difficulty = (min_difficulty.is_some() || max_difficulty.is_some()).then(|| {
+        min_difficulty.unwrap_or(*DIFFICULTY_LEVELS.start())..=max_difficulty.unwrap_or(*DIFFICULTY_LEVELS.end())
+    });
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    get_words_page(&conn, order, after, limit, letter, categories.as_deref(), tags.as_deref(), pos.as_deref(), difficulty, starred_only)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "get_initials", signature = (db_path, categories=None))]
+pub fn py_get_initials(db_path: &str, categories: Option<Vec<String>>) -> PyResult<Vec<String>> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    get_initials(&conn, categories.as_deref())
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "search_words", signature = (db_path, query, fields=None, limit=Some(50)))]
+pub fn py_search_words(db_path: &str, query: &str, fields: Option<Vec<String>>, limit: Option<usize>) -> PyResult<Vec<Word>> {
+    let fields = fields.unwrap_or_else(|| vec!["word".to_string(), "meaning".to_string(), "synonyms".to_string()]);
+    if let Some(unknown) = fields.iter().find(|f| !SEARCH_FIELDS.contains(&f.as_str())) {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Unknown search field '{}'. Expected one of: {}", unknown, SEARCH_FIELDS.join(", "))));
+    }
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    search_words(&conn, query, &fields, limit)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "get_word_by_id")]
+pub fn py_get_word_by_id(db_path: &str, word_id: i64) -> PyResult<Option<Word>> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    get_word_by_id(&conn, word_id)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "get_words_by_ids")]
+pub fn py_get_words_by_ids(db_path: &str, ids: Vec<i64>) -> PyResult<Vec<Option<Word>>> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    get_words_by_ids(&conn, &ids)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "add_word", signature = (db_path, word, meaning, synonyms, antonyms, category, reading=None, check_duplicates=false, notes=None, pos=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn py_add_word(
+    py: Python<'_>,
+    db_path: &str,
+    word: &str,
+    meaning: &str,
+    synonyms: &str,
+    antonyms: &str,
+    category: &str,
+    reading: Option<&str>,
+    check_duplicates: bool,
+    notes: Option<&str>,
+    pos: Option<&str>,
+) -> PyResult<i64> {
+    let fields = WordUpdate {
+        word: Some(word.to_string()),
+        meaning: Some(meaning.to_string()),
+        synonyms: Some(synonyms.to_string()),
+        antonyms: Some(antonyms.to_string()),
+        category: Some(category.to_string()),
+        reading: reading.map(String::from),
+        notes: notes.map(String::from),
+        pos: pos.map(String::from),
+    };
+    let fields = validate_word(&fields, true).map_err(|issues| validation_error(py, issues))?;
+    let (word, category) = (fields.word.unwrap_or_default(), fields.category.unwrap_or_default());
+    
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    if check_duplicates && find_duplicate(&conn, &word, &category, None)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?
+        .is_some()
+    {
+        return Err(validation_error(py, vec![duplicate_issue(&word, &category)]));
+    }
+    
+    let id = add_word(
+        &conn, &word, &fields.meaning.unwrap_or_default(), &fields.synonyms.unwrap_or_default(),
+        &fields.antonyms.unwrap_or_default(), &category, fields.reading.as_deref(),
+    ).map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let details = WordUpdate { notes: fields.notes, pos: fields.pos, ..Default::default() };
+    if !details.is_empty() {
+        update_word(&conn, id, &details)
+            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    }
+    Ok(id)
+}
+
+#[pyfunction]
+#[pyo3(name = "add_word_upsert", signature = (db_path, word, meaning, synonyms, antonyms, category, reading=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn py_add_word_upsert(
+    py: Python<'_>,
+    db_path: &str,
+    word: &str,
+    meaning: &str,
+    synonyms: &str,
+    antonyms: &str,
+    category: &str,
+    reading: Option<&str>,
+) -> PyResult<i64> {
+    let fields = WordUpdate {
+        word: Some(word.to_string()),
+        meaning: Some(meaning.to_string()),
+        synonyms: Some(synonyms.to_string()),
+        antonyms: Some(antonyms.to_string()),
+        category: Some(category.to_string()),
+        reading: reading.map(String::from),
+        notes: None,
+        pos: None,
+    };
+    let fields = validate_word(&fields, true).map_err(|issues| validation_error(py, issues))?;
+    
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    add_word_upsert(
+        &conn, &fields.word.unwrap_or_default(), &fields.meaning.unwrap_or_default(), &fields.synonyms.unwrap_or_default(),
+        &fields.antonyms.unwrap_or_default(), &fields.category.unwrap_or_default(), fields.reading.as_deref(),
+    ).map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "set_difficulty", signature = (db_path, word_id, difficulty=None))]
+pub fn py_set_difficulty(db_path: &str, word_id: i64, difficulty: Option<i64>) -> PyResult<bool> {
+    if difficulty.is_some_and(|d| !DIFFICULTY_LEVELS.contains(&d)) {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "difficulty must be between {} and {}", DIFFICULTY_LEVELS.start(), DIFFICULTY_LEVELS.end()
+        )));
+    }
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    set_difficulty(&conn, word_id, difficulty)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "set_media", signature = (db_path, word_id, audio_path=None, image_path=None))]
+pub fn py_set_media(db_path: &str, word_id: i64, audio_path: Option<&str>, image_path: Option<&str>) -> PyResult<bool> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    set_media(&conn, word_id, audio_path.map(str::trim), image_path.map(str::trim))
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "set_note")]
+pub fn py_set_note(py: Python<'_>, db_path: &str, word_id: i64, text: &str) -> PyResult<bool> {
+    let update = WordUpdate { notes: Some(text.to_string()), ..Default::default() };
+    let update = validate_word(&update, false).map_err(|issues| validation_error(py, issues))?;
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    set_note(&conn, word_id, update.notes.as_deref().unwrap_or_default())
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
 }
 
-#[pymethods]
-impl Word {
-    fn __repr__(&self) -> String {
-        format!("Word(id={}, word='{}', category='{}')", 
-                self.id, self.word, self.category)
-    }
+#[pyfunction]
+#[pyo3(name = "enable_unique_words")]
+pub fn py_enable_unique_words(db_path: &str) -> PyResult<()> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    enable_unique_words(&conn)
+        .map(|_| ())
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to create unique index (merge duplicates first): {}", e)))
 }
 
-/// Category info with word count
-#[pyclass]
-#[derive(Debug, Clone)]
-pub struct CategoryInfo {
-    #[pyo3(get)]
-    pub name: String,
-    #[pyo3(get)]
-    pub word_count: i64,
+#[pyfunction]
+#[pyo3(name = "set_uniqueness_mode")]
+pub fn py_set_uniqueness_mode(db_path: &str, mode: &str) -> PyResult<()> {
+    let mode = UniquenessMode::parse(mode).map_err(pyo3::exceptions::PyValueError::new_err)?;
+    let mut conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    set_uniqueness_mode(&mut conn, mode)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
 }
 
-/// Initialize database with schema
-pub fn init_database(db_path: &str) -> SqliteResult<Connection> {
-    let conn = Connection::open(db_path)?;
-    
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS vocabulary (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            word TEXT NOT NULL,
-            meaning TEXT NOT NULL,
-            synonyms TEXT,
-            antonyms TEXT,
-            category TEXT DEFAULT 'Default',
-            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
-        )",
-        [],
-    )?;
+#[pyfunction]
+#[pyo3(name = "get_uniqueness_mode")]
+pub fn py_get_uniqueness_mode(db_path: &str) -> PyResult<String> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    uniqueness_mode(&conn)
+        .map(|mode| mode.as_str().to_string())
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "add_words", signature = (db_path, entries, category=None, check_duplicates=false))]
+pub fn py_add_words(db_path: &str, entries: Vec<Bound<'_, PyDict>>, category: Option<String>, check_duplicates: bool) -> PyResult<Vec<AddWordResult>> {
+    let field = |entry: &Bound<'_, PyDict>, key: &str| -> PyResult<Option<String>> {
+        match entry.get_item(key)? {
+            Some(value) if !value.is_none() => Ok(Some(value.extract()?)),
+            _ => Ok(None),
+        }
+    };
+    let entries = entries.iter().map(|entry| Ok(WordUpdate {
+        word: Some(field(entry, "word")?.unwrap_or_default()),
+        meaning: field(entry, "meaning")?,
+        synonyms: field(entry, "synonyms")?,
+        antonyms: field(entry, "antonyms")?,
+        category: field(entry, "category")?,
+        reading: field(entry, "reading")?,
+        notes: field(entry, "notes")?,
+        pos: field(entry, "pos")?,
+    })).collect::<PyResult<Vec<_>>>()?;
     
-    // Add category column if it doesn't exist (migration for existing DBs)
-    let _ = conn.execute("ALTER TABLE vocabulary ADD COLUMN category TEXT DEFAULT 'Default'", []);
+    let mut conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let category = category.unwrap_or_else(|| get_default_category(&conn));
+    add_words(&mut conn, &entries, &category, check_duplicates)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "update_word", signature = (db_path, word_id, word=None, meaning=None, synonyms=None, antonyms=None, category=None, reading=None, check_duplicates=false, notes=None, pos=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn py_update_word(
+    py: Python<'_>,
+    db_path: &str,
+    word_id: i64,
+    word: Option<String>,
+    meaning: Option<String>,
+    synonyms: Option<String>,
+    antonyms: Option<String>,
+    category: Option<String>,
+    reading: Option<String>,
+    check_duplicates: bool,
+    notes: Option<String>,
+    pos: Option<String>,
+) -> PyResult<bool> {
+    let update = WordUpdate { word, meaning, synonyms, antonyms, category, reading, notes, pos };
+    let update = validate_word(&update, false).map_err(|issues| validation_error(py, issues))?;
     
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS attempts (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            word_id INTEGER REFERENCES vocabulary(id),
-            mode TEXT NOT NULL,
-            question_type TEXT NOT NULL,
-            is_correct INTEGER NOT NULL,
-            user_answer TEXT,
-            expected_answer TEXT,
-            time_taken_ms INTEGER,
-            attempted_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
-        )",
-        [],
-    )?;
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    if check_duplicates && (update.word.is_some() || update.category.is_some()) {
+        let current = get_word_by_id(&conn, word_id)
+            .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+        if let Some(current) = current {
+            let word = update.word.clone().unwrap_or(current.word);
+            let category = update.category.clone().unwrap_or(current.category);
+            if find_duplicate(&conn, &word, &category, Some(word_id))
+                .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?
+                .is_some()
+            {
+                return Err(validation_error(py, vec![duplicate_issue(&word, &category)]));
+            }
+        }
+    }
     
-    Ok(conn)
+    update_word(&conn, word_id, &update)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
 }
 
-/// Load vocabulary from parsed Excel data with category
-pub fn load_vocabulary(conn: &Connection, words: Vec<Word>, category: &str) -> SqliteResult<usize> {
-    let mut count = 0;
-    for word in words {
-        conn.execute(
-            "INSERT INTO vocabulary (word, meaning, synonyms, antonyms, category) VALUES (?1, ?2, ?3, ?4, ?5)",
-            params![word.word, word.meaning, word.synonyms, word.antonyms, category],
-        )?;
-        count += 1;
+/// Trimmed tag name, rejecting empty ones
+fn tag_name(tag: &str) -> PyResult<&str> {
+    let tag = tag.trim();
+    if tag.is_empty() {
+        return Err(pyo3::exceptions::PyValueError::new_err("tag cannot be empty"));
     }
-    Ok(count)
+    Ok(tag)
 }
 
-/// Get words with ordering and optional category filter
-pub fn get_words(conn: &Connection, order: &str, letter: Option<char>, categories: Option<Vec<String>>) -> SqliteResult<Vec<Word>> {
-    let base_query = "SELECT id, word, meaning, synonyms, antonyms, COALESCE(category, 'Default') FROM vocabulary";
-    
-    let mut conditions = Vec::new();
-    
-    // Category filter
-    if let Some(ref cats) = categories {
-        if !cats.is_empty() {
-            let cat_list: Vec<String> = cats.iter().map(|c| format!("'{}'", c.replace("'", "''"))).collect();
-            conditions.push(format!("category IN ({})", cat_list.join(", ")));
-        }
+#[pyfunction]
+#[pyo3(name = "tag_words")]
+pub fn py_tag_words(db_path: &str, word_ids: Vec<i64>, tag: &str) -> PyResult<usize> {
+    let tag = tag_name(tag)?;
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    tag_words(&conn, &word_ids, tag)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "untag_words")]
+pub fn py_untag_words(db_path: &str, word_ids: Vec<i64>, tag: &str) -> PyResult<usize> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    untag_words(&conn, &word_ids, tag.trim())
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "delete_tag")]
+pub fn py_delete_tag(db_path: &str, tag: &str) -> PyResult<bool> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    delete_tag(&conn, tag.trim())
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "get_tags")]
+pub fn py_get_tags(db_path: &str) -> PyResult<Vec<TagInfo>> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    get_tags(&conn)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "get_word_tags")]
+pub fn py_get_word_tags(db_path: &str, word_id: i64) -> PyResult<Vec<String>> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    get_word_tags(&conn, word_id)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "add_example", signature = (db_path, word_id, sentence, source=None))]
+pub fn py_add_example(db_path: &str, word_id: i64, sentence: &str, source: Option<&str>) -> PyResult<i64> {
+    let sentence = sentence.trim();
+    if sentence.is_empty() {
+        return Err(pyo3::exceptions::PyValueError::new_err("Example sentence must not be empty"));
     }
-    
-    // Letter filter
-    if let Some(c) = letter {
-        conditions.push(format!("LOWER(word) LIKE '{}%'", c.to_lowercase()));
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    if get_word_by_id(&conn, word_id).map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?.is_none() {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!("Word not found: {}", word_id)));
     }
-    
-    let where_clause = if conditions.is_empty() {
-        String::new()
-    } else {
-        format!(" WHERE {}", conditions.join(" AND "))
+    add_example(&conn, word_id, sentence, source)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "get_examples")]
+pub fn py_get_examples(db_path: &str, word_id: i64) -> PyResult<Vec<Example>> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    get_examples(&conn, word_id)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "soft_delete", signature = (db_path, word_ids=None, category=None))]
+pub fn py_soft_delete(db_path: &str, word_ids: Option<Vec<i64>>, category: Option<&str>) -> PyResult<usize> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let result = match (word_ids, category) {
+        (Some(ids), None) => soft_delete_words(&conn, &ids),
+        (None, Some(category)) => soft_delete_category(&conn, category),
+        _ => return Err(pyo3::exceptions::PyValueError::new_err("pass either word_ids or category")),
     };
-    
-    let order_clause = match order.to_lowercase().as_str() {
-        "a_to_z" => " ORDER BY word ASC",
-        "z_to_a" => " ORDER BY word DESC",
-        "random" => " ORDER BY RANDOM()",
-        _ => " ORDER BY word ASC",
+    result.map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "restore", signature = (db_path, word_ids=None, category=None))]
+pub fn py_restore(db_path: &str, word_ids: Option<Vec<i64>>, category: Option<&str>) -> PyResult<usize> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let result = match (word_ids, category) {
+        (Some(ids), None) => restore_words(&conn, &ids),
+        (None, Some(category)) => restore_category(&conn, category),
+        _ => return Err(pyo3::exceptions::PyValueError::new_err("pass either word_ids or category")),
     };
-    
-    let query = format!("{}{}{}", base_query, where_clause, order_clause);
-    
-    let mut stmt = conn.prepare(&query)?;
-    let word_iter = stmt.query_map([], |row| {
-        Ok(Word {
-            id: row.get(0)?,
-            word: row.get(1)?,
-            meaning: row.get(2)?,
-            synonyms: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
-            antonyms: row.get::<_, Option<String>>(4)?.unwrap_or_default(),
-            category: row.get::<_, Option<String>>(5)?.unwrap_or_else(|| "Default".to_string()),
-        })
-    })?;
-    
-    let words: Vec<Word> = word_iter.filter_map(|w| w.ok()).collect();
-    Ok(words)
+    result.map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
 }
 
-/// Get single word by ID
-pub fn get_word_by_id(conn: &Connection, word_id: i64) -> SqliteResult<Option<Word>> {
-    let mut stmt = conn.prepare(
-        "SELECT id, word, meaning, synonyms, antonyms, COALESCE(category, 'Default') FROM vocabulary WHERE id = ?1"
-    )?;
-    
-    let result = stmt.query_row(params![word_id], |row| {
-        Ok(Word {
-            id: row.get(0)?,
-            word: row.get(1)?,
-            meaning: row.get(2)?,
-            synonyms: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
-            antonyms: row.get::<_, Option<String>>(4)?.unwrap_or_default(),
-            category: row.get::<_, Option<String>>(5)?.unwrap_or_else(|| "Default".to_string()),
-        })
-    });
-    
-    match result {
-        Ok(word) => Ok(Some(word)),
-        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-        Err(e) => Err(e),
-    }
+#[pyfunction]
+#[pyo3(name = "get_trash")]
+pub fn py_get_trash(db_path: &str) -> PyResult<Vec<TrashedWord>> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    get_trash(&conn)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
 }
 
-/// Get all words (for MCQ option generation)
-pub fn get_all_words(conn: &Connection) -> SqliteResult<Vec<Word>> {
-    get_words(conn, "random", None, None)
+#[pyfunction]
+#[pyo3(name = "purge_trash", signature = (db_path, older_than_days=None))]
+pub fn py_purge_trash(db_path: &str, older_than_days: Option<f64>) -> PyResult<usize> {
+    if older_than_days.is_some_and(|d| !(d.is_finite() && d >= 0.0)) {
+        return Err(pyo3::exceptions::PyValueError::new_err("older_than_days cannot be negative"));
+    }
+    let mut conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    purge_trash(&mut conn, older_than_days)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
 }
 
-/// Add a single word to the database
-pub fn add_word(conn: &Connection, word: &str, meaning: &str, synonyms: &str, antonyms: &str, category: &str) -> SqliteResult<i64> {
-    conn.execute(
-        "INSERT INTO vocabulary (word, meaning, synonyms, antonyms, category) VALUES (?1, ?2, ?3, ?4, ?5)",
-        params![word, meaning, synonyms, antonyms, category],
-    )?;
-    Ok(conn.last_insert_rowid())
+#[pyfunction]
+#[pyo3(name = "ignore_words")]
+pub fn py_ignore_words(db_path: &str, word_ids: Vec<i64>) -> PyResult<usize> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    ignore_words(&conn, &word_ids)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
 }
 
-/// Get all categories with word counts
-pub fn get_categories(conn: &Connection) -> SqliteResult<Vec<CategoryInfo>> {
-    let mut stmt = conn.prepare(
-        "SELECT COALESCE(category, 'Default') as cat, COUNT(*) FROM vocabulary GROUP BY cat ORDER BY cat"
-    )?;
-    
-    let cat_iter = stmt.query_map([], |row| {
-        Ok(CategoryInfo {
-            name: row.get(0)?,
-            word_count: row.get(1)?,
-        })
-    })?;
-    
-    let categories: Vec<CategoryInfo> = cat_iter.filter_map(|c| c.ok()).collect();
-    Ok(categories)
+#[pyfunction]
+#[pyo3(name = "unignore_words")]
+pub fn py_unignore_words(db_path: &str, word_ids: Vec<i64>) -> PyResult<usize> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    unignore_words(&conn, &word_ids)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
 }
 
-/// Delete a category and all its words
-pub fn delete_category(conn: &Connection, category: &str) -> SqliteResult<usize> {
-    // First delete orphan attempts
-    conn.execute(
-        "DELETE FROM attempts WHERE word_id IN (SELECT id FROM vocabulary WHERE category = ?1)",
-        params![category],
-    )?;
-    
-    // Then delete words
-    let deleted = conn.execute(
-        "DELETE FROM vocabulary WHERE category = ?1",
-        params![category],
-    )?;
-    
-    Ok(deleted)
+#[pyfunction]
+#[pyo3(name = "get_ignored_words")]
+pub fn py_get_ignored_words(db_path: &str) -> PyResult<Vec<Word>> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    get_ignored_words(&conn)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
 }
 
-// ============= Python Bindings =============
+#[pyfunction]
+#[pyo3(name = "pin_word")]
+pub fn py_pin_word(db_path: &str, word_id: i64) -> PyResult<bool> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    pin_word(&conn, word_id)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
 
 #[pyfunction]
-#[pyo3(name = "init_database")]
-pub fn py_init_database(db_path: &str) -> PyResult<()> {
-    init_database(db_path)
-        .map(|_| ())
+#[pyo3(name = "unpin_word")]
+pub fn py_unpin_word(db_path: &str, word_id: i64) -> PyResult<bool> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    unpin_word(&conn, word_id)
         .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
 }
 
 #[pyfunction]
-#[pyo3(name = "get_all_words")]
-pub fn py_get_all_words(db_path: &str) -> PyResult<Vec<Word>> {
-    let conn = Connection::open(db_path)
+#[pyo3(name = "star_word")]
+pub fn py_star_word(db_path: &str, word_id: i64) -> PyResult<bool> {
+    let conn = open_database(db_path)
         .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
-    get_all_words(&conn)
+    star_word(&conn, word_id)
         .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
 }
 
 #[pyfunction]
-#[pyo3(name = "get_words_by_order")]
-pub fn py_get_words_by_order(db_path: &str, order: &str, letter: Option<char>, categories: Option<Vec<String>>) -> PyResult<Vec<Word>> {
-    let conn = Connection::open(db_path)
+#[pyo3(name = "unstar_word")]
+pub fn py_unstar_word(db_path: &str, word_id: i64) -> PyResult<bool> {
+    let conn = open_database(db_path)
         .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
-    get_words(&conn, order, letter, categories)
+    unstar_word(&conn, word_id)
         .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
 }
 
 #[pyfunction]
-#[pyo3(name = "get_word_by_id")]
-pub fn py_get_word_by_id(db_path: &str, word_id: i64) -> PyResult<Option<Word>> {
-    let conn = Connection::open(db_path)
+#[pyo3(name = "get_pinned_words", signature = (db_path, categories=None))]
+pub fn py_get_pinned_words(db_path: &str, categories: Option<Vec<String>>) -> PyResult<Vec<Word>> {
+    let conn = open_database(db_path)
         .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
-    get_word_by_id(&conn, word_id)
+    get_pinned_words(&conn, categories.as_deref())
         .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
 }
 
 #[pyfunction]
-#[pyo3(name = "add_word")]
-pub fn py_add_word(db_path: &str, word: &str, meaning: &str, synonyms: &str, antonyms: &str, category: &str) -> PyResult<i64> {
-    let conn = Connection::open(db_path)
+#[pyo3(name = "delete_word")]
+pub fn py_delete_word(db_path: &str, word_id: i64) -> PyResult<bool> {
+    let mut conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let tx = conn.transaction()
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let deleted = delete_word(&tx, word_id)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    tx.commit()
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    Ok(deleted)
+}
+
+#[pyfunction]
+#[pyo3(name = "delete_words")]
+pub fn py_delete_words(db_path: &str, ids: Vec<i64>) -> PyResult<usize> {
+    let mut conn = open_database(db_path)
         .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
-    add_word(&conn, word, meaning, synonyms, antonyms, category)
+    delete_words(&mut conn, &ids)
         .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
 }
 
 #[pyfunction]
 #[pyo3(name = "get_categories")]
 pub fn py_get_categories(db_path: &str) -> PyResult<Vec<CategoryInfo>> {
-    let conn = Connection::open(db_path)
+    let conn = open_database(db_path)
         .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
     get_categories(&conn)
         .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
@@ -272,8 +2842,97 @@ pub fn py_get_categories(db_path: &str) -> PyResult<Vec<CategoryInfo>> {
 #[pyfunction]
 #[pyo3(name = "delete_category")]
 pub fn py_delete_category(db_path: &str, category: &str) -> PyResult<usize> {
-    let conn = Connection::open(db_path)
+    let mut conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    delete_category(&mut conn, category)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "rename_category")]
+pub fn py_rename_category(db_path: &str, old: &str, new: &str) -> PyResult<usize> {
+    let mut conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    rename_category(&mut conn, old, new)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "merge_categories", signature = (db_path, sources, target, dedupe=true))]
+pub fn py_merge_categories(db_path: &str, sources: Vec<String>, target: String, dedupe: bool) -> PyResult<MergeResult> {
+    let target = target.trim();
+    if target.is_empty() {
+        return Err(pyo3::exceptions::PyValueError::new_err("target category cannot be empty"));
+    }
+    let mut conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    merge_categories(&mut conn, &sources, target, dedupe)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "move_words_to_category")]
+pub fn py_move_words_to_category(db_path: &str, word_ids: Vec<i64>, category: &str) -> PyResult<usize> {
+    let category = category.trim();
+    if category.is_empty() {
+        return Err(pyo3::exceptions::PyValueError::new_err("category cannot be empty"));
+    }
+    let mut conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    move_words_to_category(&mut conn, &word_ids, category)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "get_default_category")]
+pub fn py_get_default_category(db_path: &str) -> PyResult<String> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    Ok(get_default_category(&conn))
+}
+
+#[pyfunction]
+#[pyo3(name = "set_default_category", signature = (db_path, name, rename_existing=true))]
+pub fn py_set_default_category(db_path: &str, name: &str, rename_existing: bool) -> PyResult<usize> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(pyo3::exceptions::PyValueError::new_err("category cannot be empty"));
+    }
+    let mut conn = init_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    set_default_category(&mut conn, name, rename_existing)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "get_extra")]
+pub fn py_get_extra(py: Python<'_>, db_path: &str, word_id: i64, key: &str) -> PyResult<PyObject> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let value = get_extra(&conn, word_id, key)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    match value {
+        Some(v) => json_to_py(py, &v),
+        None => Ok(py.None()),
+    }
+}
+
+#[pyfunction]
+#[pyo3(name = "set_extra")]
+pub fn py_set_extra(db_path: &str, word_id: i64, key: &str, value: &Bound<'_, PyAny>) -> PyResult<bool> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let value = if value.is_none() { None } else { Some(py_to_json(value)?) };
+    set_extra(&conn, word_id, key, value)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "get_words_by_field", signature = (db_path, key, value=None, categories=None))]
+pub fn py_get_words_by_field(db_path: &str, key: &str, value: Option<&Bound<'_, PyAny>>, categories: Option<Vec<String>>) -> PyResult<Vec<Word>> {
+    let value = value.map(py_to_json).transpose()?;
+    let conn = open_database(db_path)
         .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
-    delete_category(&conn, category)
+    find_words_by_field(&conn, key, value.as_ref(), categories.as_deref())
         .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
 }
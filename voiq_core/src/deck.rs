@@ -0,0 +1,302 @@
+//! Deck bundles: a category's words, authored questions and attribution in one JSON file
+
+use pyo3::prelude::*;
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::content_filter::ContentFilter;
+use crate::db::{open_database, get_words, init_database, load_vocabulary_for_import, unique_index_mode, LoadMode, Word, DIFFICULTY_LEVELS};
+use crate::question_bank::{insert_question_records, question_records, QuestionRecord};
+
+/// Identifies a deck bundle file
+pub const DECK_FORMAT: &str = "voiq-deck";
+pub const DECK_FORMAT_VERSION: u32 = 1;
+
+/// Attribution and licensing of a deck (category)
+#[pyclass]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeckMetadata {
+    #[pyo3(get)]
+    #[serde(skip)]
+    pub category: String,
+    #[pyo3(get)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[pyo3(get)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    #[pyo3(get)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_url: Option<String>,
+    /// Deck version as chosen by the author (e.g. "1.2")
+    #[pyo3(get)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+#[pymethods]
+impl DeckMetadata {
+    fn __repr__(&self) -> String {
+        let show = |f: &Option<String>| f.as_ref().map_or("None".to_string(), |v| format!("'{}'", v));
+        format!("DeckMetadata(category='{}', author={}, license={}, version={})",
+                self.category, show(&self.author), show(&self.license), show(&self.version))
+    }
+}
+
+/// A word as stored in a deck bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeckWord {
+    pub word: String,
+    #[serde(default)]
+    pub meaning: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub synonyms: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub antonyms: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub reading: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub notes: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub pos: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frequency_rank: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extra: Option<Value>,
+}
+
+/// Deck bundle file contents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeckBundle {
+    pub format: String,
+    pub format_version: u32,
+    pub category: String,
+    #[serde(default)]
+    pub metadata: DeckMetadata,
+    pub words: Vec<DeckWord>,
+    #[serde(default)]
+    pub questions: Vec<QuestionRecord>,
+}
+
+/// Read a category's metadata
+pub fn read_deck_metadata(conn: &Connection, category: &str) -> rusqlite::Result<Option<DeckMetadata>> {
+    conn.query_row(
+        "SELECT category, author, license, source_url, version FROM category_meta WHERE category = ?1",
+        params![category],
+        |row| Ok(DeckMetadata {
+            category: row.get(0)?,
+            author: row.get(1)?,
+            license: row.get(2)?,
+            source_url: row.get(3)?,
+            version: row.get(4)?,
+        }),
+    ).optional()
+}
+
+/// Write metadata fields for a category. None leaves a field unchanged, an empty string clears it.
+pub fn write_deck_metadata(
+    conn: &Connection,
+    category: &str,
+    author: Option<&str>,
+    license: Option<&str>,
+    source_url: Option<&str>,
+    version: Option<&str>,
+) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO category_meta (category, author, license, source_url, version)
+         VALUES (?1, NULLIF(?2, ''), NULLIF(?3, ''), NULLIF(?4, ''), NULLIF(?5, ''))
+         ON CONFLICT(category) DO UPDATE SET
+            author = CASE WHEN ?2 IS NULL THEN author ELSE NULLIF(?2, '') END,
+            license = CASE WHEN ?3 IS NULL THEN license ELSE NULLIF(?3, '') END,
+            source_url = CASE WHEN ?4 IS NULL THEN source_url ELSE NULLIF(?4, '') END,
+            version = CASE WHEN ?5 IS NULL THEN version ELSE NULLIF(?5, '') END,
+            updated_at = CURRENT_TIMESTAMP",
+        params![category, author, license, source_url, version],
+    )?;
+    Ok(())
+}
+
+/// Set attribution fields of a deck (see `write_deck_metadata`)
+pub fn set_deck_metadata(
+    db_path: &str,
+    category: &str,
+    author: Option<&str>,
+    license: Option<&str>,
+    source_url: Option<&str>,
+    version: Option<&str>,
+) -> Result<DeckMetadata, String> {
+    let conn = init_database(db_path)
+        .map_err(|e| format!("Failed to init database: {}", e))?;
+    write_deck_metadata(&conn, category, author, license, source_url, version)
+        .map_err(|e| format!("Failed to save deck metadata: {}", e))?;
+    read_deck_metadata(&conn, category)
+        .map_err(|e| format!("Failed to read deck metadata: {}", e))
+        .map(|m| m.unwrap_or_default())
+}
+
+/// Attribution fields of a deck (None if none were set)
+pub fn get_deck_metadata(db_path: &str, category: &str) -> Result<Option<DeckMetadata>, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    read_deck_metadata(&conn, category)
+        .map_err(|e| format!("Failed to read deck metadata: {}", e))
+}
+
+/// Build the bundle for a category
+pub fn build_deck_bundle(conn: &Connection, category: &str) -> Result<DeckBundle, String> {
+    let categories = vec![category.to_string()];
+    let words = get_words(conn, "a_to_z", None, Some(categories.clone()), None, None, None, false)
+        .map_err(|e| format!("Failed to get words: {}", e))?;
+    let metadata = read_deck_metadata(conn, category)
+        .map_err(|e| format!("Failed to read deck metadata: {}", e))?
+        .unwrap_or_default();
+    
+    Ok(DeckBundle {
+        format: DECK_FORMAT.to_string(),
+        format_version: DECK_FORMAT_VERSION,
+        category: category.to_string(),
+        metadata,
+        words: words.into_iter().map(|w| DeckWord {
+            extra: serde_json::from_str(&w.extra).ok(),
+            word: w.word,
+            meaning: w.meaning,
+            synonyms: w.synonyms,
+            antonyms: w.antonyms,
+            reading: w.reading,
+            notes: w.notes,
+            pos: w.pos,
+            difficulty: w.difficulty,
+            frequency_rank: w.frequency_rank,
+        }).collect(),
+        questions: question_records(conn, Some(&categories))?,
+    })
+}
+
+/// Export a category with its authored questions and metadata to a deck bundle file
+pub fn export_deck(db_path: &str, category: &str, out_path: &str) -> Result<usize, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    
+    let bundle = build_deck_bundle(&conn, category)?;
+    if bundle.words.is_empty() && bundle.questions.is_empty() {
+        return Err(format!("Category '{}' is empty", category));
+    }
+    
+    let json = serde_json::to_string_pretty(&bundle)
+        .map_err(|e| format!("Failed to encode deck: {}", e))?;
+    std::fs::write(out_path, json)
+        .map_err(|e| format!("Failed to write file: {}", e))?;
+    
+    Ok(bundle.words.len())
+}
+
+/// Parse and check a deck bundle file
+pub fn read_deck_bundle(file_path: &str) -> Result<DeckBundle, String> {
+    let text = std::fs::read_to_string(file_path)
+        .map_err(|e| format!("Failed to read deck file: {}", e))?;
+    let bundle: DeckBundle = serde_json::from_str(&text)
+        .map_err(|e| format!("Invalid deck file: {}", e))?;
+    
+    if bundle.format != DECK_FORMAT {
+        return Err(format!("Not a VoIQ deck file (format '{}')", bundle.format));
+    }
+    if bundle.format_version > DECK_FORMAT_VERSION {
+        return Err(format!("Deck format version {} is newer than supported ({})",
+                           bundle.format_version, DECK_FORMAT_VERSION));
+    }
+    Ok(bundle)
+}
+
+/// Import a deck bundle into `category` (default: the bundle's own category), including
+/// its questions and metadata. Returns the number of words added.
+pub fn import_deck(file_path: &str, db_path: &str, category: Option<&str>) -> Result<usize, String> {
+    let bundle = read_deck_bundle(file_path)?;
+    let category = category.unwrap_or(&bundle.category);
+    
+    let mut conn = init_database(db_path)
+        .map_err(|e| format!("Failed to init database: {}", e))?;
+    let tx = conn.transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    
+    let filter = ContentFilter::load(&tx);
+    let words: Vec<Word> = bundle.words.into_iter()
+        .filter(|w| !w.word.trim().is_empty())
+        .map(|w| Word {
+            word: w.word,
+            meaning: w.meaning,
+            synonyms: w.synonyms,
+            antonyms: w.antonyms,
+            reading: w.reading,
+            notes: w.notes,
+            pos: w.pos,
+            difficulty: w.difficulty.filter(|d| DIFFICULTY_LEVELS.contains(d)),
+            frequency_rank: w.frequency_rank.filter(|r| *r > 0),
+            extra: w.extra.filter(|e| e.is_object()).map(|e| e.to_string()).unwrap_or_default(),
+            ..Default::default()
+        })
+        .filter(|w| !filter.excludes_word(w))
+        .collect();
+    // While words are unique, words the category already has are updated instead of clashing
+    let mode = match unique_index_mode(&tx).map_err(|e| format!("Failed to read indexes: {}", e))? {
+        Some(_) => LoadMode::Upsert,
+        None => LoadMode::Append,
+    };
+    let count = load_vocabulary_for_import(&tx, words, category, None, mode)
+        .map_err(|e| format!("Failed to load vocabulary: {}", e))?;
+    
+    // Questions follow the deck into the target category
+    let questions: Vec<QuestionRecord> = bundle.questions.into_iter()
+        .map(|q| QuestionRecord { category: None, ..q })
+        .collect();
+    insert_question_records(&tx, &questions, category)?;
+    
+    let meta = &bundle.metadata;
+    let field = |f: &Option<String>| Some(f.clone().unwrap_or_default());
+    write_deck_metadata(
+        &tx, category,
+        field(&meta.author).as_deref(), field(&meta.license).as_deref(),
+        field(&meta.source_url).as_deref(), field(&meta.version).as_deref(),
+    ).map_err(|e| format!("Failed to save deck metadata: {}", e))?;
+    
+    tx.commit().map_err(|e| format!("Failed to commit deck import: {}", e))?;
+    Ok(count)
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "set_deck_metadata", signature = (db_path, category, author=None, license=None, source_url=None, version=None))]
+pub fn py_set_deck_metadata(
+    db_path: &str,
+    category: &str,
+    author: Option<&str>,
+    license: Option<&str>,
+    source_url: Option<&str>,
+    version: Option<&str>,
+) -> PyResult<DeckMetadata> {
+    set_deck_metadata(db_path, category, author, license, source_url, version)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "get_deck_metadata")]
+pub fn py_get_deck_metadata(db_path: &str, category: &str) -> PyResult<Option<DeckMetadata>> {
+    get_deck_metadata(db_path, category)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "export_deck")]
+pub fn py_export_deck(db_path: &str, category: &str, out_path: &str) -> PyResult<usize> {
+    export_deck(db_path, category, out_path)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "import_deck", signature = (file_path, db_path, category=None))]
+pub fn py_import_deck(file_path: &str, db_path: &str, category: Option<&str>) -> PyResult<usize> {
+    import_deck(file_path, db_path, category)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
@@ -0,0 +1,338 @@
+//! Duplicate word detection, merging and category comparison
+
+use pyo3::prelude::*;
+use rusqlite::{Connection, params};
+use std::collections::{BTreeMap, HashMap};
+use strsim::{jaro_winkler, normalized_levenshtein};
+use crate::collation::unicode_compare;
+use crate::db::{open_database, category_exists, fold_word_into, get_all_words, get_word_by_id, get_words, get_words_by_ids, Word};
+
+/// Words that look like copies of each other
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+    /// Normalized text of the group's oldest word
+    #[pyo3(get)]
+    pub key: String,
+    /// Category the group was found in (None when searching across categories)
+    #[pyo3(get)]
+    pub category: Option<String>,
+    /// The duplicate words, oldest first
+    #[pyo3(get)]
+    pub words: Vec<Word>,
+    /// Weakest similarity that linked two words of the group (1.0 for identical texts)
+    #[pyo3(get)]
+    pub similarity: f64,
+}
+
+#[pymethods]
+impl DuplicateGroup {
+    /// Ids of the words in the group, oldest first
+    #[getter]
+    pub fn word_ids(&self) -> Vec<i64> {
+        self.words.iter().map(|w| w.id).collect()
+    }
+    
+    fn __repr__(&self) -> String {
+        format!("DuplicateGroup(key='{}', words={}, similarity={:.2})", self.key, self.words.len(), self.similarity)
+    }
+}
+
+/// Where duplicates are looked for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DuplicateScope {
+    /// Only words of the same category are compared
+    Category,
+    /// Words are compared across all categories
+    All,
+}
+
+impl DuplicateScope {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "" | "category" => Ok(DuplicateScope::Category),
+            "all" => Ok(DuplicateScope::All),
+            _ => Err(format!("Unknown duplicate scope: {}", name)),
+        }
+    }
+}
+
+/// Word text compared for duplicates: case-folded, trimmed, inner whitespace collapsed
+fn normalize_text(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+fn find_root(parents: &mut [usize], mut i: usize) -> usize {
+    while parents[i] != i {
+        parents[i] = parents[parents[i]];
+        i = parents[i];
+    }
+    i
+}
+
+/// Group words with identical normalized text, or with `threshold` also texts whose
+/// Jaro-Winkler similarity reaches it. Only groups of two or more words are returned.
+pub fn group_duplicates(words: Vec<Word>, scope: DuplicateScope, threshold: Option<f64>) -> Vec<DuplicateGroup> {
+    let mut buckets: BTreeMap<Option<String>, Vec<Word>> = BTreeMap::new();
+    for word in words {
+        let bucket = match scope {
+            DuplicateScope::Category => Some(word.category.clone()),
+            DuplicateScope::All => None,
+        };
+        buckets.entry(bucket).or_default().push(word);
+    }
+    
+    let mut groups = Vec::new();
+    for (category, mut words) in buckets {
+        words.sort_by_key(|w| w.id);
+        
+        // Identical texts first; fuzzy matching then only compares distinct texts
+        let mut keys: Vec<String> = Vec::new();
+        let mut members: Vec<Vec<Word>> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        for word in words {
+            let key = normalize_text(&word.word);
+            if key.is_empty() {
+                continue;
+            }
+            let i = *index.entry(key.clone()).or_insert_with(|| {
+                keys.push(key);
+                members.push(Vec::new());
+                keys.len() - 1
+            });
+            members[i].push(word);
+        }
+        
+        let mut parents: Vec<usize> = (0..keys.len()).collect();
+        let mut weakest: Vec<f64> = vec![1.0; keys.len()];
+        if let Some(threshold) = threshold {
+            for a in 0..keys.len() {
+                for b in (a + 1)..keys.len() {
+                    let similarity = jaro_winkler(&keys[a], &keys[b]);
+                    if similarity < threshold {
+                        continue;
+                    }
+                    let (ra, rb) = (find_root(&mut parents, a), find_root(&mut parents, b));
+                    let linked = weakest[ra].min(weakest[rb]).min(similarity);
+                    let root = ra.min(rb);
+                    parents[ra.max(rb)] = root;
+                    weakest[root] = linked;
+                }
+            }
+        }
+        
+        let mut merged: BTreeMap<usize, Vec<Word>> = BTreeMap::new();
+        for (i, words) in members.into_iter().enumerate() {
+            let root = find_root(&mut parents, i);
+            merged.entry(root).or_default().extend(words);
+        }
+        
+        for (root, mut words) in merged {
+            if words.len() < 2 {
+                continue;
+            }
+            words.sort_by_key(|w| w.id);
+            groups.push(DuplicateGroup {
+                key: normalize_text(&words[0].word),
+                category: category.clone(),
+                words,
+                similarity: weakest[root],
+            });
+        }
+    }
+    
+    groups.sort_by(|a, b| a.key.cmp(&b.key).then_with(|| a.category.cmp(&b.category)));
+    groups
+}
+
+/// Find groups of duplicate words (see `group_duplicates`)
+pub fn find_duplicates(db_path: &str, scope: DuplicateScope, threshold: Option<f64>) -> Result<Vec<DuplicateGroup>, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let words = get_all_words(&conn)
+        .map_err(|e| format!("Failed to get all words: {}", e))?;
+    Ok(group_duplicates(words, scope, threshold))
+}
+
+/// Union of comma-separated lists, keeping the first spelling of each entry
+fn union_lists<'a>(lists: impl Iterator<Item = &'a str>, exclude: &str) -> String {
+    let exclude = normalize_text(exclude);
+    let mut seen = Vec::new();
+    let mut items = Vec::new();
+    for item in lists.flat_map(|l| l.split(',')).map(str::trim).filter(|s| !s.is_empty()) {
+        let key = normalize_text(item);
+        if key != exclude && !seen.contains(&key) {
+            seen.push(key);
+            items.push(item.to_string());
+        }
+    }
+    items.join(", ")
+}
+
+/// Merge duplicate words into one: `keep_id` (default: the oldest word) gets the union of
+/// all synonyms and antonyms, and the others' attempts, questions and examples are
+/// moved over before they are deleted. Returns the merged word.
+pub fn merge_duplicates(conn: &mut Connection, word_ids: &[i64], keep_id: Option<i64>) -> Result<Word, String> {
+    let tx = conn.transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    
+    let words: Vec<Word> = get_words_by_ids(&tx, word_ids)
+        .map_err(|e| format!("Failed to get words: {}", e))?
+        .into_iter()
+        .flatten()
+        .collect();
+    let keep_id = keep_id.or_else(|| words.iter().map(|w| w.id).min())
+        .ok_or("No words to merge")?;
+    let keep = words.iter().find(|w| w.id == keep_id)
+        .ok_or_else(|| format!("Word {} is not among the words to merge", keep_id))?;
+    
+    // The kept word's own entries come first so their order is preserved
+    let ordered: Vec<&Word> = std::iter::once(keep)
+        .chain(words.iter().filter(|w| w.id != keep_id))
+        .collect();
+    let synonyms = union_lists(ordered.iter().map(|w| w.synonyms.as_str()), &keep.word);
+    let antonyms = union_lists(ordered.iter().map(|w| w.antonyms.as_str()), &keep.word);
+    tx.execute(
+        "UPDATE vocabulary SET synonyms = ?2, antonyms = ?3 WHERE id = ?1",
+        params![keep_id, synonyms, antonyms],
+    ).map_err(|e| format!("Failed to update word: {}", e))?;
+    
+    for word in ordered.iter().skip(1) {
+        fold_word_into(&tx, word.id, keep_id)
+            .map_err(|e| format!("Failed to merge word {}: {}", word.id, e))?;
+    }
+    
+    let merged = get_word_by_id(&tx, keep_id)
+        .map_err(|e| format!("Failed to get word: {}", e))?
+        .ok_or("Word not found")?;
+    tx.commit().map_err(|e| format!("Failed to commit merge: {}", e))?;
+    Ok(merged)
+}
+
+/// A word present in both compared categories
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct SharedWord {
+    #[pyo3(get)]
+    pub word_a: Word,
+    #[pyo3(get)]
+    pub word_b: Word,
+    /// Similarity of the two meanings (1.0 when they match after normalization)
+    #[pyo3(get)]
+    pub meaning_similarity: f64,
+}
+
+#[pymethods]
+impl SharedWord {
+    /// True if the two entries give different meanings
+    #[getter]
+    pub fn meaning_differs(&self) -> bool {
+        self.meaning_similarity < 1.0
+    }
+    
+    fn __repr__(&self) -> String {
+        format!("SharedWord(word='{}', meaning_similarity={:.2})", self.word_a.word, self.meaning_similarity)
+    }
+}
+
+/// Result of comparing the words of two categories
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct CategoryDiff {
+    #[pyo3(get)]
+    pub only_a: Vec<Word>,
+    #[pyo3(get)]
+    pub only_b: Vec<Word>,
+    /// Words in both categories, least similar meanings first
+    #[pyo3(get)]
+    pub both: Vec<SharedWord>,
+}
+
+#[pymethods]
+impl CategoryDiff {
+    fn __repr__(&self) -> String {
+        format!("CategoryDiff(only_a={}, only_b={}, both={})", self.only_a.len(), self.only_b.len(), self.both.len())
+    }
+}
+
+/// Compare two word lists by normalized word text. A text repeated within one list
+/// is paired once; its extra copies count as unmatched.
+pub fn diff_words(words_a: Vec<Word>, words_b: Vec<Word>) -> CategoryDiff {
+    let mut unmatched_b: HashMap<String, Vec<Word>> = HashMap::new();
+    for word in words_b.into_iter().rev() {
+        unmatched_b.entry(normalize_text(&word.word)).or_default().push(word);
+    }
+    
+    let mut only_a = Vec::new();
+    let mut both = Vec::new();
+    for word_a in words_a {
+        match unmatched_b.get_mut(&normalize_text(&word_a.word)).and_then(Vec::pop) {
+            Some(word_b) => {
+                let meaning_similarity = normalized_levenshtein(
+                    &normalize_text(&word_a.meaning), &normalize_text(&word_b.meaning),
+                );
+                both.push(SharedWord { word_a, word_b, meaning_similarity });
+            }
+            None => only_a.push(word_a),
+        }
+    }
+    
+    let mut only_b: Vec<Word> = unmatched_b.into_values().flatten().collect();
+    only_b.sort_by(|a, b| unicode_compare(&a.word, &b.word).then(a.id.cmp(&b.id)));
+    both.sort_by(|a, b| a.meaning_similarity.total_cmp(&b.meaning_similarity)
+        .then_with(|| unicode_compare(&a.word_a.word, &b.word_a.word)));
+    
+    CategoryDiff { only_a, only_b, both }
+}
+
+/// Compare the words of two categories (see `diff_words`)
+pub fn diff_categories(db_path: &str, cat_a: &str, cat_b: &str) -> Result<CategoryDiff, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    
+    let mut lists = Vec::with_capacity(2);
+    for category in [cat_a, cat_b] {
+        let exists = category_exists(&conn, category)
+            .map_err(|e| format!("Failed to check category: {}", e))?;
+        if !exists {
+            return Err(format!("Category not found: {}", category));
+        }
+        let words = get_words(&conn, "a_to_z", None, Some(vec![category.to_string()]), None, None, None, false)
+            .map_err(|e| format!("Failed to get words: {}", e))?;
+        lists.push(words);
+    }
+    
+    let words_b = lists.pop().unwrap_or_default();
+    let words_a = lists.pop().unwrap_or_default();
+    Ok(diff_words(words_a, words_b))
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "find_duplicates", signature = (db_path, scope="category", threshold=None))]
+pub fn py_find_duplicates(db_path: &str, scope: &str, threshold: Option<f64>) -> PyResult<Vec<DuplicateGroup>> {
+    let scope = DuplicateScope::parse(scope)
+        .map_err(pyo3::exceptions::PyValueError::new_err)?;
+    if threshold.is_some_and(|t| !(0.0..=1.0).contains(&t)) {
+        return Err(pyo3::exceptions::PyValueError::new_err("threshold must be between 0.0 and 1.0"));
+    }
+    find_duplicates(db_path, scope, threshold)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "merge_duplicates", signature = (db_path, word_ids, keep_id=None))]
+pub fn py_merge_duplicates(db_path: &str, word_ids: Vec<i64>, keep_id: Option<i64>) -> PyResult<Word> {
+    let mut conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+    merge_duplicates(&mut conn, &word_ids, keep_id)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "diff_categories")]
+pub fn py_diff_categories(db_path: &str, cat_a: &str, cat_b: &str) -> PyResult<CategoryDiff> {
+    diff_categories(db_path, cat_a, cat_b)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
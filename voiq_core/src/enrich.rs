@@ -0,0 +1,406 @@
+//! Dictionary enrichment: fill in missing word data from an external source.
+//!
+//! The lookup itself (usually an HTTP call) is a Python callable; this module rate-limits
+//! the calls, retries failures with exponential backoff, and keeps per-word job state in
+//! the database so a long run can be paused and resumed.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rusqlite::{Connection, OptionalExtension, params};
+use serde_json::{Map, Value};
+use std::time::{Duration, Instant};
+use crate::db::{open_database, category_or_default, get_word_by_id, init_database, py_to_json, set_extra, Word};
+
+/// Job and item states
+pub const STATUS_PENDING: &str = "pending";
+pub const STATUS_RUNNING: &str = "running";
+pub const STATUS_PAUSED: &str = "paused";
+pub const STATUS_DONE: &str = "done";
+pub const STATUS_FAILED: &str = "failed";
+pub const STATUS_CANCELLED: &str = "cancelled";
+
+/// Rate limiting and retry settings for an enrichment run
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct EnrichmentPolicy {
+    /// Maximum lookups per second (0 disables rate limiting)
+    #[pyo3(get, set)]
+    pub requests_per_second: f64,
+    /// Retries after the first failed lookup of a word before it is marked failed
+    #[pyo3(get, set)]
+    pub max_retries: u32,
+    /// Wait before the first retry; doubles on every further retry
+    #[pyo3(get, set)]
+    pub base_backoff_ms: u64,
+    #[pyo3(get, set)]
+    pub max_backoff_ms: u64,
+}
+
+impl Default for EnrichmentPolicy {
+    fn default() -> Self {
+        EnrichmentPolicy {
+            requests_per_second: 2.0,
+            max_retries: 5,
+            base_backoff_ms: 500,
+            max_backoff_ms: 30_000,
+        }
+    }
+}
+
+impl EnrichmentPolicy {
+    /// Minimum time between two lookups
+    pub fn min_interval(&self) -> Duration {
+        if self.requests_per_second > 0.0 {
+            Duration::from_secs_f64(1.0 / self.requests_per_second)
+        } else {
+            Duration::ZERO
+        }
+    }
+    
+    /// Wait before retry number `retry` (1-based)
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u64.saturating_pow(retry.saturating_sub(1));
+        Duration::from_millis(self.base_backoff_ms.saturating_mul(factor).min(self.max_backoff_ms))
+    }
+}
+
+#[pymethods]
+impl EnrichmentPolicy {
+    #[new]
+    #[pyo3(signature = (requests_per_second=2.0, max_retries=5, base_backoff_ms=500, max_backoff_ms=30000))]
+    fn new(requests_per_second: f64, max_retries: u32, base_backoff_ms: u64, max_backoff_ms: u64) -> Self {
+        EnrichmentPolicy { requests_per_second, max_retries, base_backoff_ms, max_backoff_ms }
+    }
+    
+    fn __repr__(&self) -> String {
+        format!("EnrichmentPolicy(requests_per_second={}, max_retries={})",
+                self.requests_per_second, self.max_retries)
+    }
+}
+
+/// State of an enrichment job
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct EnrichmentProgress {
+    #[pyo3(get)]
+    pub job_id: i64,
+    #[pyo3(get)]
+    pub status: String,
+    #[pyo3(get)]
+    pub total: i64,
+    #[pyo3(get)]
+    pub done: i64,
+    #[pyo3(get)]
+    pub failed: i64,
+    #[pyo3(get)]
+    pub pending: i64,
+}
+
+#[pymethods]
+impl EnrichmentProgress {
+    fn __repr__(&self) -> String {
+        format!("EnrichmentProgress(job_id={}, status='{}', done={}/{}, failed={})",
+                self.job_id, self.status, self.done, self.total, self.failed)
+    }
+}
+
+/// Create a job covering every word (optionally of one category); returns the job id
+pub fn create_enrichment_job(db_path: &str, category: Option<&str>) -> Result<i64, String> {
+    let mut conn = init_database(db_path)
+        .map_err(|e| format!("Failed to init database: {}", e))?;
+    let tx = conn.transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    
+    tx.execute("INSERT INTO enrichment_jobs (category) VALUES (?1)", params![category])
+        .map_err(|e| format!("Failed to create job: {}", e))?;
+    let job_id = tx.last_insert_rowid();
+    
+    tx.execute(
+        concat!(
+            "INSERT INTO enrichment_items (job_id, word_id)
+             SELECT ?1, id FROM vocabulary WHERE deleted_at IS NULL AND (?2 IS NULL OR ", category_or_default!("category"), " = ?2)"
+        ),
+        params![job_id, category],
+    ).map_err(|e| format!("Failed to queue words: {}", e))?;
+    
+    tx.commit().map_err(|e| format!("Failed to commit job: {}", e))?;
+    Ok(job_id)
+}
+
+/// Read the state of a job (None if it doesn't exist)
+pub fn read_progress(conn: &Connection, job_id: i64) -> rusqlite::Result<Option<EnrichmentProgress>> {
+    let status: Option<String> = conn.query_row(
+        "SELECT status FROM enrichment_jobs WHERE id = ?1", params![job_id], |row| row.get(0),
+    ).optional()?;
+    let Some(status) = status else {
+        return Ok(None);
+    };
+    
+    conn.query_row(
+        "SELECT COUNT(*),
+                COALESCE(SUM(status = 'done'), 0),
+                COALESCE(SUM(status = 'failed'), 0),
+                COALESCE(SUM(status = 'pending'), 0)
+         FROM enrichment_items WHERE job_id = ?1",
+        params![job_id],
+        |row| Ok(Some(EnrichmentProgress {
+            job_id,
+            status,
+            total: row.get(0)?,
+            done: row.get(1)?,
+            failed: row.get(2)?,
+            pending: row.get(3)?,
+        })),
+    )
+}
+
+/// Progress of a job
+pub fn get_enrichment_progress(db_path: &str, job_id: i64) -> Result<EnrichmentProgress, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    read_progress(&conn, job_id)
+        .map_err(|e| format!("Failed to read job: {}", e))?
+        .ok_or_else(|| format!("Enrichment job {} not found", job_id))
+}
+
+/// Cancel a job; later runs leave it untouched. Returns false if it doesn't exist.
+pub fn cancel_enrichment_job(db_path: &str, job_id: i64) -> Result<bool, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    conn.execute(
+        "UPDATE enrichment_jobs SET status = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+        params![STATUS_CANCELLED, job_id],
+    )
+        .map(|n| n > 0)
+        .map_err(|e| format!("Failed to cancel job: {}", e))
+}
+
+/// Set a job's status; a cancelled job stays cancelled
+fn set_job_status(conn: &Connection, job_id: i64, status: &str) -> rusqlite::Result<usize> {
+    conn.execute(
+        "UPDATE enrichment_jobs SET status = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2 AND status != ?3",
+        params![status, job_id, STATUS_CANCELLED],
+    )
+}
+
+/// Whether a job was cancelled (possibly by another connection while it runs)
+fn is_cancelled(conn: &Connection, job_id: i64) -> rusqlite::Result<bool> {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM enrichment_jobs WHERE id = ?1 AND status = ?2)",
+        params![job_id, STATUS_CANCELLED],
+        |row| row.get(0),
+    )
+}
+
+fn set_item_status(conn: &Connection, job_id: i64, word_id: i64, status: &str, error: Option<&str>) -> rusqlite::Result<usize> {
+    conn.execute(
+        "UPDATE enrichment_items SET status = ?1, attempts = attempts + 1, last_error = ?2,
+                updated_at = CURRENT_TIMESTAMP
+         WHERE job_id = ?3 AND word_id = ?4",
+        params![status, error, job_id, word_id],
+    )
+}
+
+/// Fill a word's empty fields from looked-up data. `meaning`, `synonyms`, `antonyms` and
+/// `reading` fill the columns of that name; other keys become extra fields. Existing data
+/// is never overwritten.
+pub fn apply_enrichment(conn: &Connection, word: &Word, data: &Map<String, Value>) -> rusqlite::Result<()> {
+    let text = |key: &str, current: &str| -> Option<String> {
+        if !current.trim().is_empty() {
+            return None;
+        }
+        data.get(key).and_then(|v| v.as_str()).map(str::trim).filter(|s| !s.is_empty()).map(String::from)
+    };
+    
+    conn.execute(
+        "UPDATE vocabulary SET
+            meaning = COALESCE(?1, meaning),
+            synonyms = COALESCE(?2, synonyms),
+            antonyms = COALESCE(?3, antonyms),
+            reading = COALESCE(?4, reading)
+         WHERE id = ?5",
+        params![
+            text("meaning", &word.meaning),
+            text("synonyms", &word.synonyms),
+            text("antonyms", &word.antonyms),
+            text("reading", &word.reading),
+            word.id,
+        ],
+    )?;
+    
+    let existing: Map<String, Value> = serde_json::from_str(&word.extra).unwrap_or_default();
+    for (key, value) in data {
+        let is_column = matches!(key.as_str(), "meaning" | "synonyms" | "antonyms" | "reading");
+        if !is_column && !value.is_null() && !existing.contains_key(key) {
+            set_extra(conn, word.id, key, Some(value.clone()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Convert the lookup's return value (dict or None) into fields
+fn lookup_result(result: &Bound<'_, PyAny>) -> Result<Map<String, Value>, String> {
+    if result.is_none() {
+        return Ok(Map::new());
+    }
+    if !result.is_instance_of::<PyDict>() {
+        return Err("Lookup must return a dict or None".to_string());
+    }
+    match py_to_json(result) {
+        Ok(Value::Object(map)) => Ok(map),
+        Ok(_) => Err("Lookup must return a dict or None".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Run (or resume) a job: call `lookup(word)` for each pending word, at most
+/// `policy.requests_per_second` times per second, retrying failures with exponential
+/// backoff. Progress is committed per word, so an interrupted run (Ctrl-C, `max_items`
+/// reached, crash) continues where it stopped. `retry_failed` re-queues words that
+/// exhausted their retries in an earlier run. Cancelling the job while it runs stops it
+/// after the current word.
+pub fn run_enrichment_job(
+    py: Python<'_>,
+    db_path: &str,
+    job_id: i64,
+    lookup: &Bound<'_, PyAny>,
+    policy: &EnrichmentPolicy,
+    max_items: Option<usize>,
+    retry_failed: bool,
+) -> PyResult<EnrichmentProgress> {
+    let db_err = |e: rusqlite::Error| pyo3::exceptions::PyRuntimeError::new_err(format!("Enrichment database error: {}", e));
+    let conn = open_database(db_path).map_err(db_err)?;
+    
+    let progress = read_progress(&conn, job_id).map_err(db_err)?
+        .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("Enrichment job {} not found", job_id)))?;
+    if progress.status == STATUS_CANCELLED {
+        return Ok(progress);
+    }
+    
+    if retry_failed {
+        conn.execute(
+            "UPDATE enrichment_items SET status = ?1 WHERE job_id = ?2 AND status = ?3",
+            params![STATUS_PENDING, job_id, STATUS_FAILED],
+        ).map_err(db_err)?;
+    }
+    set_job_status(&conn, job_id, STATUS_RUNNING).map_err(db_err)?;
+    
+    let pending: Vec<i64> = {
+        let mut stmt = conn.prepare(
+            "SELECT word_id FROM enrichment_items WHERE job_id = ?1 AND status = 'pending' ORDER BY word_id"
+        ).map_err(db_err)?;
+        let rows = stmt.query_map(params![job_id], |row| row.get(0)).map_err(db_err)?;
+        rows.filter_map(|r| r.ok()).collect()
+    };
+    
+    let mut last_call: Option<Instant> = None;
+    let wait = |py: Python<'_>, duration: Duration| {
+        if !duration.is_zero() {
+            py.allow_threads(|| std::thread::sleep(duration));
+        }
+    };
+    
+    for (processed, word_id) in pending.into_iter().enumerate() {
+        // A cancel from elsewhere (e.g. the UI) stops the run before the next word
+        if max_items.is_some_and(|max| processed >= max) || is_cancelled(&conn, job_id).map_err(db_err)? {
+            break;
+        }
+        
+        let Some(word) = get_word_by_id(&conn, word_id).map_err(db_err)? else {
+            set_item_status(&conn, job_id, word_id, STATUS_FAILED, Some("Word no longer exists")).map_err(db_err)?;
+            continue;
+        };
+        
+        let mut retry = 0;
+        loop {
+            // Ctrl-C pauses the job; the current word stays pending
+            if let Err(e) = py.check_signals() {
+                set_job_status(&conn, job_id, STATUS_PAUSED).map_err(db_err)?;
+                return Err(e);
+            }
+            
+            if let Some(last) = last_call {
+                wait(py, policy.min_interval().saturating_sub(last.elapsed()));
+            }
+            last_call = Some(Instant::now());
+            
+            let error = match lookup.call1((word.clone(),)) {
+                Ok(result) => match lookup_result(&result) {
+                    Ok(data) => {
+                        apply_enrichment(&conn, &word, &data).map_err(db_err)?;
+                        set_item_status(&conn, job_id, word_id, STATUS_DONE, None).map_err(db_err)?;
+                        break;
+                    }
+                    // A malformed result won't improve on retry
+                    Err(message) => {
+                        set_item_status(&conn, job_id, word_id, STATUS_FAILED, Some(&message)).map_err(db_err)?;
+                        break;
+                    }
+                },
+                Err(e) if e.is_instance_of::<pyo3::exceptions::PyKeyboardInterrupt>(py) => {
+                    set_job_status(&conn, job_id, STATUS_PAUSED).map_err(db_err)?;
+                    return Err(e);
+                }
+                Err(e) => e.to_string(),
+            };
+            
+            retry += 1;
+            if retry > policy.max_retries {
+                set_item_status(&conn, job_id, word_id, STATUS_FAILED, Some(&error)).map_err(db_err)?;
+                break;
+            }
+            conn.execute(
+                "UPDATE enrichment_items SET attempts = attempts + 1, last_error = ?1 WHERE job_id = ?2 AND word_id = ?3",
+                params![error, job_id, word_id],
+            ).map_err(db_err)?;
+            wait(py, policy.backoff(retry));
+        }
+    }
+    
+    let progress = read_progress(&conn, job_id).map_err(db_err)?
+        .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Enrichment job disappeared"))?;
+    if progress.status == STATUS_CANCELLED {
+        return Ok(progress);
+    }
+    let status = if progress.pending == 0 { STATUS_DONE } else { STATUS_PAUSED };
+    set_job_status(&conn, job_id, status).map_err(db_err)?;
+    
+    Ok(EnrichmentProgress { status: status.to_string(), ..progress })
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "create_enrichment_job", signature = (db_path, category=None))]
+pub fn py_create_enrichment_job(db_path: &str, category: Option<&str>) -> PyResult<i64> {
+    create_enrichment_job(db_path, category)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "run_enrichment_job", signature = (db_path, job_id, lookup, policy=None, max_items=None, retry_failed=false))]
+pub fn py_run_enrichment_job(
+    py: Python<'_>,
+    db_path: &str,
+    job_id: i64,
+    lookup: &Bound<'_, PyAny>,
+    policy: Option<EnrichmentPolicy>,
+    max_items: Option<usize>,
+    retry_failed: bool,
+) -> PyResult<EnrichmentProgress> {
+    run_enrichment_job(py, db_path, job_id, lookup, &policy.unwrap_or_default(), max_items, retry_failed)
+}
+
+#[pyfunction]
+#[pyo3(name = "get_enrichment_progress")]
+pub fn py_get_enrichment_progress(db_path: &str, job_id: i64) -> PyResult<EnrichmentProgress> {
+    get_enrichment_progress(db_path, job_id)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "cancel_enrichment_job")]
+pub fn py_cancel_enrichment_job(db_path: &str, job_id: i64) -> PyResult<bool> {
+    cancel_enrichment_job(db_path, job_id)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
@@ -1,14 +1,78 @@
 //! File parsing for VoIQ vocabulary import (Excel and CSV)
 
 use pyo3::prelude::*;
-use calamine::{Reader, open_workbook, Xlsx, Data};
+use calamine::{Reader, open_workbook, Xlsx, Data, Range};
 use csv::ReaderBuilder;
-use crate::db::Word;
-use rusqlite::Connection;
-use std::path::Path;
+use crate::content_filter::ContentFilter;
+use crate::db::{open_database, default_category_for, set_frequency_ranks, LoadMode, Word, DIFFICULTY_LEVELS};
+use crate::journal::{begin_operation, commit_operation, OP_IMPORT};
+use crate::ruby::ruby_base;
+use crate::srs::copy_known_progress;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// How parsed words are added to the database
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ImportOptions {
+    pub mode: LoadMode,
+    /// New words already mastered elsewhere (same text in another deck) take over that
+    /// word's review schedule instead of starting from scratch
+    pub premaster_known: bool,
+}
+
+/// File extensions `parse_file` can import
+pub const SUPPORTED_EXTENSIONS: &[&str] = &["xlsx", "xls", "csv"];
+
+/// Where `import_folder` takes each file's category from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CategorySource {
+    /// The file name without its extension
+    FileName,
+    /// Each sheet of a workbook becomes its own category; CSV files use their file name
+    SheetName,
+}
+
+impl CategorySource {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "" | "filename" | "file" => Ok(CategorySource::FileName),
+            "sheet" | "sheetname" => Ok(CategorySource::SheetName),
+            _ => Err(format!("Unknown category source: {}", name)),
+        }
+    }
+}
+
+/// Outcome of importing one file (or one sheet of a workbook) in `import_folder`
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct ImportReport {
+    #[pyo3(get)]
+    pub file_path: String,
+    /// Set when the category came from a sheet
+    #[pyo3(get)]
+    pub sheet: Option<String>,
+    #[pyo3(get)]
+    pub category: String,
+    #[pyo3(get)]
+    pub imported: usize,
+    /// Why nothing was imported from this file, if it failed
+    #[pyo3(get)]
+    pub error: Option<String>,
+}
+
+#[pymethods]
+impl ImportReport {
+    fn __repr__(&self) -> String {
+        match &self.error {
+            Some(error) => format!("ImportReport(file_path='{}', category='{}', error='{}')", self.file_path, self.category, error),
+            None => format!("ImportReport(file_path='{}', category='{}', imported={})", self.file_path, self.category, self.imported),
+        }
+    }
+}
 
 /// Parse file (Excel or CSV) and load into database with category
-pub fn parse_file(file_path: &str, db_path: &str, category: &str) -> Result<usize, String> {
+pub fn parse_file(file_path: &str, db_path: &str, category: &str, options: ImportOptions) -> Result<usize, String> {
     let path = Path::new(file_path);
     let extension = path.extension()
         .and_then(|e| e.to_str())
@@ -16,8 +80,8 @@ pub fn parse_file(file_path: &str, db_path: &str, category: &str) -> Result<usiz
         .unwrap_or_default();
     
     match extension.as_str() {
-        "xlsx" | "xls" => parse_excel(file_path, db_path, category),
-        "csv" => parse_csv(file_path, db_path, category),
+        "xlsx" | "xls" => parse_excel(file_path, db_path, category, options),
+        "csv" => parse_csv(file_path, db_path, category, options),
         _ => Err(format!("Unsupported file format: .{}", extension)),
     }
 }
@@ -29,10 +93,19 @@ pub struct ColumnMapping {
     pub meaning: Option<usize>,
     pub synonyms: Option<usize>,
     pub antonyms: Option<usize>,
+    pub reading: Option<usize>,
+    pub notes: Option<usize>,
+    pub example: Option<usize>,
+    pub pos: Option<usize>,
+    pub difficulty: Option<usize>,
+    /// Word ids written by `export_for_editing`; imports ignore them
+    pub id: Option<usize>,
+    /// Unrecognized columns (index, header) stored as extra fields
+    pub extra: Vec<(usize, String)>,
 }
 
 /// Detect column indices from header names
-fn detect_columns(headers: &[String]) -> Result<ColumnMapping, String> {
+pub(crate) fn detect_columns(headers: &[String]) -> Result<ColumnMapping, String> {
     let mut mapping = ColumnMapping::default();
     let mut word_found = false;
     
@@ -45,7 +118,14 @@ fn detect_columns(headers: &[String]) -> Result<ColumnMapping, String> {
             "meaning" | "meanings" | "definition" | "definitions" => mapping.meaning = Some(i),
             "synonym" | "synonyms" => mapping.synonyms = Some(i),
             "antonym" | "antonyms" => mapping.antonyms = Some(i),
-            _ => {} // Unknown columns ignored
+            "reading" | "readings" | "furigana" | "kana" | "pinyin" => mapping.reading = Some(i),
+            "note" | "notes" | "mnemonic" | "mnemonics" => mapping.notes = Some(i),
+            "example" | "examples" | "sentence" | "example sentence" => mapping.example = Some(i),
+            "pos" | "part of speech" | "part_of_speech" | "word class" => mapping.pos = Some(i),
+            "difficulty" | "level" => mapping.difficulty = Some(i),
+            "voiq id" => mapping.id = Some(i),
+            "" => {} // Unnamed columns ignored
+            _ => mapping.extra.push((i, header.trim().to_string())),
         }
     }
     
@@ -63,7 +143,13 @@ fn detect_columns(headers: &[String]) -> Result<ColumnMapping, String> {
 }
 
 /// Parse Excel file and load into database with category
-pub fn parse_excel(file_path: &str, db_path: &str, category: &str) -> Result<usize, String> {
+pub fn parse_excel(file_path: &str, db_path: &str, category: &str, options: ImportOptions) -> Result<usize, String> {
+    let (_, words) = read_excel_words(file_path)?;
+    save_words_to_db(db_path, words, category, file_path, options)
+}
+
+/// Words of the first sheet of a workbook, with the detected columns
+fn read_excel_words(file_path: &str) -> Result<(ColumnMapping, Vec<Word>), String> {
     let mut workbook: Xlsx<_> = open_workbook(file_path)
         .map_err(|e| format!("Failed to open Excel file: {}", e))?;
     
@@ -74,6 +160,11 @@ pub fn parse_excel(file_path: &str, db_path: &str, category: &str) -> Result<usi
     let range = workbook.worksheet_range(&sheet_name)
         .map_err(|e| format!("Failed to read sheet: {}", e))?;
     
+    read_sheet_words(&range)
+}
+
+/// Words of a worksheet whose first row is a header, with the detected columns
+fn read_sheet_words(range: &Range<Data>) -> Result<(ColumnMapping, Vec<Word>), String> {
     let mut words = Vec::new();
     let mut rows = range.rows();
     
@@ -88,24 +179,115 @@ pub fn parse_excel(file_path: &str, db_path: &str, category: &str) -> Result<usi
         let meaning_val = mapping.meaning.filter(|&i| i < row_len).map(|i| get_cell_string(&row[i])).unwrap_or_default();
         let synonyms_val = mapping.synonyms.filter(|&i| i < row_len).map(|i| get_cell_string(&row[i])).unwrap_or_default();
         let antonyms_val = mapping.antonyms.filter(|&i| i < row_len).map(|i| get_cell_string(&row[i])).unwrap_or_default();
+        let extra_val = build_extra(&mapping, |i| row.get(i).and_then(get_cell_value));
+        let reading_val = mapping.reading.filter(|&i| i < row_len).map(|i| get_cell_string(&row[i])).unwrap_or_default();
+        let notes_val = mapping.notes.filter(|&i| i < row_len).map(|i| get_cell_string(&row[i])).unwrap_or_default();
+        let example_val = mapping.example.filter(|&i| i < row_len).map(|i| get_cell_string(&row[i])).unwrap_or_default();
+        let pos_val = mapping.pos.filter(|&i| i < row_len).map(|i| get_cell_string(&row[i])).unwrap_or_default();
+        let difficulty_val = mapping.difficulty.filter(|&i| i < row_len).and_then(|i| parse_difficulty(&get_cell_string(&row[i])));
+        let id_val = mapping.id.filter(|&i| i < row_len).map_or(0, |i| parse_id(&get_cell_string(&row[i])));
+        let (word_val, reading_val) = split_ruby_word(word_val, reading_val);
         
         if !word_val.is_empty() {
             words.push(Word {
-                id: 0,
+                id: id_val,
                 word: word_val,
+                reading: reading_val,
                 meaning: meaning_val,
                 synonyms: synonyms_val,
                 antonyms: antonyms_val,
-                category: String::new(),
+                extra: extra_val,
+                notes: notes_val,
+                example: example_val,
+                pos: pos_val,
+                difficulty: difficulty_val,
+                ..Default::default()
             });
         }
     }
     
-    save_words_to_db(db_path, words, category)
+    Ok((mapping, words))
+}
+
+/// Import every sheet of a workbook into a category named after the sheet
+fn import_sheets(file_path: &str, db_path: &str, options: ImportOptions) -> Result<Vec<ImportReport>, String> {
+    let mut workbook: Xlsx<_> = open_workbook(file_path)
+        .map_err(|e| format!("Failed to open Excel file: {}", e))?;
+    let mut reports = Vec::new();
+    for sheet_name in workbook.sheet_names() {
+        let category = sheet_name.trim().to_string();
+        let imported = workbook.worksheet_range(&sheet_name)
+            .map_err(|e| format!("Failed to read sheet: {}", e))
+            .and_then(|range| read_sheet_words(&range))
+            .and_then(|(_, words)| save_words_to_db(db_path, words, &category, file_path, options));
+        reports.push(ImportReport {
+            file_path: file_path.to_string(),
+            sheet: Some(sheet_name),
+            category,
+            imported: *imported.as_ref().unwrap_or(&0),
+            error: imported.err(),
+        });
+    }
+    Ok(reports)
+}
+
+/// Supported files directly inside `dir_path`, in name order, skipping hidden files and
+/// the lock files spreadsheet programs leave next to open workbooks
+pub(crate) fn supported_files(dir_path: &str) -> Result<Vec<PathBuf>, String> {
+    let entries = std::fs::read_dir(dir_path)
+        .map_err(|e| format!("Failed to read folder: {}", e))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+            !name.starts_with('.') && !name.starts_with("~$") && SUPPORTED_EXTENSIONS.contains(&extension.as_str())
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Import one file into `category`, or its file name (or sheet names) per `category_from`
+pub(crate) fn import_path(path: &Path, db_path: &str, category: Option<&str>, category_from: CategorySource, options: ImportOptions) -> Vec<ImportReport> {
+    let file_path = path.to_string_lossy().to_string();
+    let is_csv = path.extension().is_some_and(|e| e.eq_ignore_ascii_case("csv"));
+    if category.is_none() && category_from == CategorySource::SheetName && !is_csv {
+        return match import_sheets(&file_path, db_path, options) {
+            Ok(sheets) => sheets,
+            Err(error) => vec![ImportReport { file_path, sheet: None, category: String::new(), imported: 0, error: Some(error) }],
+        };
+    }
+    let category = category.map(String::from)
+        .unwrap_or_else(|| path.file_stem().map(|s| s.to_string_lossy().trim().to_string()).unwrap_or_default());
+    let imported = parse_file(&file_path, db_path, &category, options);
+    vec![ImportReport {
+        file_path,
+        sheet: None,
+        category,
+        imported: *imported.as_ref().unwrap_or(&0),
+        error: imported.err(),
+    }]
+}
+
+/// Import every supported file directly inside `dir_path` (in name order, hidden and
+/// lock files skipped), each into the category named by `category_from`. A file that
+/// fails is reported and doesn't stop the others.
+pub fn import_folder(dir_path: &str, db_path: &str, category_from: CategorySource, options: ImportOptions) -> Result<Vec<ImportReport>, String> {
+    Ok(supported_files(dir_path)?.iter()
+        .flat_map(|path| import_path(path, db_path, None, category_from, options))
+        .collect())
 }
 
 /// Parse CSV file and load into database with category
-pub fn parse_csv(file_path: &str, db_path: &str, category: &str) -> Result<usize, String> {
+pub fn parse_csv(file_path: &str, db_path: &str, category: &str, options: ImportOptions) -> Result<usize, String> {
+    let (_, words) = read_csv_words(file_path)?;
+    save_words_to_db(db_path, words, category, file_path, options)
+}
+
+/// Words of a CSV file with a header row, with the detected columns
+fn read_csv_words(file_path: &str) -> Result<(ColumnMapping, Vec<Word>), String> {
     let mut reader = ReaderBuilder::new()
         .has_headers(true)
         .flexible(true)
@@ -130,25 +312,67 @@ pub fn parse_csv(file_path: &str, db_path: &str, category: &str) -> Result<usize
         let meaning_val = mapping.meaning.filter(|&i| i < row_len).map(|i| record.get(i).unwrap_or("").trim().to_string()).unwrap_or_default();
         let synonyms_val = mapping.synonyms.filter(|&i| i < row_len).map(|i| record.get(i).unwrap_or("").trim().to_string()).unwrap_or_default();
         let antonyms_val = mapping.antonyms.filter(|&i| i < row_len).map(|i| record.get(i).unwrap_or("").trim().to_string()).unwrap_or_default();
+        let extra_val = build_extra(&mapping, |i| {
+            record.get(i).map(str::trim).filter(|s| !s.is_empty()).map(|s| Value::String(s.to_string()))
+        });
+        let reading_val = mapping.reading.filter(|&i| i < row_len).map(|i| record.get(i).unwrap_or("").trim().to_string()).unwrap_or_default();
+        let notes_val = mapping.notes.filter(|&i| i < row_len).map(|i| record.get(i).unwrap_or("").trim().to_string()).unwrap_or_default();
+        let example_val = mapping.example.filter(|&i| i < row_len).map(|i| record.get(i).unwrap_or("").trim().to_string()).unwrap_or_default();
+        let pos_val = mapping.pos.filter(|&i| i < row_len).map(|i| record.get(i).unwrap_or("").trim().to_string()).unwrap_or_default();
+        let difficulty_val = mapping.difficulty.filter(|&i| i < row_len).and_then(|i| parse_difficulty(record.get(i).unwrap_or("")));
+        let id_val = mapping.id.filter(|&i| i < row_len).map_or(0, |i| parse_id(record.get(i).unwrap_or("")));
+        let (word_val, reading_val) = split_ruby_word(word_val, reading_val);
         
         if !word_val.is_empty() {
             words.push(Word {
-                id: 0,
+                id: id_val,
                 word: word_val,
+                reading: reading_val,
                 meaning: meaning_val,
                 synonyms: synonyms_val,
                 antonyms: antonyms_val,
-                category: String::new(),
+                extra: extra_val,
+                notes: notes_val,
+                example: example_val,
+                pos: pos_val,
+                difficulty: difficulty_val,
+                ..Default::default()
             });
         }
     }
     
-    save_words_to_db(db_path, words, category)
+    Ok((mapping, words))
+}
+
+/// Words of an Excel or CSV file (first sheet only) with the detected columns. Words
+/// carry the id from a "VoIQ ID" column, or 0.
+pub(crate) fn read_words(file_path: &str) -> Result<(ColumnMapping, Vec<Word>), String> {
+    let extension = Path::new(file_path).extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "xlsx" | "xls" => read_excel_words(file_path),
+        "csv" => read_csv_words(file_path),
+        _ => Err(format!("Unsupported file format: .{}", extension)),
+    }
+}
+
+/// Words written in ruby bracket notation ("漢字[かんじ]") are stored as the plain word with
+/// the notation as their reading, unless the file has its own reading column value
+fn split_ruby_word(word: String, reading: String) -> (String, String) {
+    if word.contains('[') && reading.is_empty() {
+        let base = ruby_base(&word);
+        if base != word {
+            return (base, word);
+        }
+    }
+    (word, reading)
 }
 
 /// Save words to database with category (shared by Excel and CSV parsers)
-fn save_words_to_db(db_path: &str, words: Vec<Word>, category: &str) -> Result<usize, String> {
-    let conn = Connection::open(db_path)
+fn save_words_to_db(db_path: &str, words: Vec<Word>, category: &str, source: &str, options: ImportOptions) -> Result<usize, String> {
+    let conn = open_database(db_path)
         .map_err(|e| format!("Failed to open database: {}", e))?;
     
     // Initialize database
@@ -158,12 +382,60 @@ fn save_words_to_db(db_path: &str, words: Vec<Word>, category: &str) -> Result<u
     // Note: No longer clearing all vocabulary - just adding to the category
     // To replace a category, delete it first then re-upload
     
-    let count = crate::db::load_vocabulary(&conn, words, category)
+    // Entries matching the content filter are skipped in exclude mode
+    let filter = ContentFilter::load(&conn);
+    let words: Vec<Word> = words.into_iter().filter(|w| !filter.excludes_word(w)).collect();
+    
+    // Journaled so words from an import cut short can be removed on the next start
+    let journal_id = begin_operation(&conn, OP_IMPORT, Some(category), Some(source))
+        .map_err(|e| format!("Failed to write import journal: {}", e))?;
+    let count = crate::db::load_vocabulary_for_import(&conn, words, category, Some(journal_id), options.mode)
         .map_err(|e| format!("Failed to load vocabulary: {}", e))?;
+    if options.premaster_known {
+        copy_known_progress(&conn, journal_id)
+            .map_err(|e| format!("Failed to copy progress of known words: {}", e))?;
+    }
+    commit_operation(&conn, journal_id, count)
+        .map_err(|e| format!("Failed to write import journal: {}", e))?;
     
     Ok(count)
 }
 
+/// Collect unrecognized columns of a row into a JSON object string (empty if none are set)
+fn build_extra(mapping: &ColumnMapping, value_at: impl Fn(usize) -> Option<Value>) -> String {
+    let map: Map<String, Value> = mapping.extra.iter()
+        .filter_map(|(i, header)| value_at(*i).map(|v| (header.clone(), v)))
+        .collect();
+    
+    if map.is_empty() { String::new() } else { Value::Object(map).to_string() }
+}
+
+/// Difficulty from a cell: a whole number within `DIFFICULTY_LEVELS` ("3" or "3.0"),
+/// None for anything else
+fn parse_difficulty(text: &str) -> Option<i64> {
+    let value: f64 = text.trim().parse().ok()?;
+    let level = value as i64;
+    (value.fract() == 0.0 && DIFFICULTY_LEVELS.contains(&level)).then_some(level)
+}
+
+/// Word id from a cell ("12" or "12.0"), 0 when blank or not an id
+fn parse_id(text: &str) -> i64 {
+    text.trim().parse::<f64>().ok()
+        .filter(|id| id.fract() == 0.0 && *id > 0.0)
+        .map_or(0, |id| id as i64)
+}
+
+/// Helper to extract a typed JSON value from Excel cell (None for empty cells)
+fn get_cell_value(cell: &Data) -> Option<Value> {
+    match cell {
+        Data::Int(i) => Some(Value::from(*i)),
+        Data::Float(f) => Some(Value::from(*f)),
+        Data::Bool(b) => Some(Value::Bool(*b)),
+        Data::Error(_) | Data::Empty => None,
+        _ => Some(get_cell_string(cell)).filter(|s| !s.is_empty()).map(Value::String),
+    }
+}
+
 /// Helper to extract string from Excel cell
 fn get_cell_string(cell: &Data) -> String {
     match cell {
@@ -179,20 +451,91 @@ fn get_cell_string(cell: &Data) -> String {
     }
 }
 
+/// Read a frequency list CSV (word and rank columns) and store the ranks on matching words.
+/// A header row naming the columns is optional; without one the first two columns are used.
+/// Repeated words keep their best rank. Returns how many words got a rank.
+pub fn load_frequency_ranks(file_path: &str, db_path: &str) -> Result<usize, String> {
+    let mut reader = ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_path(file_path)
+        .map_err(|e| format!("Failed to open CSV file: {}", e))?;
+    
+    let mut records = reader.records();
+    let first = match records.next() {
+        Some(record) => record.map_err(|e| format!("Failed to read CSV row: {}", e))?,
+        None => return Ok(0),
+    };
+    
+    let parse_rank = |s: Option<&str>| s.and_then(|s| s.trim().parse::<i64>().ok()).filter(|r| *r > 0);
+    let (mut word_col, mut rank_col) = (0, 1);
+    let mut ranks: HashMap<String, i64> = HashMap::new();
+    let mut add = |word: &str, rank: i64| {
+        let word = word.trim();
+        if !word.is_empty() {
+            let best = ranks.entry(word.to_lowercase()).or_insert(rank);
+            *best = (*best).min(rank);
+        }
+    };
+    
+    if parse_rank(first.get(1)).is_some() {
+        add(first.get(0).unwrap_or(""), parse_rank(first.get(1)).unwrap_or_default());
+    } else {
+        for (i, header) in first.iter().enumerate() {
+            match header.to_lowercase().trim() {
+                "word" | "words" | "vocabulary" | "lemma" => word_col = i,
+                "rank" | "frequency rank" | "frequency_rank" | "frequency" => rank_col = i,
+                _ => {}
+            }
+        }
+    }
+    
+    for result in records {
+        let record = result.map_err(|e| format!("Failed to read CSV row: {}", e))?;
+        if let Some(rank) = parse_rank(record.get(rank_col)) {
+            add(record.get(word_col).unwrap_or(""), rank);
+        }
+    }
+    
+    let ranks: Vec<(String, i64)> = ranks.into_iter().collect();
+    let mut conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    set_frequency_ranks(&mut conn, &ranks)
+        .map_err(|e| format!("Failed to save frequency ranks: {}", e))
+}
+
 // ============= Python Bindings =============
 
 #[pyfunction]
-#[pyo3(name = "parse_excel")]
-pub fn py_parse_excel(file_path: &str, db_path: &str, category: Option<&str>) -> PyResult<usize> {
-    let cat = category.unwrap_or("Default");
-    parse_file(file_path, db_path, cat)
-        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e))
+#[pyo3(name = "parse_excel", signature = (file_path, db_path, category=None, mode="append", premaster_known=false))]
+pub fn py_parse_excel(file_path: &str, db_path: &str, category: Option<&str>, mode: &str, premaster_known: bool) -> PyResult<usize> {
+    let mode = LoadMode::parse(mode).map_err(pyo3::exceptions::PyValueError::new_err)?;
+    let cat = category.map(String::from).unwrap_or_else(|| default_category_for(db_path));
+    parse_file(file_path, db_path, &cat, ImportOptions { mode, premaster_known })
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "parse_csv", signature = (file_path, db_path, category=None, mode="append", premaster_known=false))]
+pub fn py_parse_csv(file_path: &str, db_path: &str, category: Option<&str>, mode: &str, premaster_known: bool) -> PyResult<usize> {
+    let mode = LoadMode::parse(mode).map_err(pyo3::exceptions::PyValueError::new_err)?;
+    let cat = category.map(String::from).unwrap_or_else(|| default_category_for(db_path));
+    parse_csv(file_path, db_path, &cat, ImportOptions { mode, premaster_known })
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "import_folder", signature = (dir_path, db_path, category_from="filename", mode="append", premaster_known=false))]
+pub fn py_import_folder(dir_path: &str, db_path: &str, category_from: &str, mode: &str, premaster_known: bool) -> PyResult<Vec<ImportReport>> {
+    let category_from = CategorySource::parse(category_from).map_err(pyo3::exceptions::PyValueError::new_err)?;
+    let mode = LoadMode::parse(mode).map_err(pyo3::exceptions::PyValueError::new_err)?;
+    import_folder(dir_path, db_path, category_from, ImportOptions { mode, premaster_known })
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
 }
 
 #[pyfunction]
-#[pyo3(name = "parse_csv")]
-pub fn py_parse_csv(file_path: &str, db_path: &str, category: Option<&str>) -> PyResult<usize> {
-    let cat = category.unwrap_or("Default");
-    parse_csv(file_path, db_path, cat)
-        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e))
+#[pyo3(name = "set_frequency_ranks_from_csv")]
+pub fn py_set_frequency_ranks_from_csv(file_path: &str, db_path: &str) -> PyResult<usize> {
+    load_frequency_ranks(file_path, db_path)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
 }
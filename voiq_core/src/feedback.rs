@@ -0,0 +1,80 @@
+//! Question quality feedback - user ratings that steer the generator away from bad questions
+
+use pyo3::prelude::*;
+use rusqlite::{Connection, Result as SqliteResult, params};
+use std::collections::{HashMap, HashSet};
+use crate::questions::MCQQuestion;
+use crate::db::open_database;
+
+/// Ratings at or below this value (on a 1-5 scale) flag a question as bad
+pub const FLAG_RATING_MAX: i64 = 2;
+
+/// Record a rating (1-5) for a question as it was shown to the user.
+/// Generated questions have no stored id, so the word, question type and
+/// options are recorded; authored questions are also linked by `question_id`.
+pub fn rate_question(conn: &Connection, question: &MCQQuestion, rating: i64, reason: Option<&str>) -> Result<i64, String> {
+    if !(1..=5).contains(&rating) {
+        return Err(format!("Rating must be between 1 and 5, got {}", rating));
+    }
+    
+    // Store the wrong options so flagged distractor combinations can be avoided later
+    let distractors: Vec<&String> = question.options.iter()
+        .enumerate()
+        .filter(|(i, _)| *i != question.correct_index)
+        .map(|(_, o)| o)
+        .collect();
+    let distractors_json = serde_json::to_string(&distractors)
+        .map_err(|e| format!("Failed to encode options: {}", e))?;
+    
+    conn.execute(
+        "INSERT INTO question_ratings (question_id, word_id, question_type, distractors, rating, reason)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![question.question_id, question.word_id, question.question_type, distractors_json, rating, reason],
+    ).map_err(|e| format!("Failed to save rating: {}", e))?;
+    
+    Ok(conn.last_insert_rowid())
+}
+
+/// Distractors that appeared in flagged questions for a word and question type
+pub fn flagged_distractors(conn: &Connection, word_id: i64, question_type: &str) -> SqliteResult<HashSet<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT distractors FROM question_ratings WHERE word_id = ?1 AND question_type = ?2 AND rating <= ?3"
+    )?;
+    let rows = stmt.query_map(params![word_id, question_type, FLAG_RATING_MAX], |row| row.get::<_, String>(0))?;
+    
+    Ok(rows
+        .filter_map(|r| r.ok())
+        .flat_map(|json| serde_json::from_str::<Vec<String>>(&json).unwrap_or_default())
+        .collect())
+}
+
+/// Number of times each question type was flagged for a word
+pub fn flagged_type_counts(conn: &Connection, word_id: i64) -> SqliteResult<HashMap<String, i64>> {
+    let mut stmt = conn.prepare(
+        "SELECT question_type, COUNT(*) FROM question_ratings
+         WHERE word_id = ?1 AND question_id IS NULL AND rating <= ?2
+         GROUP BY question_type"
+    )?;
+    let rows = stmt.query_map(params![word_id, FLAG_RATING_MAX], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    Ok(rows.filter_map(|r| r.ok()).collect())
+}
+
+/// Ids of authored questions that have been flagged
+pub fn flagged_custom_questions(conn: &Connection) -> SqliteResult<HashSet<i64>> {
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT question_id FROM question_ratings WHERE question_id IS NOT NULL AND rating <= ?1"
+    )?;
+    let rows = stmt.query_map(params![FLAG_RATING_MAX], |row| row.get(0))?;
+    Ok(rows.filter_map(|r| r.ok()).collect())
+}
+
+// ============= Python Binding =============
+
+#[pyfunction]
+#[pyo3(name = "rate_question", signature = (db_path, question, rating, reason=None))]
+pub fn py_rate_question(db_path: &str, question: MCQQuestion, rating: i64, reason: Option<&str>) -> PyResult<i64> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    rate_question(&conn, &question, rating, reason)
+        .map_err(pyo3::exceptions::PyValueError::new_err)
+}
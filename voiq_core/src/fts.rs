@@ -0,0 +1,153 @@
+//! Full-text search over vocabulary using an SQLite FTS5 index.
+//!
+//! `vocabulary_fts` is an external-content index of `vocabulary`, kept in sync by
+//! triggers, so large decks can be searched without scanning every meaning with LIKE.
+
+use pyo3::prelude::*;
+use rusqlite::{Connection, params};
+use crate::db::{open_database, Word, WORD_COLUMNS, category_condition, word_from_row};
+
+/// A search result with its rank and a highlighted excerpt
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    #[pyo3(get)]
+    pub word: Word,
+    /// bm25 score; lower is more relevant
+    #[pyo3(get)]
+    pub rank: f64,
+    /// Excerpt of the best matching field with matches wrapped in the highlight markers
+    #[pyo3(get)]
+    pub snippet: String,
+}
+
+#[pymethods]
+impl SearchHit {
+    fn __repr__(&self) -> String {
+        format!("SearchHit(word='{}', snippet='{}')", self.word.word, self.snippet)
+    }
+}
+
+/// Create the index and its sync triggers, filling the index when it is new
+pub fn create_fts(conn: &Connection) -> rusqlite::Result<()> {
+    let exists: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM sqlite_master WHERE name = 'vocabulary_fts'",
+        [],
+        |row| row.get(0),
+    )?;
+    
+    conn.execute_batch(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS vocabulary_fts USING fts5(
+            word, meaning, synonyms, antonyms, reading,
+            content='vocabulary', content_rowid='id', tokenize='unicode61 remove_diacritics 2'
+        );
+        CREATE TRIGGER IF NOT EXISTS vocabulary_fts_insert AFTER INSERT ON vocabulary BEGIN
+            INSERT INTO vocabulary_fts (rowid, word, meaning, synonyms, antonyms, reading)
+            VALUES (new.id, new.word, new.meaning, new.synonyms, new.antonyms, new.reading);
+        END;
+        CREATE TRIGGER IF NOT EXISTS vocabulary_fts_delete AFTER DELETE ON vocabulary BEGIN
+            INSERT INTO vocabulary_fts (vocabulary_fts, rowid, word, meaning, synonyms, antonyms, reading)
+            VALUES ('delete', old.id, old.word, old.meaning, old.synonyms, old.antonyms, old.reading);
+        END;
+        CREATE TRIGGER IF NOT EXISTS vocabulary_fts_update AFTER UPDATE ON vocabulary BEGIN
+            INSERT INTO vocabulary_fts (vocabulary_fts, rowid, word, meaning, synonyms, antonyms, reading)
+            VALUES ('delete', old.id, old.word, old.meaning, old.synonyms, old.antonyms, old.reading);
+            INSERT INTO vocabulary_fts (rowid, word, meaning, synonyms, antonyms, reading)
+            VALUES (new.id, new.word, new.meaning, new.synonyms, new.antonyms, new.reading);
+        END;"
+    )?;
+    
+    if !exists {
+        rebuild_index(conn)?;
+    }
+    Ok(())
+}
+
+/// Rebuild the index from `vocabulary`
+pub fn rebuild_index(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute("INSERT INTO vocabulary_fts (vocabulary_fts) VALUES ('rebuild')", [])?;
+    Ok(())
+}
+
+/// Turn free text into an FTS5 query: every term must match, the last as a prefix
+/// (so results follow typing). Quoting keeps FTS5 operators in the input literal.
+pub fn to_match_query(text: &str) -> String {
+    let terms: Vec<String> = text.split_whitespace()
+        .map(|t| format!("\"{}\"", t.replace('"', "\"\"")))
+        .collect();
+    match terms.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} {}*", rest.join(" "), last),
+        Some((last, _)) => format!("{}*", last),
+        None => String::new(),
+    }
+}
+
+/// Ranked full-text search. `raw` passes `query` to FTS5 unchanged (boolean operators,
+/// column filters like `meaning:bird`); otherwise it's treated as plain text.
+pub fn full_text_search(
+    db_path: &str,
+    query: &str,
+    categories: Option<Vec<String>>,
+    limit: usize,
+    highlight: (&str, &str),
+    raw: bool,
+) -> Result<Vec<SearchHit>, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    
+    let match_query = if raw { query.trim().to_string() } else { to_match_query(query) };
+    if match_query.is_empty() {
+        return Ok(Vec::new());
+    }
+    
+    let category_clause = category_condition(categories.as_deref())
+        .map(|c| format!(" AND {}", c))
+        .unwrap_or_default();
+    let sql = format!(
+        "SELECT {}, bm25(vocabulary_fts, 10.0, 2.0, 4.0, 1.0, 5.0) AS rank,
+                snippet(vocabulary_fts, -1, ?2, ?3, '…', 12) AS snippet
+         FROM vocabulary_fts JOIN vocabulary v ON v.id = vocabulary_fts.rowid
+         WHERE vocabulary_fts MATCH ?1 AND v.deleted_at IS NULL{}
+         ORDER BY rank LIMIT ?4",
+        WORD_COLUMNS, category_clause
+    );
+    
+    let mut stmt = conn.prepare(&sql)
+        .map_err(|e| format!("Failed to prepare search: {}", e))?;
+    let hits = stmt.query_map(params![match_query, highlight.0, highlight.1, limit as i64], |row| {
+        Ok(SearchHit {
+            word: word_from_row(row)?,
+            rank: row.get("rank")?,
+            snippet: row.get::<_, Option<String>>("snippet")?.unwrap_or_default(),
+        })
+    }).map_err(|e| format!("Search failed: {}", e))?;
+    
+    hits.collect::<rusqlite::Result<Vec<_>>>()
+        .map_err(|e| format!("Search failed: {}", e))
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "full_text_search", signature = (db_path, query, categories=None, limit=50, highlight_start="<mark>", highlight_end="</mark>", raw=false))]
+pub fn py_full_text_search(
+    db_path: &str,
+    query: &str,
+    categories: Option<Vec<String>>,
+    limit: usize,
+    highlight_start: &str,
+    highlight_end: &str,
+    raw: bool,
+) -> PyResult<Vec<SearchHit>> {
+    full_text_search(db_path, query, categories, limit, (highlight_start, highlight_end), raw)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "rebuild_search_index")]
+pub fn py_rebuild_search_index(db_path: &str) -> PyResult<()> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    rebuild_index(&conn)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
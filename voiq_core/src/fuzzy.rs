@@ -2,6 +2,7 @@
 
 use pyo3::prelude::*;
 use strsim::{levenshtein, normalized_levenshtein, jaro_winkler};
+use crate::translit::fold_script;
 
 /// Result of fuzzy matching comparison
 #[pyclass]
@@ -13,6 +14,71 @@ pub struct MatchResult {
     pub similarity_score: f64,
     #[pyo3(get)]
     pub feedback: String,
+    /// Edit distance between the normalized answers (0 for an exact match)
+    #[pyo3(get)]
+    pub distance: usize,
+    /// The expected answer as given, for UIs composing their own messages
+    #[pyo3(get)]
+    pub expected: String,
+}
+
+/// How `MatchResult::feedback` is phrased
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FeedbackStyle {
+    /// Empty feedback; the UI builds messages from the structured fields
+    None,
+    /// Short English messages without emoji (terminals, screen readers)
+    Plain,
+    /// Full sentences including match percentage and distance
+    Verbose,
+    /// Short messages with emoji (the original style)
+    #[default]
+    Emoji,
+}
+
+impl FeedbackStyle {
+    pub fn parse(style: &str) -> Option<Self> {
+        match style.to_lowercase().as_str() {
+            "none" => Some(FeedbackStyle::None),
+            "plain" => Some(FeedbackStyle::Plain),
+            "verbose" => Some(FeedbackStyle::Verbose),
+            "emoji" => Some(FeedbackStyle::Emoji),
+            _ => None,
+        }
+    }
+}
+
+/// Outcome of a comparison, before it is phrased
+enum MatchKind {
+    Exact,
+    Close,
+    Almost,
+    Incorrect,
+}
+
+/// "1 character" / "n characters"
+fn characters(n: usize) -> String {
+    if n == 1 { "1 character".to_string() } else { format!("{} characters", n) }
+}
+
+/// Phrase feedback for a comparison in the requested style
+fn compose_feedback(kind: MatchKind, similarity: f64, distance: usize, expected: &str, style: FeedbackStyle) -> String {
+    let percent = (similarity * 100.0) as i32;
+    match (style, kind) {
+        (FeedbackStyle::None, _) => String::new(),
+        (FeedbackStyle::Emoji, MatchKind::Exact) => "Perfect! ✓".to_string(),
+        (FeedbackStyle::Plain, MatchKind::Exact) => "Perfect!".to_string(),
+        (FeedbackStyle::Verbose, MatchKind::Exact) => format!("Perfect! Your answer matches '{}' exactly.", expected),
+        (FeedbackStyle::Emoji, MatchKind::Close) => format!("Close enough! ✓ ({}% match)", percent),
+        (FeedbackStyle::Plain, MatchKind::Close) => format!("Close enough! ({}% match)", percent),
+        (FeedbackStyle::Verbose, MatchKind::Close) => format!(
+            "Close enough! Your answer matches '{}' at {}% ({} off).", expected, percent, characters(distance)),
+        (FeedbackStyle::Verbose, MatchKind::Almost) => format!(
+            "Almost! Your answer is {} off ({}% match). Expected: '{}'", characters(distance), percent, expected),
+        (_, MatchKind::Almost) => format!("Almost! {} characters off. Expected: '{}'", distance, expected),
+        (FeedbackStyle::Verbose, MatchKind::Incorrect) => format!("Incorrect ({}% match). Expected: '{}'", percent, expected),
+        (_, MatchKind::Incorrect) => format!("Incorrect. Expected: '{}'", expected),
+    }
 }
 
 #[pymethods]
@@ -23,47 +89,68 @@ impl MatchResult {
     }
 }
 
-/// Check if user input matches expected answer with fuzzy matching
-pub fn check_match(user_input: &str, expected: &str, threshold: f64) -> MatchResult {
-    let input_normalized = user_input.trim().to_lowercase();
-    let expected_normalized = expected.trim().to_lowercase();
+/// Similarity of two normalized strings
+fn similarity(a: &str, b: &str) -> f64 {
+    // Weighted average (Jaro-Winkler is better for typos)
+    normalized_levenshtein(a, b) * 0.4 + jaro_winkler(a, b) * 0.6
+}
+
+/// Check if user input matches expected answer with fuzzy matching.
+/// With `transliterate`, answers are also compared after folding kana, Cyrillic and
+/// tone-number pinyin to a common script (see `translit::fold_script`).
+pub fn check_match(user_input: &str, expected: &str, threshold: f64, transliterate: bool, style: FeedbackStyle) -> MatchResult {
+    let mut input_normalized = user_input.trim().to_lowercase();
+    let mut expected_normalized = expected.trim().to_lowercase();
+    
+    if transliterate {
+        let input_folded = fold_script(&input_normalized);
+        let expected_folded = fold_script(&expected_normalized);
+        if similarity(&input_folded, &expected_folded) > similarity(&input_normalized, &expected_normalized) {
+            input_normalized = input_folded;
+            expected_normalized = expected_folded;
+        }
+    }
     
     // Exact match
     if input_normalized == expected_normalized {
         return MatchResult {
             is_correct: true,
             similarity_score: 1.0,
-            feedback: "Perfect! ✓".to_string(),
+            feedback: compose_feedback(MatchKind::Exact, 1.0, 0, expected, style),
+            distance: 0,
+            expected: expected.to_string(),
         };
     }
     
     // Calculate similarity using multiple algorithms
-    let levenshtein_sim = normalized_levenshtein(&input_normalized, &expected_normalized);
-    let jaro_sim = jaro_winkler(&input_normalized, &expected_normalized);
-    
-    // Weighted average (Jaro-Winkler is better for typos)
-    let similarity = (levenshtein_sim * 0.4 + jaro_sim * 0.6);
+    let similarity = similarity(&input_normalized, &expected_normalized);
+    let distance = levenshtein(&input_normalized, &expected_normalized);
     
-    let (is_correct, feedback) = if similarity >= threshold {
-        (true, format!("Close enough! ✓ ({}% match)", (similarity * 100.0) as i32))
+    let (is_correct, kind) = if similarity >= threshold {
+        (true, MatchKind::Close)
     } else if similarity >= 0.5 {
-        let distance = levenshtein(&input_normalized, &expected_normalized);
-        (false, format!("Almost! {} characters off. Expected: '{}'", distance, expected))
+        (false, MatchKind::Almost)
     } else {
-        (false, format!("Incorrect. Expected: '{}'", expected))
+        (false, MatchKind::Incorrect)
     };
     
     MatchResult {
         is_correct,
         similarity_score: similarity,
-        feedback,
+        feedback: compose_feedback(kind, similarity, distance, expected, style),
+        distance,
+        expected: expected.to_string(),
     }
 }
 
 // ============= Python Binding =============
 
 #[pyfunction]
-#[pyo3(name = "check_match")]
-pub fn py_check_match(user_input: &str, expected: &str, threshold: Option<f64>) -> MatchResult {
-    check_match(user_input, expected, threshold.unwrap_or(0.8))
+#[pyo3(name = "check_match", signature = (user_input, expected, threshold=None, transliterate=false, feedback_style="emoji"))]
+pub fn py_check_match(user_input: &str, expected: &str, threshold: Option<f64>, transliterate: bool, feedback_style: &str) -> PyResult<MatchResult> {
+    let style = FeedbackStyle::parse(feedback_style).ok_or_else(|| {
+        pyo3::exceptions::PyValueError::new_err(format!(
+            "Unknown feedback style '{}'. Expected one of: none, plain, verbose, emoji", feedback_style))
+    })?;
+    Ok(check_match(user_input, expected, threshold.unwrap_or(0.8), transliterate, style))
 }
@@ -0,0 +1,122 @@
+//! Edit history of vocabulary entries.
+//!
+//! Triggers on `vocabulary` write a `word_history` row for every changed field of an
+//! updated word, so edits are recorded whichever API (or import) made them. Moving a word
+//! to the trash and restoring it are recorded as "trash" and "restore"; deleting a word
+//! for good records a "delete" row holding the word's last values as a JSON object.
+//! History outlives the word it describes.
+
+use pyo3::prelude::*;
+use rusqlite::{params, Connection};
+use crate::db::open_database;
+
+/// Vocabulary columns whose changes are recorded
+const TRACKED_FIELDS: &[&str] = &[
+    "word", "meaning", "synonyms", "antonyms", "category", "reading", "notes", "pos",
+    "difficulty", "frequency_rank", "audio_path", "image_path", "extra",
+];
+
+/// One recorded change of a word
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct WordHistoryEntry {
+    #[pyo3(get)]
+    pub id: i64,
+    #[pyo3(get)]
+    pub word_id: i64,
+    /// "update", "trash", "restore" or "delete"
+    #[pyo3(get)]
+    pub action: String,
+    /// Changed field for updates (None otherwise)
+    #[pyo3(get)]
+    pub field: Option<String>,
+    /// Value before the change; for "delete", the word's fields as a JSON object
+    #[pyo3(get)]
+    pub old_value: Option<String>,
+    #[pyo3(get)]
+    pub new_value: Option<String>,
+    /// UTC "YYYY-MM-DD HH:MM:SS"
+    #[pyo3(get)]
+    pub changed_at: String,
+}
+
+#[pymethods]
+impl WordHistoryEntry {
+    fn __repr__(&self) -> String {
+        let field = self.field.as_ref().map_or("None".to_string(), |f| format!("'{}'", f));
+        format!("WordHistoryEntry(word_id={}, action='{}', field={}, changed_at='{}')",
+                self.word_id, self.action, field, self.changed_at)
+    }
+}
+
+/// Create the history table and the triggers that fill it
+pub fn create_history(conn: &Connection) -> rusqlite::Result<()> {
+    let changes = TRACKED_FIELDS.iter()
+        .map(|f| format!("SELECT '{0}' AS field, old.{0} AS old_value, new.{0} AS new_value", f))
+        .collect::<Vec<_>>()
+        .join(" UNION ALL ");
+    let snapshot = TRACKED_FIELDS.iter()
+        .map(|f| format!("'{0}', old.{0}", f))
+        .collect::<Vec<_>>()
+        .join(", ");
+    conn.execute_batch(&format!(
+        "CREATE TABLE IF NOT EXISTS word_history (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            word_id INTEGER NOT NULL,
+            action TEXT NOT NULL,
+            field TEXT,
+            old_value TEXT,
+            new_value TEXT,
+            changed_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        );
+        CREATE INDEX IF NOT EXISTS idx_word_history_word ON word_history(word_id, id);
+        CREATE TRIGGER IF NOT EXISTS vocabulary_history_update AFTER UPDATE ON vocabulary BEGIN
+            INSERT INTO word_history (word_id, action, field, old_value, new_value)
+            SELECT new.id, 'update', field, old_value, new_value FROM ({changes})
+            WHERE old_value IS NOT new_value;
+            INSERT INTO word_history (word_id, action)
+            SELECT new.id, CASE WHEN new.deleted_at IS NULL THEN 'restore' ELSE 'trash' END
+            WHERE (old.deleted_at IS NULL) != (new.deleted_at IS NULL);
+        END;
+        CREATE TRIGGER IF NOT EXISTS vocabulary_history_delete AFTER DELETE ON vocabulary BEGIN
+            INSERT INTO word_history (word_id, action, old_value) VALUES (old.id, 'delete', json_object({snapshot}));
+        END;",
+    ))
+}
+
+/// Changes of a word, newest first (at most `limit` when given)
+pub fn read_word_history(conn: &Connection, word_id: i64, limit: Option<usize>) -> rusqlite::Result<Vec<WordHistoryEntry>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, word_id, action, field, old_value, new_value, changed_at FROM word_history
+         WHERE word_id = ?1 ORDER BY id DESC LIMIT ?2"
+    )?;
+    let entries = stmt.query_map(params![word_id, limit.map_or(-1, |l| l as i64)], |row| Ok(WordHistoryEntry {
+        id: row.get(0)?,
+        word_id: row.get(1)?,
+        action: row.get(2)?,
+        field: row.get(3)?,
+        old_value: row.get(4)?,
+        new_value: row.get(5)?,
+        changed_at: row.get(6)?,
+    }))?
+    .filter_map(|e| e.ok())
+    .collect();
+    Ok(entries)
+}
+
+/// Changes of a word, newest first; also available after the word was deleted
+pub fn get_word_history(db_path: &str, word_id: i64, limit: Option<usize>) -> Result<Vec<WordHistoryEntry>, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    read_word_history(&conn, word_id, limit)
+        .map_err(|e| format!("Failed to read word history: {}", e))
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "get_word_history", signature = (db_path, word_id, limit=None))]
+pub fn py_get_word_history(db_path: &str, word_id: i64, limit: Option<usize>) -> PyResult<Vec<WordHistoryEntry>> {
+    get_word_history(db_path, word_id, limit)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
@@ -0,0 +1,331 @@
+//! Persistent queue for long-running maintenance jobs (enrichment, re-indexing, ...).
+//!
+//! Jobs are queued with a kind and JSON parameters and executed by `run_jobs`, which the
+//! app calls from a background thread or a scheduled task. Built-in kinds run in Rust;
+//! any other kind is dispatched to a Python handler.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rusqlite::{Connection, OptionalExtension, Row, params};
+use serde_json::Value;
+use crate::db::{open_database, init_database, json_to_py, py_to_json};
+use crate::fts::rebuild_index;
+use crate::enrich::{create_enrichment_job, run_enrichment_job, EnrichmentPolicy};
+
+/// Job states
+pub const JOB_QUEUED: &str = "queued";
+pub const JOB_RUNNING: &str = "running";
+pub const JOB_DONE: &str = "done";
+pub const JOB_FAILED: &str = "failed";
+pub const JOB_CANCELLED: &str = "cancelled";
+
+/// A job running longer than this is taken to belong to a runner that died
+pub const JOB_LEASE_MINUTES: i64 = 60;
+
+/// Kinds handled without a Python handler
+pub const BUILTIN_JOB_KINDS: &[&str] = &["reindex", "vacuum", "enrichment"];
+
+/// A queued, running or finished job
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct Job {
+    #[pyo3(get)]
+    pub id: i64,
+    #[pyo3(get)]
+    pub kind: String,
+    /// Parameters as a JSON object string (empty when none)
+    #[pyo3(get)]
+    pub params: String,
+    #[pyo3(get)]
+    pub status: String,
+    /// Result as a JSON string (empty until the job is done)
+    #[pyo3(get)]
+    pub result: String,
+    #[pyo3(get)]
+    pub error: Option<String>,
+    #[pyo3(get)]
+    pub created_at: String,
+    #[pyo3(get)]
+    pub started_at: Option<String>,
+    #[pyo3(get)]
+    pub finished_at: Option<String>,
+}
+
+#[pymethods]
+impl Job {
+    fn __repr__(&self) -> String {
+        format!("Job(id={}, kind='{}', status='{}')", self.id, self.kind, self.status)
+    }
+}
+
+const JOB_COLUMNS: &str = "id, kind, params, status, result, error, created_at, started_at, finished_at";
+
+fn job_from_row(row: &Row) -> rusqlite::Result<Job> {
+    Ok(Job {
+        id: row.get("id")?,
+        kind: row.get("kind")?,
+        params: row.get::<_, Option<String>>("params")?.unwrap_or_default(),
+        status: row.get("status")?,
+        result: row.get::<_, Option<String>>("result")?.unwrap_or_default(),
+        error: row.get("error")?,
+        created_at: row.get::<_, Option<String>>("created_at")?.unwrap_or_default(),
+        started_at: row.get("started_at")?,
+        finished_at: row.get("finished_at")?,
+    })
+}
+
+/// Queue a job; returns its id
+pub fn enqueue_job(db_path: &str, kind: &str, params: Option<&Value>) -> Result<i64, String> {
+    let conn = init_database(db_path)
+        .map_err(|e| format!("Failed to init database: {}", e))?;
+    conn.execute(
+        "INSERT INTO jobs (kind, params) VALUES (?1, ?2)",
+        params![kind, params.map(Value::to_string)],
+    ).map_err(|e| format!("Failed to queue job: {}", e))?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Read a job (None if it doesn't exist)
+pub fn read_job(conn: &Connection, job_id: i64) -> rusqlite::Result<Option<Job>> {
+    conn.query_row(
+        &format!("SELECT {} FROM jobs WHERE id = ?1", JOB_COLUMNS),
+        params![job_id],
+        job_from_row,
+    ).optional()
+}
+
+pub fn get_job(db_path: &str, job_id: i64) -> Result<Option<Job>, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    read_job(&conn, job_id).map_err(|e| format!("Failed to read job: {}", e))
+}
+
+/// Jobs, newest first, optionally only those with the given status
+pub fn list_jobs(db_path: &str, status: Option<&str>) -> Result<Vec<Job>, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM jobs WHERE ?1 IS NULL OR status = ?1 ORDER BY id DESC", JOB_COLUMNS
+    )).map_err(|e| format!("Failed to query jobs: {}", e))?;
+    
+    let jobs = stmt.query_map(params![status], job_from_row)
+        .map_err(|e| format!("Failed to query jobs: {}", e))?
+        .filter_map(|j| j.ok())
+        .collect();
+    Ok(jobs)
+}
+
+/// Cancel a queued job. A running job is marked cancelled and its result discarded when
+/// it returns; handlers can poll `is_job_cancelled` to stop early. Returns false if the
+/// job doesn't exist or has already finished.
+pub fn cancel_job(db_path: &str, job_id: i64) -> Result<bool, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let updated = conn.execute(
+        "UPDATE jobs SET status = ?1, finished_at = CURRENT_TIMESTAMP
+         WHERE id = ?2 AND status IN (?3, ?4)",
+        params![JOB_CANCELLED, job_id, JOB_QUEUED, JOB_RUNNING],
+    ).map_err(|e| format!("Failed to cancel job: {}", e))?;
+    Ok(updated > 0)
+}
+
+pub fn is_job_cancelled(db_path: &str, job_id: i64) -> Result<bool, String> {
+    Ok(get_job(db_path, job_id)?.is_some_and(|j| j.status == JOB_CANCELLED))
+}
+
+/// Mark the oldest queued job as running and return it; None when the queue is empty.
+/// The status check in the UPDATE keeps two runners from claiming the same job.
+fn claim_next_job(conn: &Connection) -> rusqlite::Result<Option<Job>> {
+    loop {
+        let next: Option<i64> = conn.query_row(
+            "SELECT id FROM jobs WHERE status = ?1 ORDER BY id LIMIT 1",
+            params![JOB_QUEUED],
+            |row| row.get(0),
+        ).optional()?;
+        let Some(job_id) = next else {
+            return Ok(None);
+        };
+        
+        let claimed = conn.execute(
+            "UPDATE jobs SET status = ?1, started_at = CURRENT_TIMESTAMP WHERE id = ?2 AND status = ?3",
+            params![JOB_RUNNING, job_id, JOB_QUEUED],
+        )?;
+        if claimed > 0 {
+            return read_job(conn, job_id);
+        }
+    }
+}
+
+/// Fail jobs left running past the lease by a runner that crashed or was killed, so
+/// they don't stay "running" forever. Returns how many were failed.
+fn fail_stale_jobs(conn: &Connection) -> rusqlite::Result<usize> {
+    conn.execute(
+        "UPDATE jobs SET status = ?1, error = ?2, finished_at = CURRENT_TIMESTAMP
+         WHERE status = ?3 AND started_at < datetime('now', ?4)",
+        params![
+            JOB_FAILED,
+            "The runner stopped before the job finished",
+            JOB_RUNNING,
+            format!("-{} minutes", JOB_LEASE_MINUTES),
+        ],
+    )
+}
+
+/// Record the outcome of a job unless it was cancelled while running
+fn finish_job(conn: &Connection, job_id: i64, outcome: Result<Value, String>) -> rusqlite::Result<()> {
+    let (status, result, error) = match outcome {
+        Ok(value) => (JOB_DONE, Some(value.to_string()), None),
+        Err(e) => (JOB_FAILED, None, Some(e)),
+    };
+    conn.execute(
+        "UPDATE jobs SET status = ?1, result = ?2, error = ?3, finished_at = CURRENT_TIMESTAMP
+         WHERE id = ?4 AND status = ?5",
+        params![status, result, error, job_id, JOB_RUNNING],
+    )?;
+    Ok(())
+}
+
+/// Run a job of a built-in kind
+fn run_builtin(
+    py: Python<'_>,
+    db_path: &str,
+    job: &Job,
+    params: &Value,
+    handlers: Option<&Bound<'_, PyDict>>,
+) -> PyResult<Result<Value, String>> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+    
+    Ok(match job.kind.as_str() {
+        "reindex" => conn.execute_batch("REINDEX; ANALYZE;")
+            .and_then(|_| rebuild_index(&conn))
+            .map(|_| Value::Null)
+            .map_err(|e| format!("Reindex failed: {}", e)),
+        "vacuum" => conn.execute_batch("VACUUM;")
+            .map(|_| Value::Null)
+            .map_err(|e| format!("Vacuum failed: {}", e)),
+        "enrichment" => {
+            // The lookup is the "enrichment" handler; params may name an existing
+            // enrichment job to resume, otherwise one is created for `category`
+            let Some(lookup) = handlers.map(|h| h.get_item("enrichment")).transpose()?.flatten() else {
+                return Ok(Err("No 'enrichment' handler given for the lookup".to_string()));
+            };
+            let enrichment_id = match params.get("enrichment_job_id").and_then(Value::as_i64) {
+                Some(id) => id,
+                None => match create_enrichment_job(db_path, params.get("category").and_then(Value::as_str)) {
+                    Ok(id) => id,
+                    Err(e) => return Ok(Err(e)),
+                },
+            };
+            let progress = run_enrichment_job(py, db_path, enrichment_id, &lookup, &EnrichmentPolicy::default(), None, false)?;
+            Ok(serde_json::json!({
+                "enrichment_job_id": progress.job_id,
+                "done": progress.done,
+                "failed": progress.failed,
+                "pending": progress.pending,
+            }))
+        }
+        other => Err(format!("Unknown job kind '{}'", other)),
+    })
+}
+
+/// Run queued jobs oldest first until the queue is empty (or `max_jobs` have run).
+/// `handlers` maps job kinds to Python callables taking the params dict; their return
+/// value becomes the job result and an exception fails the job. Jobs still running after
+/// `JOB_LEASE_MINUTES` are failed first. Returns the jobs run.
+pub fn run_jobs(
+    py: Python<'_>,
+    db_path: &str,
+    handlers: Option<&Bound<'_, PyDict>>,
+    max_jobs: Option<usize>,
+) -> PyResult<Vec<Job>> {
+    let db_err = |e: rusqlite::Error| pyo3::exceptions::PyRuntimeError::new_err(format!("Job queue database error: {}", e));
+    let conn = init_database(db_path).map_err(db_err)?;
+    fail_stale_jobs(&conn).map_err(db_err)?;
+    let mut ran = Vec::new();
+    
+    while max_jobs.is_none_or(|max| ran.len() < max) {
+        let Some(job) = claim_next_job(&conn).map_err(db_err)? else {
+            break;
+        };
+        
+        let params: Value = if job.params.is_empty() {
+            Value::Object(Default::default())
+        } else {
+            serde_json::from_str(&job.params).unwrap_or(Value::Null)
+        };
+        let handler = handlers.map(|h| h.get_item(job.kind.as_str())).transpose()?.flatten();
+        
+        let outcome = match handler {
+            Some(handler) if !BUILTIN_JOB_KINDS.contains(&job.kind.as_str()) => {
+                let args = json_to_py(py, &params)?;
+                match handler.call1((args,)) {
+                    Ok(result) => py_to_json(&result).map_err(|e| e.to_string()),
+                    Err(e) if e.is_instance_of::<pyo3::exceptions::PyKeyboardInterrupt>(py) => {
+                        finish_job(&conn, job.id, Err("Interrupted".to_string())).map_err(db_err)?;
+                        return Err(e);
+                    }
+                    Err(e) => Err(e.to_string()),
+                }
+            }
+            _ => match run_builtin(py, db_path, &job, &params, handlers) {
+                Ok(outcome) => outcome,
+                Err(e) => {
+                    finish_job(&conn, job.id, Err(e.to_string())).map_err(db_err)?;
+                    return Err(e);
+                }
+            },
+        };
+        
+        finish_job(&conn, job.id, outcome).map_err(db_err)?;
+        if let Some(job) = read_job(&conn, job.id).map_err(db_err)? {
+            ran.push(job);
+        }
+    }
+    
+    Ok(ran)
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "enqueue_job", signature = (db_path, kind, params=None))]
+pub fn py_enqueue_job(db_path: &str, kind: &str, params: Option<&Bound<'_, PyDict>>) -> PyResult<i64> {
+    let params = params.map(|p| py_to_json(p.as_any())).transpose()?;
+    enqueue_job(db_path, kind, params.as_ref())
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "get_job")]
+pub fn py_get_job(db_path: &str, job_id: i64) -> PyResult<Option<Job>> {
+    get_job(db_path, job_id)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "list_jobs", signature = (db_path, status=None))]
+pub fn py_list_jobs(db_path: &str, status: Option<&str>) -> PyResult<Vec<Job>> {
+    list_jobs(db_path, status)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "cancel_job")]
+pub fn py_cancel_job(db_path: &str, job_id: i64) -> PyResult<bool> {
+    cancel_job(db_path, job_id)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "is_job_cancelled")]
+pub fn py_is_job_cancelled(db_path: &str, job_id: i64) -> PyResult<bool> {
+    is_job_cancelled(db_path, job_id)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "run_jobs", signature = (db_path, handlers=None, max_jobs=None))]
+pub fn py_run_jobs(py: Python<'_>, db_path: &str, handlers: Option<&Bound<'_, PyDict>>, max_jobs: Option<usize>) -> PyResult<Vec<Job>> {
+    run_jobs(py, db_path, handlers, max_jobs)
+}
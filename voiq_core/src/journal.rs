@@ -0,0 +1,211 @@
+//! Journal of import operations.
+//!
+//! An import writes a `started` entry before adding any words and tags every row it adds
+//! with the entry id; the entry is marked `committed` once the import finishes. Entries
+//! still `started` on the next launch belong to imports that died midway, and
+//! `recover_incomplete_operations` removes the words they left behind. A committed
+//! import can be taken back with `undo_import`, which removes the words it added.
+
+use pyo3::prelude::*;
+use rusqlite::{Connection, OptionalExtension, params};
+use crate::db::{delete_word, init_database, open_database};
+
+pub const OP_STARTED: &str = "started";
+pub const OP_COMMITTED: &str = "committed";
+pub const OP_ROLLED_BACK: &str = "rolled_back";
+pub const OP_UNDONE: &str = "undone";
+
+/// Operation recorded by spreadsheet and CSV imports and by reimports of edited files
+pub const OP_IMPORT: &str = "import";
+
+const ENTRY_COLUMNS: &str = "id, operation, category, source, status, started_at, row_count";
+
+/// An import recorded in the journal
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct JournalEntry {
+    #[pyo3(get)]
+    pub id: i64,
+    #[pyo3(get)]
+    pub operation: String,
+    #[pyo3(get)]
+    pub category: Option<String>,
+    /// File the import read from
+    #[pyo3(get)]
+    pub source: Option<String>,
+    #[pyo3(get)]
+    pub status: String,
+    #[pyo3(get)]
+    pub started_at: String,
+    /// Rows the operation wrote (None until it commits)
+    #[pyo3(get)]
+    pub row_count: Option<usize>,
+    /// Words removed while rolling the operation back
+    #[pyo3(get)]
+    pub words_removed: usize,
+}
+
+#[pymethods]
+impl JournalEntry {
+    fn __repr__(&self) -> String {
+        format!("JournalEntry(id={}, operation='{}', status='{}')", self.id, self.operation, self.status)
+    }
+}
+
+/// Record the start of an operation; returns the journal id to tag its rows with
+pub fn begin_operation(conn: &Connection, operation: &str, category: Option<&str>, source: Option<&str>) -> rusqlite::Result<i64> {
+    conn.execute(
+        "INSERT INTO import_journal (operation, category, source, status) VALUES (?1, ?2, ?3, ?4)",
+        params![operation, category, source, OP_STARTED],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Mark an operation as finished after writing `row_count` rows
+pub fn commit_operation(conn: &Connection, journal_id: i64, row_count: usize) -> rusqlite::Result<()> {
+    conn.execute(
+        "UPDATE import_journal SET status = ?1, finished_at = CURRENT_TIMESTAMP, row_count = ?2 WHERE id = ?3",
+        params![OP_COMMITTED, row_count as i64, journal_id],
+    )?;
+    Ok(())
+}
+
+fn read_entry(row: &rusqlite::Row) -> rusqlite::Result<JournalEntry> {
+    Ok(JournalEntry {
+        id: row.get(0)?,
+        operation: row.get(1)?,
+        category: row.get(2)?,
+        source: row.get(3)?,
+        status: row.get(4)?,
+        started_at: row.get::<_, Option<String>>(5)?.unwrap_or_default(),
+        row_count: row.get::<_, Option<i64>>(6)?.map(|n| n as usize),
+        words_removed: 0,
+    })
+}
+
+/// Permanently delete the words an operation added, with their progress. Returns how
+/// many were deleted.
+fn remove_added_words(conn: &Connection, journal_id: i64) -> Result<usize, String> {
+    let word_ids: Vec<i64> = {
+        let mut stmt = conn.prepare("SELECT id FROM vocabulary WHERE import_id = ?1")
+            .map_err(|e| format!("Failed to find imported words: {}", e))?;
+        let rows = stmt.query_map(params![journal_id], |row| row.get(0))
+            .map_err(|e| format!("Failed to find imported words: {}", e))?;
+        rows.filter_map(|r| r.ok()).collect()
+    };
+    for word_id in &word_ids {
+        delete_word(conn, *word_id)
+            .map_err(|e| format!("Failed to remove word {}: {}", word_id, e))?;
+    }
+    Ok(word_ids.len())
+}
+
+/// Imports in the journal, newest first
+pub fn get_imports(db_path: &str) -> Result<Vec<JournalEntry>, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM import_journal WHERE operation = ?1 ORDER BY id DESC", ENTRY_COLUMNS,
+    )).map_err(|e| format!("Failed to read journal: {}", e))?;
+    let entries = stmt.query_map(params![OP_IMPORT], read_entry)
+        .map_err(|e| format!("Failed to read journal: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(entries)
+}
+
+/// Undo a committed import (the latest one without `batch_id`) by deleting the words it
+/// added, together with their progress. Words it updated in upsert mode keep the
+/// imported values. Returns the undone entry.
+pub fn undo_import(db_path: &str, batch_id: Option<i64>) -> Result<JournalEntry, String> {
+    let mut conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let tx = conn.transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    
+    let entry = match batch_id {
+        Some(id) => tx.query_row(
+            &format!("SELECT {} FROM import_journal WHERE id = ?1 AND operation = ?2", ENTRY_COLUMNS),
+            params![id, OP_IMPORT],
+            read_entry,
+        ),
+        None => tx.query_row(
+            &format!("SELECT {} FROM import_journal WHERE operation = ?1 AND status = ?2 ORDER BY id DESC LIMIT 1", ENTRY_COLUMNS),
+            params![OP_IMPORT, OP_COMMITTED],
+            read_entry,
+        ),
+    }.optional().map_err(|e| format!("Failed to read journal: {}", e))?;
+    let mut entry = match (entry, batch_id) {
+        (Some(entry), _) => entry,
+        (None, Some(id)) => return Err(format!("Import {} not found", id)),
+        (None, None) => return Err("No import to undo".to_string()),
+    };
+    if entry.status != OP_COMMITTED {
+        return Err(format!("Import {} can't be undone (status '{}')", entry.id, entry.status));
+    }
+    
+    entry.words_removed = remove_added_words(&tx, entry.id)?;
+    tx.execute(
+        "UPDATE import_journal SET status = ?1, finished_at = CURRENT_TIMESTAMP WHERE id = ?2",
+        params![OP_UNDONE, entry.id],
+    ).map_err(|e| format!("Failed to update journal: {}", e))?;
+    tx.commit().map_err(|e| format!("Failed to commit undo: {}", e))?;
+    
+    entry.status = OP_UNDONE.to_string();
+    Ok(entry)
+}
+
+/// Roll back every operation that started but never committed, deleting the words it
+/// added. Call once at startup, before any import runs. Returns the rolled back entries.
+pub fn recover_incomplete_operations(db_path: &str) -> Result<Vec<JournalEntry>, String> {
+    let mut conn = init_database(db_path)
+        .map_err(|e| format!("Failed to init database: {}", e))?;
+    
+    let mut entries: Vec<JournalEntry> = {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM import_journal WHERE status = ?1 ORDER BY id", ENTRY_COLUMNS,
+        )).map_err(|e| format!("Failed to read journal: {}", e))?;
+        let rows = stmt.query_map(params![OP_STARTED], read_entry)
+            .map_err(|e| format!("Failed to read journal: {}", e))?;
+        rows.filter_map(|r| r.ok()).collect()
+    };
+    
+    for entry in &mut entries {
+        let tx = conn.transaction()
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+        let words_removed = remove_added_words(&tx, entry.id)?;
+        tx.execute(
+            "UPDATE import_journal SET status = ?1, finished_at = CURRENT_TIMESTAMP WHERE id = ?2",
+            params![OP_ROLLED_BACK, entry.id],
+        ).map_err(|e| format!("Failed to update journal: {}", e))?;
+        tx.commit().map_err(|e| format!("Failed to commit rollback: {}", e))?;
+        
+        entry.status = OP_ROLLED_BACK.to_string();
+        entry.words_removed = words_removed;
+    }
+    
+    Ok(entries)
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "recover_incomplete_operations")]
+pub fn py_recover_incomplete_operations(db_path: &str) -> PyResult<Vec<JournalEntry>> {
+    recover_incomplete_operations(db_path)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "get_imports")]
+pub fn py_get_imports(db_path: &str) -> PyResult<Vec<JournalEntry>> {
+    get_imports(db_path)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "undo_import", signature = (db_path, batch_id=None))]
+pub fn py_undo_import(db_path: &str, batch_id: Option<i64>) -> PyResult<JournalEntry> {
+    undo_import(db_path, batch_id)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
@@ -0,0 +1,140 @@
+//! Export of the whole database to one JSON file, for keeping it under version control
+//! or moving it to other tools. Words and attempts are written in id order, so exporting
+//! an unchanged database gives the same file apart from `exported_at`.
+
+use pyo3::prelude::*;
+use rusqlite::Connection;
+use serde::Serialize;
+use crate::db::{get_all_words, get_categories, latest_schema_version, open_database, Word};
+use crate::deck::{read_deck_metadata, DeckMetadata};
+
+/// Identifies a database export file
+pub const EXPORT_FORMAT: &str = "voiq-export";
+pub const EXPORT_FORMAT_VERSION: u32 = 1;
+
+/// A category with its attribution
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportCategory {
+    pub name: String,
+    pub word_count: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<DeckMetadata>,
+}
+
+/// An attempt as stored in an export
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportAttempt {
+    pub id: i64,
+    pub word_id: Option<i64>,
+    pub mode: String,
+    pub question_type: String,
+    pub is_correct: bool,
+    pub user_answer: Option<String>,
+    pub expected_answer: Option<String>,
+    pub time_taken_ms: Option<i64>,
+    pub client_context: Option<String>,
+    pub session_id: Option<String>,
+    pub first_answer: Option<String>,
+    pub profile_id: Option<i64>,
+    pub attempted_at: Option<String>,
+}
+
+/// Export file contents
+#[derive(Debug, Clone, Serialize)]
+pub struct DatabaseExport {
+    pub format: String,
+    pub format_version: u32,
+    pub schema_version: i64,
+    /// UTC "YYYY-MM-DD HH:MM:SS"
+    pub exported_at: String,
+    pub categories: Vec<ExportCategory>,
+    pub vocabulary: Vec<Word>,
+    /// Left out unless progress was requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attempts: Option<Vec<ExportAttempt>>,
+}
+
+/// All attempts in id order
+fn read_attempts(conn: &Connection) -> rusqlite::Result<Vec<ExportAttempt>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, word_id, mode, question_type, is_correct, user_answer, expected_answer, time_taken_ms,
+                client_context, session_id, first_answer, profile_id, attempted_at
+         FROM attempts ORDER BY id"
+    )?;
+    let attempts = stmt.query_map([], |row| Ok(ExportAttempt {
+        id: row.get(0)?,
+        word_id: row.get(1)?,
+        mode: row.get(2)?,
+        question_type: row.get(3)?,
+        is_correct: row.get(4)?,
+        user_answer: row.get(5)?,
+        expected_answer: row.get(6)?,
+        time_taken_ms: row.get(7)?,
+        client_context: row.get(8)?,
+        session_id: row.get(9)?,
+        first_answer: row.get(10)?,
+        profile_id: row.get(11)?,
+        attempted_at: row.get(12)?,
+    }))?
+    .filter_map(|a| a.ok())
+    .collect();
+    Ok(attempts)
+}
+
+/// Build the export of a database; words in the trash are left out
+pub fn build_database_export(conn: &Connection, include_progress: bool) -> Result<DatabaseExport, String> {
+    let mut vocabulary = get_all_words(conn)
+        .map_err(|e| format!("Failed to get all words: {}", e))?;
+    vocabulary.sort_by_key(|w| w.id);
+    
+    let categories = get_categories(conn)
+        .map_err(|e| format!("Failed to get categories: {}", e))?
+        .into_iter()
+        .map(|c| Ok(ExportCategory {
+            metadata: read_deck_metadata(conn, &c.name)
+                .map_err(|e| format!("Failed to read deck metadata: {}", e))?,
+            name: c.name,
+            word_count: c.word_count,
+        }))
+        .collect::<Result<Vec<_>, String>>()?;
+    
+    let attempts = if include_progress {
+        Some(read_attempts(conn).map_err(|e| format!("Failed to read attempts: {}", e))?)
+    } else {
+        None
+    };
+    
+    Ok(DatabaseExport {
+        format: EXPORT_FORMAT.to_string(),
+        format_version: EXPORT_FORMAT_VERSION,
+        schema_version: latest_schema_version(),
+        exported_at: chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        categories,
+        vocabulary,
+        attempts,
+    })
+}
+
+/// Write vocabulary and categories (and all attempts with `include_progress`) to a JSON
+/// file. Returns the number of words written.
+pub fn export_json(db_path: &str, dest_path: &str, include_progress: bool) -> Result<usize, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    
+    let export = build_database_export(&conn, include_progress)?;
+    let json = serde_json::to_string_pretty(&export)
+        .map_err(|e| format!("Failed to encode export: {}", e))?;
+    std::fs::write(dest_path, json)
+        .map_err(|e| format!("Failed to write file: {}", e))?;
+    
+    Ok(export.vocabulary.len())
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "export_json", signature = (db_path, dest_path, include_progress=false))]
+pub fn py_export_json(db_path: &str, dest_path: &str, include_progress: bool) -> PyResult<usize> {
+    export_json(db_path, dest_path, include_progress)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
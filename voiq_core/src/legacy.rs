@@ -0,0 +1,220 @@
+//! Import of progress files written by the Python app before it stored attempts in SQLite.
+//!
+//! Those files hold a JSON list of attempt records, either at the top level or under an
+//! "attempts", "history" or "progress" key. Field names changed between releases, so each
+//! field is looked up under all names it went by. Naive timestamps were written in local
+//! time and are converted to UTC like every other attempt. Each word's schedule is then
+//! rebuilt from all of its answers, old and new, in time order, and records already in
+//! the database are skipped, so importing a file twice is harmless.
+
+use pyo3::prelude::*;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::Value;
+use crate::db::open_database;
+use crate::progress::{insert_unscheduled_attempt, AttemptRecord};
+use crate::srs::rebuild_state;
+
+/// Mode and question type of records that don't name one
+const LEGACY_MODE: &str = "legacy";
+
+/// Outcome of `import_legacy_progress`
+#[pyclass]
+#[derive(Debug, Clone, Default)]
+pub struct LegacyImportReport {
+    #[pyo3(get)]
+    pub imported: usize,
+    /// Records already in the database
+    #[pyo3(get)]
+    pub duplicates: usize,
+    /// Records without a usable outcome or timestamp
+    #[pyo3(get)]
+    pub invalid: usize,
+    /// Words of records that match no word in the database, without repeats
+    #[pyo3(get)]
+    pub unknown_words: Vec<String>,
+}
+
+#[pymethods]
+impl LegacyImportReport {
+    fn __repr__(&self) -> String {
+        format!("LegacyImportReport(imported={}, duplicates={}, invalid={}, unknown_words={})",
+                self.imported, self.duplicates, self.invalid, self.unknown_words.len())
+    }
+}
+
+/// The first of `names` present in a record
+fn field<'a>(record: &'a Value, names: &[&str]) -> Option<&'a Value> {
+    names.iter().find_map(|name| record.get(*name).filter(|v| !v.is_null()))
+}
+
+fn text_field(record: &Value, names: &[&str]) -> Option<String> {
+    match field(record, names)? {
+        Value::String(s) => Some(s.trim().to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Outcome from a boolean, 0/1 or a word like "correct" or "wrong"
+fn parse_outcome(value: &Value) -> Option<bool> {
+    match value {
+        Value::Bool(b) => Some(*b),
+        Value::Number(n) => n.as_i64().filter(|n| *n == 0 || *n == 1).map(|n| n == 1),
+        Value::String(s) => match s.trim().to_lowercase().as_str() {
+            "true" | "1" | "yes" | "correct" | "right" | "pass" => Some(true),
+            "false" | "0" | "no" | "incorrect" | "wrong" | "fail" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// UTC "YYYY-MM-DD HH:MM:SS" from a Unix time (seconds or milliseconds), an RFC 3339
+/// string, or a naive local date or datetime
+fn parse_timestamp(value: &Value) -> Option<String> {
+    let utc = match value {
+        Value::Number(n) => {
+            let n = n.as_f64()?;
+            // Seconds stay below 1e11 until the year 5138
+            let ms = if n.abs() < 1e11 { n * 1000.0 } else { n };
+            chrono::DateTime::from_timestamp_millis(ms as i64)?.naive_utc()
+        }
+        Value::String(s) => {
+            let s = s.trim();
+            if let Ok(at) = chrono::DateTime::parse_from_rfc3339(s) {
+                at.naive_utc()
+            } else {
+                let naive = ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M"].iter()
+                    .find_map(|format| chrono::NaiveDateTime::parse_from_str(s, format).ok())
+                    .or_else(|| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()?.and_hms_opt(0, 0, 0))?;
+                naive.and_local_timezone(chrono::Local).earliest()?.naive_utc()
+            }
+        }
+        _ => return None,
+    };
+    Some(utc.format("%Y-%m-%d %H:%M:%S").to_string())
+}
+
+/// The list of records in a legacy file
+fn legacy_records(json: Value) -> Result<Vec<Value>, String> {
+    match json {
+        Value::Array(records) => Ok(records),
+        Value::Object(mut object) => ["attempts", "history", "progress"].iter()
+            .find_map(|key| match object.remove(*key) {
+                Some(Value::Array(records)) => Some(records),
+                _ => None,
+            })
+            .ok_or_else(|| "No attempts found in progress file".to_string()),
+        _ => Err("Progress file holds neither a list nor an object".to_string()),
+    }
+}
+
+/// Word a record refers to: its `word_id` if that word exists, else the oldest word with
+/// the same text (ignoring case), within the record's category if it names one
+fn match_word(conn: &Connection, record: &Value) -> rusqlite::Result<Option<i64>> {
+    if let Some(word_id) = field(record, &["word_id"]).and_then(Value::as_i64) {
+        let exists: Option<i64> = conn.query_row(
+            "SELECT id FROM vocabulary WHERE id = ?1 AND deleted_at IS NULL", params![word_id], |row| row.get(0),
+        ).optional()?;
+        if exists.is_some() {
+            return Ok(exists);
+        }
+    }
+    let Some(word) = text_field(record, &["word", "vocabulary", "term"]).filter(|w| !w.is_empty()) else {
+        return Ok(None);
+    };
+    let category = text_field(record, &["category", "deck"]);
+    conn.query_row(
+        "SELECT id FROM vocabulary
+         WHERE LOWER(TRIM(word)) = LOWER(?1) AND deleted_at IS NULL AND (?2 IS NULL OR category = ?2)
+         ORDER BY id LIMIT 1",
+        params![word, category],
+        |row| row.get(0),
+    ).optional()
+}
+
+/// Add the attempts in a legacy JSON progress file to the database, optionally for
+/// `profile_id`, in one transaction
+pub fn import_legacy_progress(json_path: &str, db_path: &str, profile_id: Option<i64>) -> Result<LegacyImportReport, String> {
+    let text = std::fs::read_to_string(json_path)
+        .map_err(|e| format!("Failed to read progress file: {}", e))?;
+    let json: Value = serde_json::from_str(&text)
+        .map_err(|e| format!("Invalid progress file: {}", e))?;
+    let records = legacy_records(json)?;
+    
+    let mut conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let tx = conn.transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    
+    let mut report = LegacyImportReport::default();
+    let mut attempts = Vec::new();
+    for record in &records {
+        let outcome = field(record, &["is_correct", "correct", "result"]).and_then(parse_outcome);
+        let attempted_at = field(record, &["attempted_at", "timestamp", "date", "time"]).and_then(parse_timestamp);
+        let (Some(is_correct), Some(attempted_at)) = (outcome, attempted_at) else {
+            report.invalid += 1;
+            continue;
+        };
+        let Some(word_id) = match_word(&tx, record).map_err(|e| format!("Failed to look up word: {}", e))? else {
+            let word = text_field(record, &["word", "vocabulary", "term"]).unwrap_or_default();
+            if !report.unknown_words.contains(&word) {
+                report.unknown_words.push(word);
+            }
+            continue;
+        };
+        attempts.push(AttemptRecord {
+            word_id,
+            mode: text_field(record, &["mode", "quiz_mode"]).unwrap_or_else(|| LEGACY_MODE.to_string()),
+            question_type: text_field(record, &["question_type", "type"]).unwrap_or_else(|| LEGACY_MODE.to_string()),
+            is_correct,
+            user_answer: text_field(record, &["user_answer", "answer", "response"]).unwrap_or_default(),
+            expected_answer: text_field(record, &["expected_answer", "expected", "correct_answer"]).unwrap_or_default(),
+            time_taken_ms: field(record, &["time_taken_ms"]).and_then(Value::as_i64),
+            client_context: None,
+            session_id: text_field(record, &["session_id", "session"]),
+            first_answer: None,
+            profile_id,
+            attempted_at: Some(attempted_at),
+        });
+    }
+    attempts.sort_by(|a, b| a.attempted_at.cmp(&b.attempted_at));
+    
+    let mut touched = Vec::new();
+    for attempt in &attempts {
+        let exists: bool = tx.query_row(
+            "SELECT EXISTS(SELECT 1 FROM attempts WHERE word_id = ?1 AND attempted_at = ?2 AND is_correct = ?3)",
+            params![attempt.word_id, attempt.attempted_at, attempt.is_correct],
+            |row| row.get(0),
+        ).map_err(|e| format!("Failed to check attempts: {}", e))?;
+        if exists {
+            report.duplicates += 1;
+            continue;
+        }
+        insert_unscheduled_attempt(&tx, attempt)
+            .map_err(|e| format!("Failed to save attempt: {}", e))?;
+        touched.push(attempt.word_id);
+        report.imported += 1;
+    }
+    
+    // Old answers can predate ones already stored, so each word's schedule is replayed
+    // from all of its answers in time order
+    touched.sort_unstable();
+    touched.dedup();
+    for word_id in touched {
+        rebuild_state(&tx, word_id)
+            .map_err(|e| format!("Failed to reschedule word {}: {}", word_id, e))?;
+    }
+    
+    tx.commit().map_err(|e| format!("Failed to commit attempts: {}", e))?;
+    Ok(report)
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "import_legacy_progress", signature = (json_path, db_path, profile_id=None))]
+pub fn py_import_legacy_progress(json_path: &str, db_path: &str, profile_id: Option<i64>) -> PyResult<LegacyImportReport> {
+    import_legacy_progress(json_path, db_path, profile_id)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
@@ -3,53 +3,352 @@
 //! Provides SQLite database operations, Excel parsing, fuzzy matching, and MCQ generation.
 
 mod db;
+mod collation;
 mod excel;
+mod watch;
+mod spreadsheet;
+mod roundtrip;
+mod anki;
 mod fuzzy;
+mod translit;
+mod ruby;
+mod tts;
 mod questions;
+mod question_bank;
+mod cloze;
+mod content_filter;
+mod deck;
+mod json_export;
+mod enrich;
+mod jobs;
+mod journal;
+mod quiz;
+mod audit;
+mod composer;
+mod feedback;
+mod norms;
+mod sessions;
+mod charts;
+mod report;
 mod progress;
+mod attempt_buffer;
+mod profiles;
+mod legacy;
+mod srs;
+mod cram;
+mod simulate;
+mod sample;
+mod schema;
+mod backup;
+mod maintenance;
+mod views;
+mod fts;
+mod history;
+mod validation;
+mod duplicates;
 
 use pyo3::prelude::*;
 
 // Re-export structs for Python
-pub use db::{Word, CategoryInfo, init_database, load_vocabulary, get_words, get_word_by_id, get_all_words, get_categories, delete_category};
-pub use excel::parse_excel;
-pub use fuzzy::{check_match, MatchResult};
-pub use questions::{generate_mcq, MCQQuestion};
-pub use progress::{save_attempt, get_failed_words, get_stats, AttemptStats};
+pub use db::{Word, CategoryInfo, init_database, open_database, open_database_with_flags, MEMORY_DATABASE, is_memory_database, close_memory_database, set_passphrase, configure_database, DatabaseOptions, JOURNAL_MODES, SYNCHRONOUS_LEVELS, run_migrations, schema_version, latest_schema_version, add_column, Migration, MIGRATIONS, get_setting, set_setting, get_default_category, set_default_category, FALLBACK_CATEGORY, load_vocabulary, load_vocabulary_for_import, LoadMode, enable_unique_words, UNIQUE_WORD_INDEX, NORMALIZED_WORD_INDEX, UniquenessMode, uniqueness_mode, set_uniqueness_mode, fill_word_keys, get_words, get_words_page, PAGE_ORDERS, get_initials, search_words, SEARCH_FIELDS, get_word_by_id, get_words_by_ids, get_all_words, get_recently_added_words, add_word, add_word_upsert, add_words, AddWordResult, update_word, WordUpdate, set_note, set_difficulty, DIFFICULTY_LEVELS, set_media, set_frequency_ranks, delete_word, delete_words, pin_word, unpin_word, get_pinned_words, star_word, unstar_word, ignore_words, unignore_words, get_ignored_words, tag_words, untag_words, delete_tag, get_tags, get_word_tags, TagInfo, add_example, get_examples, Example, IMPORT_EXAMPLE_SOURCE, soft_delete_words, soft_delete_category, restore_words, restore_category, get_trash, purge_trash, TrashedWord, get_categories, category_exists, rename_category, merge_categories, MergeResult, move_words_to_category, delete_category, get_extra, set_extra, find_words_by_field};
+pub use collation::{unicode_compare, register_collation, base_initial, word_key, UNICODE_COLLATION};
+pub use excel::{parse_excel, load_frequency_ranks, import_folder, ImportOptions, ImportReport, CategorySource, SUPPORTED_EXTENSIONS};
+pub use watch::FolderWatcher;
+pub use spreadsheet::{write_import_template, SheetFormat, TEMPLATE_COLUMNS};
+pub use roundtrip::{export_for_editing, reimport_edited_file, ReimportDiff, WordChange, ID_COLUMN};
+pub use anki::export_to_anki;
+pub use fuzzy::{check_match, FeedbackStyle, MatchResult};
+pub use translit::{transliterate, fold_script};
+pub use ruby::{parse_ruby, get_furigana, RubySegment};
+pub use tts::split_for_tts;
+pub use questions::{generate_mcq, list_question_types, question_generator, DistractorScope, MCQQuestion, QuestionGenerator, QuestionTypeInfo};
+pub use question_bank::{import_questions, export_questions, CustomQuestion};
+pub use cloze::{create_cloze_cards, ClozeImport};
+pub use enrich::{create_enrichment_job, run_enrichment_job, get_enrichment_progress, cancel_enrichment_job, EnrichmentPolicy, EnrichmentProgress};
+pub use jobs::{enqueue_job, get_job, list_jobs, cancel_job, is_job_cancelled, run_jobs, Job};
+pub use journal::{begin_operation, commit_operation, get_imports, recover_incomplete_operations, undo_import, JournalEntry};
+pub use srs::{get_due_words, record_review, rebuild_state, copy_known_progress, MASTERED_INTERVAL_DAYS, snooze_word, unsnooze_word, get_snoozed_words, SnoozedWord, SrsParams, SrsState, get_deck_srs_params, set_deck_srs_params, learning_phase, learning_phases, LearningPhase, get_recognitions_to_graduate, set_recognitions_to_graduate, RECOGNITION_MODE, DEFAULT_RECOGNITIONS_TO_GRADUATE};
+pub use cram::{build_cram_queue, recall_probability, fold_cram_results, CramItem, CramFold, CRAM_MODE};
+pub use simulate::{simulate, simulate_deck, ForgettingModel, SimulationConfig, SimulationResult, SimulatedDay};
+pub use sample::{create_sample_database, AttemptsProfile, SampleDatabase, SAMPLE_DECKS};
+pub use views::{create_views, refresh_views, VIEWS};
+pub use validation::{validate_word, find_duplicate, ValidationIssue, ValidationError};
+pub use duplicates::{find_duplicates, merge_duplicates, group_duplicates, diff_categories, diff_words, DuplicateGroup, DuplicateScope, CategoryDiff, SharedWord};
+pub use fts::{full_text_search, rebuild_index, SearchHit};
+pub use history::{get_word_history, WordHistoryEntry};
+pub use attempt_buffer::AttemptBuffer;
+pub use deck::{export_deck, import_deck, get_deck_metadata, set_deck_metadata, DeckMetadata};
+pub use json_export::{export_json, DatabaseExport};
+pub use content_filter::{scan_deck_for_flagged, set_content_filter, get_content_filter, ContentFilter, FilterMode, FlaggedEntry};
+pub use quiz::{build_quiz, requeue_missed, QuizOptions};
+pub use audit::{audit_generation, GenerationAudit};
+pub use feedback::rate_question;
+pub use norms::{compare_to_norms, NormComparison};
+pub use charts::{bar_chart_svg, line_chart_svg, render_accuracy_chart_svg, render_due_forecast_svg};
+pub use report::render_weekly_report_html;
+pub use sessions::{log_study_time, get_daily_study_time, get_study_time_by_activity, get_session_summary, DailyStudyTime, ActivityTime, SessionSummary};
+pub use progress::{save_attempt, get_failed_words, get_stats, get_words_with_progress, insert_attempt, insert_unscheduled_attempt, AttemptRecord, WordWithProgress, get_answer_change_stats, AttemptStats, AnswerChangeStats, StatsFilter, TimeCapping};
+pub use profiles::{create_profile, get_profiles, get_profile, rename_profile, delete_profile, Profile};
+pub use legacy::{import_legacy_progress, LegacyImportReport};
+pub use schema::{describe_schema, open_with_upgrade, SchemaInfo, TableInfo, ColumnInfo, UpgradeReport};
+pub use backup::{backup_database, restore_database};
+pub use maintenance::{check_capacity, maintain_database, check_database, CapacityLimits, CapacityWarning, MaintenanceReport, IntegrityReport, ACTION_VACUUM, ACTION_PURGE_TRASH, ACTION_ARCHIVE};
 
 /// VoIQ Core Python Module
 #[pymodule]
 fn voiq_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Database functions
     m.add_function(wrap_pyfunction!(db::py_init_database, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_set_passphrase, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_configure_database, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_close_memory_database, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_get_schema_version, m)?)?;
     m.add_function(wrap_pyfunction!(db::py_get_all_words, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_get_recently_added_words, m)?)?;
     m.add_function(wrap_pyfunction!(db::py_get_words_by_order, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_get_words_page, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_get_initials, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_search_words, m)?)?;
+    m.add_function(wrap_pyfunction!(fts::py_full_text_search, m)?)?;
+    m.add_function(wrap_pyfunction!(fts::py_rebuild_search_index, m)?)?;
+    m.add_function(wrap_pyfunction!(history::py_get_word_history, m)?)?;
     m.add_function(wrap_pyfunction!(db::py_get_word_by_id, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_get_words_by_ids, m)?)?;
     m.add_function(wrap_pyfunction!(db::py_add_word, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_add_word_upsert, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_add_words, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_enable_unique_words, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_set_uniqueness_mode, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_get_uniqueness_mode, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_update_word, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_set_note, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_set_difficulty, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_set_media, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_tag_words, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_untag_words, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_delete_tag, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_get_tags, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_get_word_tags, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_add_example, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_get_examples, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_soft_delete, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_restore, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_get_trash, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_purge_trash, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_ignore_words, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_unignore_words, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_get_ignored_words, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_pin_word, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_unpin_word, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_get_pinned_words, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_star_word, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_unstar_word, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_delete_word, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_delete_words, m)?)?;
     m.add_function(wrap_pyfunction!(db::py_get_categories, m)?)?;
     m.add_function(wrap_pyfunction!(db::py_delete_category, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_rename_category, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_merge_categories, m)?)?;
+    m.add_function(wrap_pyfunction!(duplicates::py_find_duplicates, m)?)?;
+    m.add_function(wrap_pyfunction!(duplicates::py_merge_duplicates, m)?)?;
+    m.add_function(wrap_pyfunction!(duplicates::py_diff_categories, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_move_words_to_category, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_get_default_category, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_set_default_category, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_get_extra, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_set_extra, m)?)?;
+    m.add_function(wrap_pyfunction!(db::py_get_words_by_field, m)?)?;
     
-    // File parsing (Excel and CSV)
+    // File parsing (Excel and CSV) and import recovery
     m.add_function(wrap_pyfunction!(excel::py_parse_excel, m)?)?;
     m.add_function(wrap_pyfunction!(excel::py_parse_csv, m)?)?;
+    m.add_function(wrap_pyfunction!(excel::py_import_folder, m)?)?;
+    m.add_function(wrap_pyfunction!(spreadsheet::py_write_import_template, m)?)?;
+    m.add_function(wrap_pyfunction!(roundtrip::py_export_for_editing, m)?)?;
+    m.add_function(wrap_pyfunction!(roundtrip::py_reimport_edited_file, m)?)?;
+    m.add_function(wrap_pyfunction!(anki::py_export_to_anki, m)?)?;
+    m.add_function(wrap_pyfunction!(excel::py_set_frequency_ranks_from_csv, m)?)?;
+    m.add_function(wrap_pyfunction!(journal::py_recover_incomplete_operations, m)?)?;
+    m.add_function(wrap_pyfunction!(journal::py_get_imports, m)?)?;
+    m.add_function(wrap_pyfunction!(journal::py_undo_import, m)?)?;
     
-    // Fuzzy matching
+    // Fuzzy matching and transliteration
     m.add_function(wrap_pyfunction!(fuzzy::py_check_match, m)?)?;
+    m.add_function(wrap_pyfunction!(translit::py_transliterate, m)?)?;
+    
+    // Ruby (furigana) readings
+    m.add_function(wrap_pyfunction!(ruby::py_parse_ruby, m)?)?;
+    m.add_function(wrap_pyfunction!(ruby::py_get_furigana, m)?)?;
+    
+    // Read-aloud
+    m.add_function(wrap_pyfunction!(tts::py_split_for_tts, m)?)?;
     
     // Question generation
     m.add_function(wrap_pyfunction!(questions::py_generate_mcq, m)?)?;
+    m.add_function(wrap_pyfunction!(questions::py_list_question_types, m)?)?;
+    
+    // Authored question bank
+    m.add_function(wrap_pyfunction!(question_bank::py_add_custom_question, m)?)?;
+    m.add_function(wrap_pyfunction!(question_bank::py_get_custom_questions, m)?)?;
+    m.add_function(wrap_pyfunction!(question_bank::py_delete_custom_question, m)?)?;
+    m.add_function(wrap_pyfunction!(question_bank::py_import_questions, m)?)?;
+    m.add_function(wrap_pyfunction!(question_bank::py_export_questions, m)?)?;
+    m.add_function(wrap_pyfunction!(cloze::py_create_cloze_cards, m)?)?;
+    
+    // Deck bundles and attribution
+    m.add_function(wrap_pyfunction!(deck::py_set_deck_metadata, m)?)?;
+    m.add_function(wrap_pyfunction!(deck::py_get_deck_metadata, m)?)?;
+    m.add_function(wrap_pyfunction!(deck::py_export_deck, m)?)?;
+    m.add_function(wrap_pyfunction!(deck::py_import_deck, m)?)?;
+    m.add_function(wrap_pyfunction!(json_export::py_export_json, m)?)?;
+    
+    // Dictionary enrichment
+    m.add_function(wrap_pyfunction!(enrich::py_create_enrichment_job, m)?)?;
+    m.add_function(wrap_pyfunction!(enrich::py_run_enrichment_job, m)?)?;
+    m.add_function(wrap_pyfunction!(enrich::py_get_enrichment_progress, m)?)?;
+    m.add_function(wrap_pyfunction!(enrich::py_cancel_enrichment_job, m)?)?;
+    
+    // Background job queue
+    m.add_function(wrap_pyfunction!(jobs::py_enqueue_job, m)?)?;
+    m.add_function(wrap_pyfunction!(jobs::py_get_job, m)?)?;
+    m.add_function(wrap_pyfunction!(jobs::py_list_jobs, m)?)?;
+    m.add_function(wrap_pyfunction!(jobs::py_cancel_job, m)?)?;
+    m.add_function(wrap_pyfunction!(jobs::py_is_job_cancelled, m)?)?;
+    m.add_function(wrap_pyfunction!(jobs::py_run_jobs, m)?)?;
+    
+    // Content filter
+    m.add_function(wrap_pyfunction!(content_filter::py_set_content_filter, m)?)?;
+    m.add_function(wrap_pyfunction!(content_filter::py_get_content_filter, m)?)?;
+    m.add_function(wrap_pyfunction!(content_filter::py_scan_deck_for_flagged, m)?)?;
+    
+    // Quiz building
+    m.add_function(wrap_pyfunction!(quiz::py_build_quiz, m)?)?;
+    m.add_function(wrap_pyfunction!(quiz::py_requeue_missed, m)?)?;
+    m.add_function(wrap_pyfunction!(audit::py_audit_generation, m)?)?;
+    
+    // Question quality feedback
+    m.add_function(wrap_pyfunction!(feedback::py_rate_question, m)?)?;
     
     // Progress tracking
     m.add_function(wrap_pyfunction!(progress::py_save_attempt, m)?)?;
     m.add_function(wrap_pyfunction!(progress::py_get_failed_words, m)?)?;
     m.add_function(wrap_pyfunction!(progress::py_get_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(progress::py_get_answer_change_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(progress::py_get_words_with_progress, m)?)?;
+    m.add_function(wrap_pyfunction!(profiles::py_create_profile, m)?)?;
+    m.add_function(wrap_pyfunction!(profiles::py_get_profiles, m)?)?;
+    m.add_function(wrap_pyfunction!(profiles::py_get_profile, m)?)?;
+    m.add_function(wrap_pyfunction!(profiles::py_rename_profile, m)?)?;
+    m.add_function(wrap_pyfunction!(profiles::py_delete_profile, m)?)?;
+    m.add_function(wrap_pyfunction!(legacy::py_import_legacy_progress, m)?)?;
+    m.add_function(wrap_pyfunction!(srs::py_get_due_words, m)?)?;
+    m.add_function(wrap_pyfunction!(srs::py_snooze_word, m)?)?;
+    m.add_function(wrap_pyfunction!(srs::py_unsnooze_word, m)?)?;
+    m.add_function(wrap_pyfunction!(srs::py_get_snoozed_words, m)?)?;
+    m.add_function(wrap_pyfunction!(srs::py_get_learning_phase, m)?)?;
+    m.add_function(wrap_pyfunction!(srs::py_get_learning_phases, m)?)?;
+    m.add_function(wrap_pyfunction!(srs::py_get_recognitions_to_graduate, m)?)?;
+    m.add_function(wrap_pyfunction!(srs::py_set_recognitions_to_graduate, m)?)?;
+    m.add_function(wrap_pyfunction!(srs::py_get_deck_srs_params, m)?)?;
+    m.add_function(wrap_pyfunction!(srs::py_set_deck_srs_params, m)?)?;
+    m.add_function(wrap_pyfunction!(norms::py_compare_to_norms, m)?)?;
+    
+    // Cram mode
+    m.add_function(wrap_pyfunction!(cram::py_build_cram_queue, m)?)?;
+    m.add_function(wrap_pyfunction!(cram::py_fold_cram_results_into_schedule, m)?)?;
+    
+    // Learner simulation
+    m.add_function(wrap_pyfunction!(simulate::py_simulate, m)?)?;
+    m.add_function(wrap_pyfunction!(simulate::py_simulate_deck, m)?)?;
+    
+    // Sample data
+    m.add_function(wrap_pyfunction!(sample::py_create_sample_database, m)?)?;
+    
+    // Sessions and study time
+    m.add_function(wrap_pyfunction!(sessions::py_get_session_summary, m)?)?;
+    m.add_function(wrap_pyfunction!(sessions::py_log_study_time, m)?)?;
+    m.add_function(wrap_pyfunction!(sessions::py_get_daily_study_time, m)?)?;
+    m.add_function(wrap_pyfunction!(sessions::py_get_study_time_by_activity, m)?)?;
+    
+    // Reports and charts
+    m.add_function(wrap_pyfunction!(report::py_render_weekly_report_html, m)?)?;
+    m.add_function(wrap_pyfunction!(charts::py_render_accuracy_chart_svg, m)?)?;
+    m.add_function(wrap_pyfunction!(charts::py_render_due_forecast_svg, m)?)?;
+    
+    // Schema introspection and upgrades
+    m.add_function(wrap_pyfunction!(schema::py_describe_schema, m)?)?;
+    m.add_function(wrap_pyfunction!(schema::py_open_with_upgrade, m)?)?;
+    m.add_function(wrap_pyfunction!(views::py_refresh_views, m)?)?;
+    
+    // Backups
+    m.add_function(wrap_pyfunction!(backup::py_backup_database, m)?)?;
+    m.add_function(wrap_pyfunction!(backup::py_restore_database, m)?)?;
+    
+    // Maintenance
+    m.add_function(wrap_pyfunction!(maintenance::py_check_capacity, m)?)?;
+    m.add_function(wrap_pyfunction!(maintenance::py_maintain_database, m)?)?;
+    m.add_function(wrap_pyfunction!(maintenance::py_check_database, m)?)?;
     
     // Register classes
     m.add_class::<db::Word>()?;
     m.add_class::<db::CategoryInfo>()?;
+    m.add_class::<db::MergeResult>()?;
+    m.add_class::<db::AddWordResult>()?;
+    m.add_class::<fts::SearchHit>()?;
+    m.add_class::<history::WordHistoryEntry>()?;
+    m.add_class::<validation::ValidationIssue>()?;
+    m.add_class::<duplicates::DuplicateGroup>()?;
+    m.add_class::<duplicates::SharedWord>()?;
+    m.add_class::<duplicates::CategoryDiff>()?;
+    m.add_class::<srs::SnoozedWord>()?;
+    m.add_class::<srs::SrsParams>()?;
+    m.add_class::<cram::CramItem>()?;
+    m.add_class::<cram::CramFold>()?;
+    m.add_class::<simulate::SimulationConfig>()?;
+    m.add_class::<simulate::SimulatedDay>()?;
+    m.add_class::<simulate::SimulationResult>()?;
+    m.add_class::<sample::SampleDatabase>()?;
+    m.add_class::<db::TrashedWord>()?;
+    m.add_class::<db::TagInfo>()?;
+    m.add_class::<db::Example>()?;
+    m.add_class::<db::DatabaseOptions>()?;
+    m.add("ValidationError", m.py().get_type::<validation::ValidationError>())?;
     m.add_class::<fuzzy::MatchResult>()?;
+    m.add_class::<ruby::RubySegment>()?;
     m.add_class::<questions::MCQQuestion>()?;
+    m.add_class::<questions::QuestionTypeInfo>()?;
+    m.add_class::<question_bank::CustomQuestion>()?;
+    m.add_class::<cloze::ClozeImport>()?;
+    m.add_class::<content_filter::FlaggedEntry>()?;
+    m.add_class::<deck::DeckMetadata>()?;
+    m.add_class::<enrich::EnrichmentPolicy>()?;
+    m.add_class::<enrich::EnrichmentProgress>()?;
+    m.add_class::<jobs::Job>()?;
+    m.add_class::<journal::JournalEntry>()?;
+    m.add_class::<quiz::QuizOptions>()?;
+    m.add_class::<audit::GenerationAudit>()?;
     m.add_class::<progress::AttemptStats>()?;
+    m.add_class::<progress::AnswerChangeStats>()?;
+    m.add_class::<progress::WordWithProgress>()?;
+    m.add_class::<attempt_buffer::AttemptBuffer>()?;
+    m.add_class::<profiles::Profile>()?;
+    m.add_class::<legacy::LegacyImportReport>()?;
+    m.add_class::<norms::NormComparison>()?;
+    m.add_class::<sessions::DailyStudyTime>()?;
+    m.add_class::<sessions::ActivityTime>()?;
+    m.add_class::<sessions::SessionSummary>()?;
+    m.add_class::<schema::SchemaInfo>()?;
+    m.add_class::<schema::TableInfo>()?;
+    m.add_class::<schema::ColumnInfo>()?;
+    m.add_class::<schema::UpgradeReport>()?;
+    m.add_class::<maintenance::CapacityLimits>()?;
+    m.add_class::<maintenance::CapacityWarning>()?;
+    m.add_class::<maintenance::MaintenanceReport>()?;
+    m.add_class::<maintenance::IntegrityReport>()?;
+    m.add_class::<excel::ImportReport>()?;
+    m.add_class::<watch::FolderWatcher>()?;
+    m.add_class::<roundtrip::WordChange>()?;
+    m.add_class::<roundtrip::ReimportDiff>()?;
     
     Ok(())
 }
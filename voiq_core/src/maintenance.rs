@@ -0,0 +1,279 @@
+//! Database upkeep: growth checks the app can run at startup to prompt maintenance, the
+//! maintenance itself, and integrity checks with optional repair.
+
+use pyo3::prelude::*;
+use rusqlite::Connection;
+use crate::db::{get_default_category, open_database};
+
+/// Recommended action: reclaim free pages (also available as the "vacuum" job)
+pub const ACTION_VACUUM: &str = "vacuum";
+/// Recommended action: permanently remove trashed words with `purge_trash`
+pub const ACTION_PURGE_TRASH: &str = "purge_trash";
+/// Recommended action: the user should archive or delete old decks or history
+pub const ACTION_ARCHIVE: &str = "archive";
+
+/// Soft limits on database growth; exceeding one produces a `CapacityWarning`
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct CapacityLimits {
+    #[pyo3(get, set)]
+    pub max_attempts: i64,
+    /// Words not in the trash
+    #[pyo3(get, set)]
+    pub max_words: i64,
+    #[pyo3(get, set)]
+    pub max_trashed_words: i64,
+    #[pyo3(get, set)]
+    pub max_file_mb: f64,
+    /// Share of the file taken by free pages left behind by deletions
+    #[pyo3(get, set)]
+    pub max_free_percent: f64,
+}
+
+impl Default for CapacityLimits {
+    fn default() -> Self {
+        CapacityLimits {
+            max_attempts: 1_000_000,
+            max_words: 100_000,
+            max_trashed_words: 10_000,
+            max_file_mb: 1024.0,
+            max_free_percent: 25.0,
+        }
+    }
+}
+
+#[pymethods]
+impl CapacityLimits {
+    #[new]
+    #[pyo3(signature = (max_attempts=1_000_000, max_words=100_000, max_trashed_words=10_000, max_file_mb=1024.0, max_free_percent=25.0))]
+    fn py_new(max_attempts: i64, max_words: i64, max_trashed_words: i64, max_file_mb: f64, max_free_percent: f64) -> Self {
+        CapacityLimits { max_attempts, max_words, max_trashed_words, max_file_mb, max_free_percent }
+    }
+    
+    fn __repr__(&self) -> String {
+        format!("CapacityLimits(max_attempts={}, max_words={}, max_file_mb={})",
+                self.max_attempts, self.max_words, self.max_file_mb)
+    }
+}
+
+/// A measurement over its soft limit, with what to do about it
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct CapacityWarning {
+    /// "attempts", "words", "trashed_words", "file_mb" or "free_percent"
+    #[pyo3(get)]
+    pub metric: String,
+    #[pyo3(get)]
+    pub value: f64,
+    #[pyo3(get)]
+    pub limit: f64,
+    /// One of `ACTION_VACUUM`, `ACTION_PURGE_TRASH`, `ACTION_ARCHIVE`
+    #[pyo3(get)]
+    pub action: String,
+    #[pyo3(get)]
+    pub message: String,
+}
+
+#[pymethods]
+impl CapacityWarning {
+    fn __repr__(&self) -> String {
+        format!("CapacityWarning(metric='{}', value={}, limit={}, action='{}')",
+                self.metric, self.value, self.limit, self.action)
+    }
+}
+
+/// Size of the database file in bytes and the share of it that is free pages
+fn file_usage(conn: &Connection) -> rusqlite::Result<(i64, f64)> {
+    let page_size: i64 = conn.query_row("PRAGMA page_size", [], |row| row.get(0))?;
+    let page_count: i64 = conn.query_row("PRAGMA page_count", [], |row| row.get(0))?;
+    let free_pages: i64 = conn.query_row("PRAGMA freelist_count", [], |row| row.get(0))?;
+    let free_percent = if page_count > 0 { free_pages as f64 * 100.0 / page_count as f64 } else { 0.0 };
+    Ok((page_size * page_count, free_percent))
+}
+
+/// Compare table sizes and file usage against `limits`. Returns one warning per
+/// exceeded limit (empty when everything is within bounds).
+pub fn check_capacity(db_path: &str, limits: &CapacityLimits) -> Result<Vec<CapacityWarning>, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let count = |sql: &str| conn.query_row(sql, [], |row| row.get::<_, i64>(0))
+        .map_err(|e| format!("Failed to count rows: {}", e));
+    
+    let attempts = count("SELECT COUNT(*) FROM attempts")?;
+    let words = count("SELECT COUNT(*) FROM vocabulary WHERE deleted_at IS NULL")?;
+    let trashed = count("SELECT COUNT(*) FROM vocabulary WHERE deleted_at IS NOT NULL")?;
+    let (bytes, free_percent) = file_usage(&conn)
+        .map_err(|e| format!("Failed to read file usage: {}", e))?;
+    let file_mb = bytes as f64 / (1024.0 * 1024.0);
+    
+    let checks = [
+        ("attempts", attempts as f64, limits.max_attempts as f64, ACTION_ARCHIVE,
+         "Study history is very large; export or remove old attempts to keep statistics fast"),
+        ("words", words as f64, limits.max_words as f64, ACTION_ARCHIVE,
+         "The vocabulary is very large; consider exporting and removing decks no longer studied"),
+        ("trashed_words", trashed as f64, limits.max_trashed_words as f64, ACTION_PURGE_TRASH,
+         "Many words are waiting in the trash; empty it to free space"),
+        ("file_mb", file_mb, limits.max_file_mb, ACTION_VACUUM,
+         "The database file is large; compact it to reclaim space"),
+        ("free_percent", free_percent, limits.max_free_percent, ACTION_VACUUM,
+         "Much of the file is unused space left by deletions; compact it to shrink the file"),
+    ];
+    Ok(checks.into_iter()
+        .filter(|(_, value, limit, _, _)| value > limit)
+        .map(|(metric, value, limit, action, message)| CapacityWarning {
+            metric: metric.to_string(),
+            value,
+            limit,
+            action: action.to_string(),
+            message: message.to_string(),
+        })
+        .collect())
+}
+
+/// File size and free space before and after `maintain_database`
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct MaintenanceReport {
+    #[pyo3(get)]
+    pub bytes_before: i64,
+    #[pyo3(get)]
+    pub bytes_after: i64,
+    #[pyo3(get)]
+    pub free_percent_before: f64,
+    #[pyo3(get)]
+    pub free_percent_after: f64,
+}
+
+#[pymethods]
+impl MaintenanceReport {
+    /// Bytes the file shrank by
+    #[getter]
+    pub fn reclaimed_bytes(&self) -> i64 {
+        (self.bytes_before - self.bytes_after).max(0)
+    }
+    
+    fn __repr__(&self) -> String {
+        format!("MaintenanceReport(bytes_before={}, bytes_after={}, reclaimed_bytes={})",
+                self.bytes_before, self.bytes_after, self.reclaimed_bytes())
+    }
+}
+
+/// Rebuild the file without its free pages (VACUUM), refresh the query planner's
+/// statistics (ANALYZE, PRAGMA optimize) and fold the write-ahead log back in. Needs
+/// exclusive access for the VACUUM, so run it while nothing else is using the database.
+pub fn maintain_database(db_path: &str) -> Result<MaintenanceReport, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let (bytes_before, free_percent_before) = file_usage(&conn)
+        .map_err(|e| format!("Failed to read file usage: {}", e))?;
+    
+    conn.execute_batch("VACUUM;")
+        .map_err(|e| format!("Vacuum failed: {}", e))?;
+    conn.execute_batch("ANALYZE; PRAGMA optimize;")
+        .map_err(|e| format!("Failed to update statistics: {}", e))?;
+    // Returns a status row in WAL mode and does nothing otherwise
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+        .map_err(|e| format!("Failed to checkpoint log: {}", e))?;
+    
+    let (bytes_after, free_percent_after) = file_usage(&conn)
+        .map_err(|e| format!("Failed to read file usage: {}", e))?;
+    Ok(MaintenanceReport { bytes_before, bytes_after, free_percent_before, free_percent_after })
+}
+
+/// Problems found by `check_database`
+#[pyclass]
+#[derive(Debug, Clone, Default)]
+pub struct IntegrityReport {
+    /// Messages from `PRAGMA integrity_check` (empty when the file is sound)
+    #[pyo3(get)]
+    pub integrity_errors: Vec<String>,
+    /// Attempts whose word no longer exists
+    #[pyo3(get)]
+    pub orphaned_attempts: i64,
+    /// Words with no category, which only show up under the default category
+    #[pyo3(get)]
+    pub null_category_words: i64,
+    /// Whether the problems above were repaired (counts are from before the repair)
+    #[pyo3(get)]
+    pub fixed: bool,
+}
+
+#[pymethods]
+impl IntegrityReport {
+    /// True when nothing was found
+    #[getter]
+    pub fn is_ok(&self) -> bool {
+        self.integrity_errors.is_empty() && self.orphaned_attempts == 0 && self.null_category_words == 0
+    }
+    
+    fn __repr__(&self) -> String {
+        format!("IntegrityReport(integrity_errors={}, orphaned_attempts={}, null_category_words={}, fixed={})",
+                self.integrity_errors.len(), self.orphaned_attempts, self.null_category_words, self.fixed)
+    }
+}
+
+/// Messages from `PRAGMA integrity_check`, without the lone "ok" of a sound file
+fn integrity_errors(conn: &Connection) -> rusqlite::Result<Vec<String>> {
+    let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+    let messages = stmt.query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(messages.into_iter().filter(|m| m != "ok").collect())
+}
+
+/// Check the file's integrity and look for attempts of deleted words and words without a
+/// category. With `fix`, orphaned attempts are deleted, category-less words are moved to
+/// the default category and indexes are rebuilt if the integrity check complained
+/// (damage to table data itself can only be undone by restoring a backup).
+pub fn check_database(db_path: &str, fix: bool) -> Result<IntegrityReport, String> {
+    let mut conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let mut report = IntegrityReport {
+        integrity_errors: integrity_errors(&conn).map_err(|e| format!("Integrity check failed: {}", e))?,
+        ..Default::default()
+    };
+    let count = |sql: &str| conn.query_row(sql, [], |row| row.get::<_, i64>(0))
+        .map_err(|e| format!("Failed to count rows: {}", e));
+    report.orphaned_attempts = count("SELECT COUNT(*) FROM attempts WHERE word_id NOT IN (SELECT id FROM vocabulary)")?;
+    report.null_category_words = count("SELECT COUNT(*) FROM vocabulary WHERE category IS NULL OR TRIM(category) = ''")?;
+    if !fix || report.is_ok() {
+        return Ok(report);
+    }
+    
+    let default_category = get_default_category(&conn);
+    let tx = conn.transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    tx.execute("DELETE FROM attempts WHERE word_id NOT IN (SELECT id FROM vocabulary)", [])
+        .map_err(|e| format!("Failed to delete orphaned attempts: {}", e))?;
+    tx.execute("UPDATE vocabulary SET category = ?1 WHERE category IS NULL OR TRIM(category) = ''", [&default_category])
+        .map_err(|e| format!("Failed to set categories: {}", e))?;
+    if !report.integrity_errors.is_empty() {
+        tx.execute_batch("REINDEX;")
+            .map_err(|e| format!("Failed to rebuild indexes: {}", e))?;
+    }
+    tx.commit().map_err(|e| format!("Failed to commit repairs: {}", e))?;
+    report.fixed = true;
+    Ok(report)
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "check_capacity", signature = (db_path, limits=None))]
+pub fn py_check_capacity(db_path: &str, limits: Option<CapacityLimits>) -> PyResult<Vec<CapacityWarning>> {
+    check_capacity(db_path, &limits.unwrap_or_default())
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "maintain_database")]
+pub fn py_maintain_database(db_path: &str) -> PyResult<MaintenanceReport> {
+    maintain_database(db_path)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "check_database", signature = (db_path, fix=false))]
+pub fn py_check_database(db_path: &str, fix: bool) -> PyResult<IntegrityReport> {
+    check_database(db_path, fix)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
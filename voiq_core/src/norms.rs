@@ -0,0 +1,169 @@
+//! Percentile comparison of a user's accuracy and speed against aggregate norms
+
+use pyo3::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use crate::db::{open_database, DELETED_WORD_IDS, IGNORED_WORD_IDS};
+
+/// Norms shipped with the module; a norms file with the same format can replace them
+const BUILTIN_NORMS: &str = include_str!("../norms/default.json");
+
+/// Key in the norms file used for question types without their own entry
+const FALLBACK_KEY: &str = "*";
+
+/// Question types need this many attempts before they are compared
+const MIN_ATTEMPTS: i64 = 5;
+
+/// Population distribution for one question type as (percentile, value) points
+#[derive(Debug, Clone, Deserialize)]
+struct TypeNorms {
+    accuracy: Vec<(f64, f64)>,
+    median_time_ms: Vec<(f64, f64)>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Norms {
+    #[serde(default)]
+    source: String,
+    question_types: HashMap<String, TypeNorms>,
+}
+
+/// The user's standing for one question type
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct NormComparison {
+    #[pyo3(get)]
+    pub question_type: String,
+    #[pyo3(get)]
+    pub attempts: i64,
+    /// Accuracy as a fraction (0.0 - 1.0)
+    #[pyo3(get)]
+    pub accuracy: f64,
+    /// Share of the population with lower accuracy (0 - 100)
+    #[pyo3(get)]
+    pub accuracy_percentile: f64,
+    #[pyo3(get)]
+    pub median_time_ms: Option<f64>,
+    /// Share of the population that is slower (0 - 100)
+    #[pyo3(get)]
+    pub speed_percentile: Option<f64>,
+    /// Where the norms came from ("builtin" or the norms file's source)
+    #[pyo3(get)]
+    pub norms_source: String,
+}
+
+#[pymethods]
+impl NormComparison {
+    fn __repr__(&self) -> String {
+        format!("NormComparison(type='{}', accuracy_percentile={:.0}, speed_percentile={:?})", 
+                self.question_type, self.accuracy_percentile, self.speed_percentile.map(|p| p.round()))
+    }
+}
+
+/// Percentile of a value within a distribution given as sorted (percentile, value) points.
+/// Interpolates linearly between points and extrapolates towards 0 and 100 at the ends.
+fn percentile_of(points: &[(f64, f64)], value: f64) -> f64 {
+    let Some(&(first_p, first_v)) = points.first() else {
+        return 50.0;
+    };
+    let &(last_p, last_v) = points.last().unwrap_or(&(first_p, first_v));
+    
+    if value <= first_v {
+        return if first_v > 0.0 { (first_p * value / first_v).max(0.0) } else { 0.0 };
+    }
+    if value >= last_v {
+        return last_p + (100.0 - last_p) * ((value - last_v) / last_v.abs().max(f64::EPSILON)).min(1.0);
+    }
+    
+    for pair in points.windows(2) {
+        let ((p0, v0), (p1, v1)) = (pair[0], pair[1]);
+        if value >= v0 && value <= v1 {
+            return if v1 > v0 { p0 + (p1 - p0) * (value - v0) / (v1 - v0) } else { p1 };
+        }
+    }
+    50.0
+}
+
+fn load_norms(norms_path: Option<&str>) -> Result<Norms, String> {
+    let text = match norms_path {
+        Some(path) => std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read norms file: {}", e))?,
+        None => BUILTIN_NORMS.to_string(),
+    };
+    let mut norms: Norms = serde_json::from_str(&text)
+        .map_err(|e| format!("Invalid norms file: {}", e))?;
+    
+    for type_norms in norms.question_types.values_mut() {
+        type_norms.accuracy.sort_by(|a, b| a.1.total_cmp(&b.1));
+        type_norms.median_time_ms.sort_by(|a, b| a.1.total_cmp(&b.1));
+    }
+    if norms.source.is_empty() {
+        norms.source = norms_path.unwrap_or("builtin").to_string();
+    }
+    Ok(norms)
+}
+
+/// Place the user's accuracy and median response time per question type into
+/// population percentiles, using the built-in norms or a norms file
+pub fn compare_to_norms(db_path: &str, norms_path: Option<&str>) -> Result<Vec<NormComparison>, String> {
+    let norms = load_norms(norms_path)?;
+    
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    
+    // Attempts at ignored or deleted words don't count
+    let counted = format!("word_id NOT IN ({}) AND word_id NOT IN ({})", IGNORED_WORD_IDS, DELETED_WORD_IDS);
+    let mut stmt = conn.prepare(&format!(
+        "SELECT question_type, COUNT(*), AVG(is_correct) FROM attempts WHERE {}
+         GROUP BY question_type HAVING COUNT(*) >= ?1 ORDER BY question_type",
+        counted
+    )).map_err(|e| format!("Failed to prepare query: {}", e))?;
+    let per_type: Vec<(String, i64, f64)> = stmt.query_map([MIN_ATTEMPTS], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .map_err(|e| format!("Failed to execute query: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    
+    let mut results = Vec::new();
+    for (question_type, attempts, accuracy) in per_type {
+        let Some(type_norms) = norms.question_types.get(&question_type).or_else(|| norms.question_types.get(FALLBACK_KEY)) else {
+            continue;
+        };
+        
+        let mut time_stmt = conn.prepare(&format!(
+            "SELECT time_taken_ms FROM attempts WHERE question_type = ?1 AND time_taken_ms IS NOT NULL AND {}",
+            counted
+        )).map_err(|e| format!("Failed to prepare query: {}", e))?;
+        let times: Vec<i64> = time_stmt.query_map([&question_type], |row| row.get(0))
+            .map_err(|e| format!("Failed to execute query: {}", e))?
+            .filter_map(|t| t.ok())
+            .collect();
+        let median_time = (!times.is_empty())
+            .then(|| crate::progress::summarize_times(times, &Default::default()).median_ms);
+        
+        // Faster than X% means X% of the population has a higher median time
+        let speed_percentile = median_time
+            .filter(|_| !type_norms.median_time_ms.is_empty())
+            .map(|t| 100.0 - percentile_of(&type_norms.median_time_ms, t));
+        
+        results.push(NormComparison {
+            question_type,
+            attempts,
+            accuracy,
+            accuracy_percentile: percentile_of(&type_norms.accuracy, accuracy),
+            median_time_ms: median_time,
+            speed_percentile,
+            norms_source: norms.source.clone(),
+        });
+    }
+    
+    Ok(results)
+}
+
+// ============= Python Binding =============
+
+#[pyfunction]
+#[pyo3(name = "compare_to_norms", signature = (db_path, norms_path=None))]
+pub fn py_compare_to_norms(db_path: &str, norms_path: Option<&str>) -> PyResult<Vec<NormComparison>> {
+    compare_to_norms(db_path, norms_path)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
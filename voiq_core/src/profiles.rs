@@ -0,0 +1,178 @@
+//! Learner profiles for installs shared by several people.
+//!
+//! Attempts carry the profile that answered them, and statistics and failed-word lists
+//! can be narrowed to one profile. Attempts saved without a profile belong to none and
+//! only show up unfiltered. Vocabulary and the review schedule are shared by everyone.
+
+use pyo3::prelude::*;
+use rusqlite::{params, Connection, OptionalExtension};
+use crate::db::open_database;
+
+/// A learner sharing the database
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct Profile {
+    #[pyo3(get)]
+    pub id: i64,
+    #[pyo3(get)]
+    pub name: String,
+    #[pyo3(get)]
+    pub created_at: String,
+    #[pyo3(get)]
+    pub attempt_count: i64,
+}
+
+#[pymethods]
+impl Profile {
+    fn __repr__(&self) -> String {
+        format!("Profile(id={}, name='{}', attempt_count={})", self.id, self.name, self.attempt_count)
+    }
+}
+
+/// Trimmed profile name, or an error for a blank one
+fn profile_name(name: &str) -> Result<&str, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Profile name must not be empty".to_string());
+    }
+    Ok(name)
+}
+
+fn name_taken(conn: &Connection, name: &str, except_id: i64) -> rusqlite::Result<bool> {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM profiles WHERE name = ?1 AND id != ?2)",
+        params![name, except_id],
+        |row| row.get(0),
+    )
+}
+
+/// Read one profile (None if there is no such profile)
+pub fn read_profile(conn: &Connection, profile_id: i64) -> rusqlite::Result<Option<Profile>> {
+    conn.query_row(
+        "SELECT id, name, created_at, (SELECT COUNT(*) FROM attempts WHERE profile_id = p.id)
+         FROM profiles p WHERE id = ?1",
+        params![profile_id],
+        |row| Ok(Profile {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            created_at: row.get(2)?,
+            attempt_count: row.get(3)?,
+        }),
+    ).optional()
+}
+
+/// Add a profile; names are unique regardless of case
+pub fn create_profile(db_path: &str, name: &str) -> Result<Profile, String> {
+    let name = profile_name(name)?;
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    if name_taken(&conn, name, 0).map_err(|e| format!("Failed to read profiles: {}", e))? {
+        return Err(format!("Profile '{}' already exists", name));
+    }
+    conn.execute("INSERT INTO profiles (name) VALUES (?1)", params![name])
+        .map_err(|e| format!("Failed to create profile: {}", e))?;
+    read_profile(&conn, conn.last_insert_rowid())
+        .map_err(|e| format!("Failed to read profile: {}", e))?
+        .ok_or_else(|| "Failed to read profile".to_string())
+}
+
+/// All profiles in name order
+pub fn get_profiles(db_path: &str) -> Result<Vec<Profile>, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let mut stmt = conn.prepare(
+        "SELECT p.id, p.name, p.created_at, COUNT(a.id) FROM profiles p
+         LEFT JOIN attempts a ON a.profile_id = p.id
+         GROUP BY p.id ORDER BY p.name"
+    ).map_err(|e| format!("Failed to prepare query: {}", e))?;
+    let profiles = stmt.query_map([], |row| Ok(Profile {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        created_at: row.get(2)?,
+        attempt_count: row.get(3)?,
+    }))
+        .map_err(|e| format!("Failed to execute query: {}", e))?
+        .filter_map(|p| p.ok())
+        .collect();
+    Ok(profiles)
+}
+
+/// A profile by id (None if there is no such profile)
+pub fn get_profile(db_path: &str, profile_id: i64) -> Result<Option<Profile>, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    read_profile(&conn, profile_id)
+        .map_err(|e| format!("Failed to read profile: {}", e))
+}
+
+/// Rename a profile. Returns false if there is no such profile.
+pub fn rename_profile(db_path: &str, profile_id: i64, name: &str) -> Result<bool, String> {
+    let name = profile_name(name)?;
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    if name_taken(&conn, name, profile_id).map_err(|e| format!("Failed to read profiles: {}", e))? {
+        return Err(format!("Profile '{}' already exists", name));
+    }
+    let renamed = conn.execute("UPDATE profiles SET name = ?1 WHERE id = ?2", params![name, profile_id])
+        .map_err(|e| format!("Failed to rename profile: {}", e))?;
+    Ok(renamed > 0)
+}
+
+/// Delete a profile. Its attempts are deleted with `delete_attempts`, otherwise kept
+/// without a profile. Returns false if there was no such profile.
+pub fn delete_profile(db_path: &str, profile_id: i64, delete_attempts: bool) -> Result<bool, String> {
+    let mut conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let tx = conn.transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let attempts_sql = if delete_attempts {
+        "DELETE FROM attempts WHERE profile_id = ?1"
+    } else {
+        "UPDATE attempts SET profile_id = NULL WHERE profile_id = ?1"
+    };
+    tx.execute(attempts_sql, params![profile_id])
+        .map_err(|e| format!("Failed to update attempts: {}", e))?;
+    let deleted = tx.execute("DELETE FROM profiles WHERE id = ?1", params![profile_id])
+        .map_err(|e| format!("Failed to delete profile: {}", e))?;
+    tx.commit().map_err(|e| format!("Failed to commit changes: {}", e))?;
+    Ok(deleted > 0)
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "create_profile")]
+pub fn py_create_profile(db_path: &str, name: &str) -> PyResult<Profile> {
+    profile_name(name).map_err(pyo3::exceptions::PyValueError::new_err)?;
+    create_profile(db_path, name)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "get_profiles")]
+pub fn py_get_profiles(db_path: &str) -> PyResult<Vec<Profile>> {
+    get_profiles(db_path)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "get_profile")]
+pub fn py_get_profile(db_path: &str, profile_id: i64) -> PyResult<Option<Profile>> {
+    get_profile(db_path, profile_id)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "rename_profile")]
+pub fn py_rename_profile(db_path: &str, profile_id: i64, name: &str) -> PyResult<bool> {
+    profile_name(name).map_err(pyo3::exceptions::PyValueError::new_err)?;
+    rename_profile(db_path, profile_id, name)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "delete_profile", signature = (db_path, profile_id, delete_attempts=false))]
+pub fn py_delete_profile(db_path: &str, profile_id: i64, delete_attempts: bool) -> PyResult<bool> {
+    delete_profile(db_path, profile_id, delete_attempts)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
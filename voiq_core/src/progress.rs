@@ -1,8 +1,12 @@
 //! Progress tracking - attempts storage and statistics
 
 use pyo3::prelude::*;
-use rusqlite::{Connection, params};
-use crate::db::Word;
+use rusqlite::{Connection, params, params_from_iter};
+use serde_json::Value;
+use std::collections::HashMap;
+use crate::db::{open_database, Word, DELETED_WORD_IDS, IGNORED_WORD_IDS, category_condition, get_words, get_words_by_ids};
+use crate::cram::CRAM_MODE;
+use crate::srs::{record_review, SNOOZED_WORD_IDS};
 
 /// Attempt statistics for a user
 #[pyclass]
@@ -16,17 +20,203 @@ pub struct AttemptStats {
     pub incorrect_count: i64,
     #[pyo3(get)]
     pub accuracy_percent: f64,
+    /// Number of attempts with a recorded response time
+    #[pyo3(get)]
+    pub timed_attempts: i64,
+    /// Mean response time after capping outliers
+    #[pyo3(get)]
+    pub avg_time_ms: f64,
+    #[pyo3(get)]
+    pub median_time_ms: f64,
+    /// Cap applied to response times (None if capping is disabled or no times were recorded)
+    #[pyo3(get)]
+    pub time_cap_ms: Option<i64>,
+    /// Number of response times that were capped as outliers
+    #[pyo3(get)]
+    pub capped_count: i64,
 }
 
 #[pymethods]
 impl AttemptStats {
     fn __repr__(&self) -> String {
-        format!("AttemptStats(total={}, correct={}, accuracy={:.1}%)", 
-                self.total_attempts, self.correct_count, self.accuracy_percent)
+        format!("AttemptStats(total={}, correct={}, accuracy={:.1}%, avg_time={:.0}ms)", 
+                self.total_attempts, self.correct_count, self.accuracy_percent, self.avg_time_ms)
     }
 }
 
-/// Save an attempt to the database
+/// A word with its review progress, for list views
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct WordWithProgress {
+    #[pyo3(get)]
+    pub word: Word,
+    #[pyo3(get)]
+    pub last_attempted_at: Option<String>,
+    #[pyo3(get)]
+    pub times_seen: i64,
+    /// Fraction of correct answers, 0-1 (None if never attempted)
+    #[pyo3(get)]
+    pub accuracy: Option<f64>,
+    /// When the word is next due for review (None if never reviewed)
+    #[pyo3(get)]
+    pub next_due: Option<String>,
+}
+
+#[pymethods]
+impl WordWithProgress {
+    fn __repr__(&self) -> String {
+        format!("WordWithProgress(word='{}', times_seen={}, next_due={:?})",
+                self.word.word, self.times_seen, self.next_due)
+    }
+}
+
+/// How often users changed their MCQ answer before submitting, and whether it helped
+#[pyclass]
+#[derive(Debug, Clone, Default)]
+pub struct AnswerChangeStats {
+    /// Attempts where a first answer was recorded
+    #[pyo3(get)]
+    pub tracked_attempts: i64,
+    #[pyo3(get)]
+    pub changed_count: i64,
+    #[pyo3(get)]
+    pub right_to_wrong: i64,
+    #[pyo3(get)]
+    pub wrong_to_right: i64,
+    #[pyo3(get)]
+    pub wrong_to_wrong: i64,
+}
+
+#[pymethods]
+impl AnswerChangeStats {
+    fn __repr__(&self) -> String {
+        format!("AnswerChangeStats(changed={}, right_to_wrong={}, wrong_to_right={})", 
+                self.changed_count, self.right_to_wrong, self.wrong_to_right)
+    }
+}
+
+/// Default absolute cap for response times (a question left open is not "slow", it's abandoned)
+pub const DEFAULT_MAX_TIME_MS: i64 = 120_000;
+
+/// Outlier handling for response-time statistics
+#[derive(Debug, Clone, Copy)]
+pub struct TimeCapping {
+    /// Absolute cap in milliseconds (None disables it)
+    pub max_time_ms: Option<i64>,
+    /// Winsorize at this percentile, 0-100 (None disables it)
+    pub percentile: Option<f64>,
+}
+
+impl Default for TimeCapping {
+    fn default() -> Self {
+        TimeCapping { max_time_ms: Some(DEFAULT_MAX_TIME_MS), percentile: None }
+    }
+}
+
+/// Summary of response times after outlier capping
+#[derive(Debug, Clone, Default)]
+pub struct TimeSummary {
+    pub count: i64,
+    pub mean_ms: f64,
+    pub median_ms: f64,
+    pub cap_ms: Option<i64>,
+    pub capped_count: i64,
+}
+
+/// Cap outliers in response times and summarize them
+pub fn summarize_times(mut times: Vec<i64>, capping: &TimeCapping) -> TimeSummary {
+    if times.is_empty() {
+        return TimeSummary::default();
+    }
+    
+    times.sort_unstable();
+    let n = times.len();
+    
+    let percentile_cap = capping.percentile.map(|p| {
+        let rank = ((p.clamp(0.0, 100.0) / 100.0) * n as f64).ceil() as usize;
+        times[rank.clamp(1, n) - 1]
+    });
+    let cap = match (capping.max_time_ms, percentile_cap) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    };
+    
+    let mut capped_count = 0;
+    if let Some(cap) = cap {
+        for t in times.iter_mut().filter(|t| **t > cap) {
+            *t = cap;
+            capped_count += 1;
+        }
+    }
+    
+    let mean = times.iter().sum::<i64>() as f64 / n as f64;
+    let median = if n.is_multiple_of(2) {
+        (times[n / 2 - 1] + times[n / 2]) as f64 / 2.0
+    } else {
+        times[n / 2] as f64
+    };
+    
+    TimeSummary {
+        count: n as i64,
+        mean_ms: mean,
+        median_ms: median,
+        cap_ms: cap,
+        capped_count,
+    }
+}
+
+/// Which attempts `get_stats` covers; unset fields don't filter
+#[derive(Debug, Clone, Default)]
+pub struct StatsFilter {
+    /// Local date or datetime ("2024-05-06" or "2024-05-06 18:00:00"), inclusive
+    pub since: Option<String>,
+    /// Local date or datetime, exclusive ("2024-05-13" ends the week of the 6th)
+    pub until: Option<String>,
+    pub mode: Option<String>,
+    pub question_type: Option<String>,
+    pub categories: Option<Vec<String>>,
+    pub profile_id: Option<i64>,
+}
+
+impl StatsFilter {
+    /// FROM/WHERE clause over `attempts a` (joined to `vocabulary v` when filtering by
+    /// category) and its parameters; the WHERE part is never empty
+    fn sql(&self) -> (String, Vec<String>) {
+        // Ignored and trashed words never count towards statistics
+        let mut conditions = vec![
+            format!("a.word_id NOT IN ({})", IGNORED_WORD_IDS),
+            format!("a.word_id NOT IN ({})", DELETED_WORD_IDS),
+        ];
+        let mut values = Vec::new();
+        let mut push = |condition: &str, value: &Option<String>| {
+            if let Some(value) = value {
+                values.push(value.clone());
+                conditions.push(condition.replace('?', &format!("?{}", values.len())));
+            }
+        };
+        push("datetime(a.attempted_at, 'localtime') >= ?", &self.since);
+        push("datetime(a.attempted_at, 'localtime') < ?", &self.until);
+        push("a.mode = ?", &self.mode);
+        push("a.question_type = ?", &self.question_type);
+        push("a.profile_id = ?", &self.profile_id.map(|id| id.to_string()));
+        
+        let mut from = "attempts a".to_string();
+        if let Some(condition) = category_condition(self.categories.as_deref()) {
+            from.push_str(" JOIN vocabulary v ON v.id = a.word_id");
+            conditions.push(condition);
+        }
+        
+        (format!("{} WHERE {}", from, conditions.join(" AND ")), values)
+    }
+}
+
+/// Save an attempt to the database.
+/// `client_context` is an optional JSON object describing where the answer came from
+/// (e.g. `{"device": "phone", "app_version": "1.2", "input_method": "spoken"}`).
+/// `session_id` groups attempts answered in one quiz session.
+/// `first_answer` is the option selected first when the user changed it before submitting.
+/// `profile_id` is the learner who answered (None for a single-user install).
+#[allow(clippy::too_many_arguments)]
 pub fn save_attempt(
     db_path: &str,
     word_id: i64,
@@ -36,87 +226,284 @@ pub fn save_attempt(
     user_answer: &str,
     expected_answer: &str,
     time_taken_ms: Option<i64>,
+    client_context: Option<&Value>,
+    session_id: Option<&str>,
+    first_answer: Option<&str>,
+    profile_id: Option<i64>,
 ) -> Result<(), String> {
-    let conn = Connection::open(db_path)
+    let conn = open_database(db_path)
         .map_err(|e| format!("Failed to open database: {}", e))?;
     
+    let record = AttemptRecord {
+        word_id,
+        mode: mode.to_string(),
+        question_type: question_type.to_string(),
+        is_correct,
+        user_answer: user_answer.to_string(),
+        expected_answer: expected_answer.to_string(),
+        time_taken_ms,
+        client_context: client_context.map(|c| c.to_string()),
+        session_id: session_id.map(String::from),
+        first_answer: first_answer.map(String::from),
+        profile_id,
+        attempted_at: None,
+    };
+    insert_attempt(&conn, &record)
+        .map_err(|e| format!("Failed to save attempt: {}", e))
+}
+
+/// An attempt ready to be stored (see `save_attempt` for the optional fields)
+#[derive(Debug, Clone)]
+pub struct AttemptRecord {
+    pub word_id: i64,
+    pub mode: String,
+    pub question_type: String,
+    pub is_correct: bool,
+    pub user_answer: String,
+    pub expected_answer: String,
+    pub time_taken_ms: Option<i64>,
+    /// JSON object string
+    pub client_context: Option<String>,
+    pub session_id: Option<String>,
+    pub first_answer: Option<String>,
+    pub profile_id: Option<i64>,
+    /// UTC "YYYY-MM-DD HH:MM:SS"; None stores the current time
+    pub attempted_at: Option<String>,
+}
+
+/// Store an attempt and reschedule its word (cram attempts leave the schedule alone)
+pub fn insert_attempt(conn: &Connection, record: &AttemptRecord) -> rusqlite::Result<()> {
+    insert_unscheduled_attempt(conn, record)?;
+    if record.mode != CRAM_MODE {
+        record_review(conn, record.word_id, record.is_correct)?;
+    }
+    Ok(())
+}
+
+/// Store an attempt without touching the schedule, for backfilled answers whose words
+/// are rescheduled afterwards with `rebuild_state`
+pub fn insert_unscheduled_attempt(conn: &Connection, record: &AttemptRecord) -> rusqlite::Result<()> {
     conn.execute(
-        "INSERT INTO attempts (word_id, mode, question_type, is_correct, user_answer, expected_answer, time_taken_ms) 
-         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-        params![word_id, mode, question_type, is_correct as i32, user_answer, expected_answer, time_taken_ms],
-    ).map_err(|e| format!("Failed to save attempt: {}", e))?;
-    
+        "INSERT INTO attempts (word_id, mode, question_type, is_correct, user_answer, expected_answer, time_taken_ms, client_context, session_id, first_answer, profile_id, attempted_at) 
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, COALESCE(?12, CURRENT_TIMESTAMP))",
+        params![
+            record.word_id, record.mode, record.question_type, record.is_correct as i32, record.user_answer,
+            record.expected_answer, record.time_taken_ms, record.client_context, record.session_id,
+            record.first_answer, record.profile_id, record.attempted_at,
+        ],
+    )?;
     Ok(())
 }
 
-/// Get words that have been answered incorrectly, sorted by fail count
-pub fn get_failed_words(db_path: &str, limit: Option<usize>) -> Result<Vec<(Word, i64)>, String> {
-    let conn = Connection::open(db_path)
+/// Consecutive correct answers after which earlier failures of a word stop counting
+pub const DEFAULT_GRADUATE_AFTER: usize = 3;
+
+/// Failures still counting against a word: those not followed by `graduate_after`
+/// consecutive correct answers (all failures when None). `outcomes` is oldest first.
+pub fn active_failures(outcomes: &[bool], graduate_after: Option<usize>) -> i64 {
+    let mut failures = 0;
+    let mut streak = 0;
+    for &correct in outcomes {
+        if correct {
+            streak += 1;
+            if graduate_after.is_some_and(|n| streak >= n) {
+                failures = 0;
+            }
+        } else {
+            failures += 1;
+            streak = 0;
+        }
+    }
+    failures
+}
+
+/// Get words with failures still counting against them (see `active_failures`), sorted
+/// by that count, most recently failed first on ties. With `profile_id`, only that
+/// profile's attempts count.
+pub fn get_failed_words(db_path: &str, limit: Option<usize>, graduate_after: Option<usize>, profile_id: Option<i64>) -> Result<Vec<(Word, i64)>, String> {
+    let conn = open_database(db_path)
         .map_err(|e| format!("Failed to open database: {}", e))?;
     
-    let limit_clause = limit.map(|l| format!(" LIMIT {}", l)).unwrap_or_default();
+    // Outcomes of words failed at least once and neither snoozed nor ignored, oldest first
+    let mut stmt = conn.prepare(&format!(
+        "SELECT word_id, is_correct, id FROM attempts
+         WHERE word_id IN (SELECT word_id FROM attempts WHERE is_correct = 0 AND (?1 IS NULL OR profile_id = ?1))
+           AND word_id NOT IN ({}) AND word_id NOT IN ({})
+           AND (?1 IS NULL OR profile_id = ?1)
+         ORDER BY word_id, id",
+        SNOOZED_WORD_IDS, IGNORED_WORD_IDS
+    )).map_err(|e| format!("Failed to prepare query: {}", e))?;
+    let rows = stmt.query_map(params![profile_id], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, bool>(1)?, row.get::<_, i64>(2)?)))
+        .map_err(|e| format!("Failed to execute query: {}", e))?;
     
-    let query = format!(
-        "SELECT v.id, v.word, v.meaning, v.synonyms, v.antonyms, COALESCE(v.category, 'Default'), COUNT(*) as fail_count
-         FROM vocabulary v
-         JOIN attempts a ON v.id = a.word_id
-         WHERE a.is_correct = 0
-         GROUP BY v.id
-         ORDER BY fail_count DESC{}",
-        limit_clause
-    );
+    let mut outcomes: HashMap<i64, (Vec<bool>, i64)> = HashMap::new();
+    for (word_id, correct, attempt_id) in rows.filter_map(|r| r.ok()) {
+        let entry = outcomes.entry(word_id).or_default();
+        entry.0.push(correct);
+        if !correct {
+            entry.1 = attempt_id;
+        }
+    }
     
-    let mut stmt = conn.prepare(&query)
-        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+    let mut ranked: Vec<(i64, i64, i64)> = outcomes.into_iter()
+        .map(|(word_id, (results, last_failure))| (word_id, active_failures(&results, graduate_after), last_failure))
+        .filter(|(_, failures, _)| *failures > 0)
+        .collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(b.2.cmp(&a.2)));
     
-    let results = stmt.query_map([], |row| {
-        Ok((
-            Word {
-                id: row.get(0)?,
-                word: row.get(1)?,
-                meaning: row.get(2)?,
-                synonyms: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
-                antonyms: row.get::<_, Option<String>>(4)?.unwrap_or_default(),
-                category: row.get::<_, Option<String>>(5)?.unwrap_or_else(|| "Default".to_string()),
-            },
-            row.get::<_, i64>(6)?,
-        ))
-    }).map_err(|e| format!("Failed to execute query: {}", e))?;
-    
-    let failed: Vec<(Word, i64)> = results.filter_map(|r| r.ok()).collect();
+    let ids: Vec<i64> = ranked.iter().map(|(word_id, _, _)| *word_id).collect();
+    let words = get_words_by_ids(&conn, &ids)
+        .map_err(|e| format!("Failed to get words: {}", e))?;
+    let failed = words.into_iter().zip(ranked)
+        .filter_map(|(word, (_, failures, _))| word.map(|w| (w, failures)))
+        .take(limit.unwrap_or(usize::MAX))
+        .collect();
     Ok(failed)
 }
 
-/// Get overall statistics
-pub fn get_stats(db_path: &str) -> Result<AttemptStats, String> {
-    let conn = Connection::open(db_path)
+/// Words answered incorrectly in a session with the question type of the latest miss,
+/// most recently missed first
+pub fn get_session_misses(conn: &Connection, session_id: &str) -> rusqlite::Result<Vec<(i64, String)>> {
+    // SQLite takes bare columns from the row that produced MAX(id)
+    let mut stmt = conn.prepare(
+        "SELECT word_id, question_type, MAX(id) FROM attempts WHERE session_id = ?1 AND is_correct = 0
+         GROUP BY word_id ORDER BY MAX(id) DESC"
+    )?;
+    let misses = stmt.query_map(params![session_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    Ok(misses.filter_map(|m| m.ok()).collect())
+}
+
+/// Words the user reliably knows: enough attempts at a high accuracy
+pub fn get_known_word_ids(conn: &Connection, min_attempts: i64, min_accuracy: f64) -> rusqlite::Result<Vec<i64>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT word_id FROM attempts
+         WHERE word_id NOT IN ({}) AND word_id NOT IN ({})
+         GROUP BY word_id
+         HAVING COUNT(*) >= ?1 AND AVG(is_correct) >= ?2
+         ORDER BY AVG(is_correct) DESC, COUNT(*) DESC",
+        IGNORED_WORD_IDS, DELETED_WORD_IDS
+    ))?;
+    let ids = stmt.query_map(params![min_attempts, min_accuracy], |row| row.get(0))?;
+    Ok(ids.filter_map(|i| i.ok()).collect())
+}
+
+/// Attempt counts per local day since `since` (YYYY-MM-DD) as (total, correct), keyed by date
+pub fn get_daily_attempt_counts(conn: &Connection, since: &str) -> rusqlite::Result<HashMap<String, (i64, i64)>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT date(attempted_at, 'localtime') AS day, COUNT(*), COALESCE(SUM(is_correct), 0)
+         FROM attempts WHERE date(attempted_at, 'localtime') >= ?1
+           AND word_id NOT IN ({}) AND word_id NOT IN ({})
+         GROUP BY day",
+        IGNORED_WORD_IDS, DELETED_WORD_IDS
+    ))?;
+    let rows = stmt.query_map(params![since], |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?))))?;
+    Ok(rows.filter_map(|r| r.ok()).collect())
+}
+
+/// Words (as `get_words` orders and filters them) with their attempt summary and next
+/// due date, gathered in two aggregate queries
+pub fn get_words_with_progress(db_path: &str, order: &str, letter: Option<char>, categories: Option<Vec<String>>) -> Result<Vec<WordWithProgress>, String> {
+    let conn = open_database(db_path)
         .map_err(|e| format!("Failed to open database: {}", e))?;
     
+    let words = get_words(&conn, order, letter, categories, None, None, None, false)
+        .map_err(|e| format!("Failed to get words: {}", e))?;
+    
+    let id_list = Value::from(words.iter().map(|w| w.id).collect::<Vec<_>>()).to_string();
+    
     let mut stmt = conn.prepare(
-        "SELECT COUNT(*) as total, SUM(is_correct) as correct FROM attempts"
+        "SELECT word_id, MAX(attempted_at), COUNT(*), AVG(is_correct) FROM attempts
+         WHERE word_id IN (SELECT value FROM json_each(?1)) GROUP BY word_id"
     ).map_err(|e| format!("Failed to prepare query: {}", e))?;
+    let attempts: HashMap<i64, (Option<String>, i64, Option<f64>)> = stmt
+        .query_map(params![id_list], |row| Ok((row.get(0)?, (row.get(1)?, row.get(2)?, row.get(3)?))))
+        .map_err(|e| format!("Failed to execute query: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
     
-    let stats = stmt.query_row([], |row| {
-        let total: i64 = row.get(0)?;
-        let correct: i64 = row.get::<_, Option<i64>>(1)?.unwrap_or(0);
-        let incorrect = total - correct;
-        let accuracy = if total > 0 { (correct as f64 / total as f64) * 100.0 } else { 0.0 };
-        
-        Ok(AttemptStats {
-            total_attempts: total,
-            correct_count: correct,
-            incorrect_count: incorrect,
-            accuracy_percent: accuracy,
-        })
+    let mut stmt = conn.prepare("SELECT word_id, due_at FROM srs_state WHERE word_id IN (SELECT value FROM json_each(?1))")
+        .map_err(|e| format!("Failed to prepare query: {}", e))?;
+    let due: HashMap<i64, String> = stmt.query_map(params![id_list], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| format!("Failed to execute query: {}", e))?
+        .filter_map(|r| r.ok())
+        .collect();
+    
+    Ok(words.into_iter().map(|word| {
+        let (last_attempted_at, times_seen, accuracy) = attempts.get(&word.id).cloned().unwrap_or((None, 0, None));
+        let next_due = due.get(&word.id).cloned();
+        WordWithProgress { word, last_attempted_at, times_seen, accuracy, next_due }
+    }).collect())
+}
+
+/// Summarize answer changes (first selected option vs submitted option); ignored and
+/// trashed words don't count
+pub fn get_answer_change_stats(db_path: &str) -> Result<AnswerChangeStats, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    
+    conn.query_row(
+        "SELECT COUNT(*),
+                COALESCE(SUM(a.first_answer != a.user_answer), 0),
+                COALESCE(SUM(a.first_answer != a.user_answer AND a.first_answer = a.expected_answer AND a.is_correct = 0), 0),
+                COALESCE(SUM(a.first_answer != a.user_answer AND a.first_answer != a.expected_answer AND a.is_correct = 1), 0),
+                COALESCE(SUM(a.first_answer != a.user_answer AND a.first_answer != a.expected_answer AND a.is_correct = 0), 0)
+         FROM attempts a JOIN vocabulary v ON v.id = a.word_id
+         WHERE a.first_answer IS NOT NULL AND v.ignored = 0 AND v.deleted_at IS NULL",
+        [],
+        |row| Ok(AnswerChangeStats {
+            tracked_attempts: row.get(0)?,
+            changed_count: row.get(1)?,
+            right_to_wrong: row.get(2)?,
+            wrong_to_right: row.get(3)?,
+            wrong_to_wrong: row.get(4)?,
+        }),
+    ).map_err(|e| format!("Failed to get answer change stats: {}", e))
+}
+
+/// Get statistics over the attempts matching `filter`, capping response-time outliers
+/// as configured
+pub fn get_stats(db_path: &str, capping: &TimeCapping, filter: &StatsFilter) -> Result<AttemptStats, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    
+    let (from, values) = filter.sql();
+    let mut stmt = conn.prepare(
+        &format!("SELECT COUNT(*) as total, SUM(a.is_correct) as correct FROM {}", from)
+    ).map_err(|e| format!("Failed to prepare query: {}", e))?;
+    
+    let (total, correct) = stmt.query_row(params_from_iter(&values), |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, Option<i64>>(1)?.unwrap_or(0)))
     }).map_err(|e| format!("Failed to get stats: {}", e))?;
     
-    Ok(stats)
+    let mut time_stmt = conn.prepare(&format!(
+        "SELECT a.time_taken_ms FROM {} AND a.time_taken_ms IS NOT NULL AND a.time_taken_ms >= 0", from
+    )).map_err(|e| format!("Failed to prepare query: {}", e))?;
+    let times: Vec<i64> = time_stmt.query_map(params_from_iter(&values), |row| row.get(0))
+        .map_err(|e| format!("Failed to execute query: {}", e))?
+        .filter_map(|t| t.ok())
+        .collect();
+    let time_summary = summarize_times(times, capping);
+    
+    let accuracy = if total > 0 { (correct as f64 / total as f64) * 100.0 } else { 0.0 };
+    
+    Ok(AttemptStats {
+        total_attempts: total,
+        correct_count: correct,
+        incorrect_count: total - correct,
+        accuracy_percent: accuracy,
+        timed_attempts: time_summary.count,
+        avg_time_ms: time_summary.mean_ms,
+        median_time_ms: time_summary.median_ms,
+        time_cap_ms: time_summary.cap_ms,
+        capped_count: time_summary.capped_count,
+    })
 }
 
 // ============= Python Bindings =============
 
 #[pyfunction]
-#[pyo3(name = "save_attempt")]
+#[pyo3(name = "save_attempt", signature = (db_path, word_id, mode, question_type, is_correct, user_answer, expected_answer, time_taken_ms=None, client_context=None, session_id=None, first_answer=None, profile_id=None))]
+#[allow(clippy::too_many_arguments)]
 pub fn py_save_attempt(
     db_path: &str,
     word_id: i64,
@@ -126,21 +513,56 @@ pub fn py_save_attempt(
     user_answer: &str,
     expected_answer: &str,
     time_taken_ms: Option<i64>,
+    client_context: Option<&Bound<'_, PyAny>>,
+    session_id: Option<&str>,
+    first_answer: Option<&str>,
+    profile_id: Option<i64>,
 ) -> PyResult<()> {
-    save_attempt(db_path, word_id, mode, question_type, is_correct, user_answer, expected_answer, time_taken_ms)
-        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e))
+    let context = client_context
+        .filter(|c| !c.is_none())
+        .map(crate::db::py_to_json)
+        .transpose()?;
+    save_attempt(db_path, word_id, mode, question_type, is_correct, user_answer, expected_answer, time_taken_ms, context.as_ref(), session_id, first_answer, profile_id)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "get_failed_words", signature = (db_path, limit=None, graduate_after=Some(DEFAULT_GRADUATE_AFTER), profile_id=None))]
+pub fn py_get_failed_words(db_path: &str, limit: Option<usize>, graduate_after: Option<usize>, profile_id: Option<i64>) -> PyResult<Vec<(Word, i64)>> {
+    get_failed_words(db_path, limit, graduate_after, profile_id)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "get_stats", signature = (db_path, max_time_ms=Some(DEFAULT_MAX_TIME_MS), winsorize_percentile=None, since=None, until=None, mode=None, question_type=None, categories=None, profile_id=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn py_get_stats(
+    db_path: &str,
+    max_time_ms: Option<i64>,
+    winsorize_percentile: Option<f64>,
+    since: Option<String>,
+    until: Option<String>,
+    mode: Option<String>,
+    question_type: Option<String>,
+    categories: Option<Vec<String>>,
+    profile_id: Option<i64>,
+) -> PyResult<AttemptStats> {
+    let capping = TimeCapping { max_time_ms, percentile: winsorize_percentile };
+    let filter = StatsFilter { since, until, mode, question_type, categories, profile_id };
+    get_stats(db_path, &capping, &filter)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
 }
 
 #[pyfunction]
-#[pyo3(name = "get_failed_words")]
-pub fn py_get_failed_words(db_path: &str, limit: Option<usize>) -> PyResult<Vec<(Word, i64)>> {
-    get_failed_words(db_path, limit)
-        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e))
+#[pyo3(name = "get_words_with_progress", signature = (db_path, order="a_to_z", letter=None, categories=None))]
+pub fn py_get_words_with_progress(db_path: &str, order: &str, letter: Option<char>, categories: Option<Vec<String>>) -> PyResult<Vec<WordWithProgress>> {
+    get_words_with_progress(db_path, order, letter, categories)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
 }
 
 #[pyfunction]
-#[pyo3(name = "get_stats")]
-pub fn py_get_stats(db_path: &str) -> PyResult<AttemptStats> {
-    get_stats(db_path)
-        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e))
+#[pyo3(name = "get_answer_change_stats")]
+pub fn py_get_answer_change_stats(db_path: &str) -> PyResult<AnswerChangeStats> {
+    get_answer_change_stats(db_path)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
 }
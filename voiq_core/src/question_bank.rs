@@ -0,0 +1,383 @@
+//! Question bank - teacher-authored MCQs stored alongside generated ones
+
+use pyo3::prelude::*;
+use csv::ReaderBuilder;
+use rusqlite::{Connection, OptionalExtension, Result as SqliteResult, params};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use crate::db::{open_database, category_or_default, default_category_for, get_default_category, DELETED_WORD_IDS};
+use crate::questions::{accessible_prompt, MCQQuestion};
+
+/// Question type reported for authored questions
+pub const CUSTOM_QUESTION_TYPE: &str = "custom";
+
+/// An authored multiple-choice question
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct CustomQuestion {
+    #[pyo3(get)]
+    pub id: i64,
+    /// Linked vocabulary word (attempts on this question count for the word)
+    #[pyo3(get)]
+    pub word_id: Option<i64>,
+    #[pyo3(get)]
+    pub category: String,
+    #[pyo3(get)]
+    pub stem: String,
+    #[pyo3(get)]
+    pub options: Vec<String>,
+    #[pyo3(get)]
+    pub correct_index: usize,
+}
+
+#[pymethods]
+impl CustomQuestion {
+    fn __repr__(&self) -> String {
+        format!("CustomQuestion(id={}, stem='{}...')",
+                self.id, &self.stem.chars().take(40).collect::<String>())
+    }
+}
+
+impl CustomQuestion {
+    /// Present the authored question in the same shape as generated ones
+    pub fn to_mcq(&self) -> MCQQuestion {
+        MCQQuestion {
+            word_id: self.word_id.unwrap_or(0),
+            question_type: CUSTOM_QUESTION_TYPE.to_string(),
+            question_text: self.stem.clone(),
+            options: self.options.clone(),
+            correct_index: self.correct_index,
+            correct_answer: self.options[self.correct_index].clone(),
+            recommended_time_ms: None,
+            question_id: Some(self.id),
+            accessible_prompt: accessible_prompt(&self.stem, self.options.len()),
+            spelled_word: None,
+            alt_text: None,
+        }
+    }
+}
+
+/// Portable question record used by the JSON import/export format.
+/// Words are referenced by text so files can move between databases.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuestionRecord {
+    #[serde(alias = "question")]
+    pub stem: String,
+    pub options: Vec<String>,
+    /// 0-based index into `options`
+    pub correct_index: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub word: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+}
+
+/// Check an authored question is usable
+fn validate_question(stem: &str, options: &[String], correct_index: usize) -> Result<(), String> {
+    if stem.trim().is_empty() {
+        return Err("Question stem is empty".to_string());
+    }
+    if options.len() < 2 {
+        return Err(format!("Question '{}' needs at least 2 options", stem));
+    }
+    if correct_index >= options.len() {
+        return Err(format!("Question '{}' has correct index {} but only {} options",
+                           stem, correct_index, options.len()));
+    }
+    Ok(())
+}
+
+/// Find a vocabulary word by text, preferring the given category
+pub(crate) fn find_word_id(conn: &Connection, word: &str, category: &str) -> SqliteResult<Option<i64>> {
+    conn.query_row(
+        concat!(
+            "SELECT id FROM vocabulary WHERE LOWER(word) = LOWER(?1) AND deleted_at IS NULL
+             ORDER BY (", category_or_default!("category"), " = ?2) DESC, id LIMIT 1"
+        ),
+        params![word.trim(), category],
+        |row| row.get(0),
+    ).optional()
+}
+
+/// Add a single authored question, returning its id
+pub fn add_custom_question(
+    conn: &Connection,
+    stem: &str,
+    options: &[String],
+    correct_index: usize,
+    word_id: Option<i64>,
+    category: &str,
+) -> Result<i64, String> {
+    validate_question(stem, options, correct_index)?;
+    
+    let options_json = serde_json::to_string(options)
+        .map_err(|e| format!("Failed to encode options: {}", e))?;
+    
+    conn.execute(
+        "INSERT INTO custom_questions (word_id, category, stem, options, correct_index) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![word_id, category, stem.trim(), options_json, correct_index as i64],
+    ).map_err(|e| format!("Failed to save question: {}", e))?;
+    
+    Ok(conn.last_insert_rowid())
+}
+
+/// Get authored questions, optionally limited to some categories
+pub fn get_custom_questions(conn: &Connection, categories: Option<&[String]>) -> SqliteResult<Vec<CustomQuestion>> {
+    let mut query = concat!(
+        "SELECT id, word_id, ", category_or_default!("category"), ", stem, options, correct_index FROM custom_questions"
+    ).to_string();
+    
+    // Questions about trashed words are left out
+    query.push_str(&format!(" WHERE (word_id IS NULL OR word_id NOT IN ({}))", DELETED_WORD_IDS));
+    if let Some(cats) = categories.filter(|c| !c.is_empty()) {
+        let cat_list: Vec<String> = cats.iter().map(|c| format!("'{}'", c.replace("'", "''"))).collect();
+        query.push_str(&format!(" AND {} IN ({})", category_or_default!("category"), cat_list.join(", ")));
+    }
+    query.push_str(" ORDER BY id");
+    
+    let mut stmt = conn.prepare(&query)?;
+    let rows = stmt.query_map([], |row| {
+        let options_json: String = row.get(4)?;
+        Ok(CustomQuestion {
+            id: row.get(0)?,
+            word_id: row.get(1)?,
+            category: row.get(2)?,
+            stem: row.get(3)?,
+            options: serde_json::from_str(&options_json).unwrap_or_default(),
+            correct_index: row.get::<_, i64>(5)? as usize,
+        })
+    })?;
+    
+    // Skip rows whose stored options no longer line up with the correct index
+    Ok(rows
+        .filter_map(|q| q.ok())
+        .filter(|q| q.correct_index < q.options.len())
+        .collect())
+}
+
+/// Delete an authored question
+pub fn delete_custom_question(conn: &Connection, question_id: i64) -> SqliteResult<bool> {
+    let deleted = conn.execute("DELETE FROM custom_questions WHERE id = ?1", params![question_id])?;
+    Ok(deleted > 0)
+}
+
+/// Parse the "correct" cell of a CSV row: 1-based number, option letter, or the option text
+fn parse_correct(value: &str, options: &[String]) -> Option<usize> {
+    let value = value.trim();
+    
+    if let Ok(n) = value.parse::<usize>() {
+        return n.checked_sub(1);
+    }
+    
+    let mut chars = value.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        if c.is_ascii_alphabetic() {
+            return Some((c.to_ascii_lowercase() as u8 - b'a') as usize);
+        }
+    }
+    
+    options.iter().position(|o| o.eq_ignore_ascii_case(value))
+}
+
+/// Read authored questions from a CSV file.
+/// Headers: Question/Stem, Option 1..N (or A..F), Correct, and optional Word and Category.
+fn read_csv_questions(file_path: &str) -> Result<Vec<QuestionRecord>, String> {
+    let mut reader = ReaderBuilder::new()
+        .has_headers(true)
+        .flexible(true)
+        .from_path(file_path)
+        .map_err(|e| format!("Failed to open CSV file: {}", e))?;
+    
+    let headers: Vec<String> = reader.headers()
+        .map_err(|e| format!("Failed to read CSV headers: {}", e))?
+        .iter()
+        .map(|h| h.trim().to_lowercase())
+        .collect();
+    
+    let mut stem_col = None;
+    let mut correct_col = None;
+    let mut word_col = None;
+    let mut category_col = None;
+    let mut option_cols = Vec::new();
+    
+    for (i, header) in headers.iter().enumerate() {
+        match header.as_str() {
+            "question" | "stem" | "prompt" => stem_col = Some(i),
+            "correct" | "answer" | "correct_index" | "correct answer" => correct_col = Some(i),
+            "word" => word_col = Some(i),
+            "category" => category_col = Some(i),
+            h if h.starts_with("option") || (h.len() == 1 && ("a"..="f").contains(&h)) => option_cols.push(i),
+            _ => {}
+        }
+    }
+    
+    let stem_col = stem_col.ok_or("Missing required 'Question' column in file header")?;
+    let correct_col = correct_col.ok_or("Missing required 'Correct' column in file header")?;
+    if option_cols.len() < 2 {
+        return Err("At least two option columns required (Option 1, Option 2, ...)".to_string());
+    }
+    
+    let mut records = Vec::new();
+    for (line, result) in reader.records().enumerate() {
+        let record = result.map_err(|e| format!("Failed to read CSV row: {}", e))?;
+        let cell = |i: usize| record.get(i).unwrap_or("").trim().to_string();
+        
+        let stem = cell(stem_col);
+        if stem.is_empty() {
+            continue;
+        }
+        
+        let options: Vec<String> = option_cols.iter().map(|&i| cell(i)).filter(|o| !o.is_empty()).collect();
+        let correct_index = parse_correct(&cell(correct_col), &options)
+            .ok_or_else(|| format!("Row {}: can't understand correct answer '{}'", line + 2, cell(correct_col)))?;
+        
+        records.push(QuestionRecord {
+            stem,
+            options,
+            correct_index,
+            word: word_col.map(cell).filter(|w| !w.is_empty()),
+            category: category_col.map(cell).filter(|c| !c.is_empty()),
+        });
+    }
+    
+    Ok(records)
+}
+
+/// Import authored questions from a CSV or JSON file into a category
+pub fn import_questions(file_path: &str, db_path: &str, category: &str) -> Result<usize, String> {
+    let extension = Path::new(file_path).extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    
+    let records = match extension.as_str() {
+        "csv" => read_csv_questions(file_path)?,
+        "json" => {
+            let text = std::fs::read_to_string(file_path)
+                .map_err(|e| format!("Failed to read JSON file: {}", e))?;
+            serde_json::from_str::<Vec<QuestionRecord>>(&text)
+                .map_err(|e| format!("Invalid question JSON: {}", e))?
+        }
+        _ => return Err(format!("Unsupported file format: .{}", extension)),
+    };
+    
+    // Validate everything up front so a bad row doesn't leave a half-imported bank
+    for record in &records {
+        validate_question(&record.stem, &record.options, record.correct_index)?;
+    }
+    
+    let mut conn = crate::db::init_database(db_path)
+        .map_err(|e| format!("Failed to init database: {}", e))?;
+    let tx = conn.transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    
+    insert_question_records(&tx, &records, category)?;
+    
+    tx.commit().map_err(|e| format!("Failed to commit import: {}", e))?;
+    Ok(records.len())
+}
+
+/// Store question records, linking them to words by text. Records without a category
+/// go to `category`.
+pub(crate) fn insert_question_records(conn: &Connection, records: &[QuestionRecord], category: &str) -> Result<(), String> {
+    for record in records {
+        let cat = record.category.as_deref().unwrap_or(category);
+        let word_id = match &record.word {
+            Some(w) => find_word_id(conn, w, cat).map_err(|e| format!("Failed to look up word: {}", e))?,
+            None => None,
+        };
+        add_custom_question(conn, &record.stem, &record.options, record.correct_index, word_id, cat)?;
+    }
+    Ok(())
+}
+
+/// Authored questions as portable records (words referenced by text)
+pub(crate) fn question_records(conn: &Connection, categories: Option<&[String]>) -> Result<Vec<QuestionRecord>, String> {
+    let questions = get_custom_questions(conn, categories)
+        .map_err(|e| format!("Failed to load questions: {}", e))?;
+    
+    let mut records = Vec::with_capacity(questions.len());
+    for q in questions {
+        let word = match q.word_id {
+            Some(id) => crate::db::get_word_by_id(conn, id)
+                .map_err(|e| format!("Failed to get word: {}", e))?
+                .map(|w| w.word),
+            None => None,
+        };
+        records.push(QuestionRecord {
+            stem: q.stem,
+            options: q.options,
+            correct_index: q.correct_index,
+            word,
+            category: Some(q.category),
+        });
+    }
+    Ok(records)
+}
+
+/// Export authored questions (optionally only some categories) to a JSON file
+pub fn export_questions(db_path: &str, out_path: &str, categories: Option<Vec<String>>) -> Result<usize, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    
+    let records = question_records(&conn, categories.as_deref())?;
+    
+    let json = serde_json::to_string_pretty(&records)
+        .map_err(|e| format!("Failed to encode questions: {}", e))?;
+    std::fs::write(out_path, json)
+        .map_err(|e| format!("Failed to write file: {}", e))?;
+    
+    Ok(records.len())
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "add_custom_question", signature = (db_path, stem, options, correct_index, word_id=None, category=None))]
+pub fn py_add_custom_question(
+    db_path: &str,
+    stem: &str,
+    options: Vec<String>,
+    correct_index: usize,
+    word_id: Option<i64>,
+    category: Option<&str>,
+) -> PyResult<i64> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    let category = category.map(String::from).unwrap_or_else(|| get_default_category(&conn));
+    add_custom_question(&conn, stem, &options, correct_index, word_id, &category)
+        .map_err(pyo3::exceptions::PyValueError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "get_custom_questions", signature = (db_path, categories=None))]
+pub fn py_get_custom_questions(db_path: &str, categories: Option<Vec<String>>) -> PyResult<Vec<CustomQuestion>> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    get_custom_questions(&conn, categories.as_deref())
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "delete_custom_question")]
+pub fn py_delete_custom_question(db_path: &str, question_id: i64) -> PyResult<bool> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    delete_custom_question(&conn, question_id)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "import_questions", signature = (file_path, db_path, category=None))]
+pub fn py_import_questions(file_path: &str, db_path: &str, category: Option<&str>) -> PyResult<usize> {
+    let category = category.map(String::from).unwrap_or_else(|| default_category_for(db_path));
+    import_questions(file_path, db_path, &category)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "export_questions", signature = (db_path, out_path, categories=None))]
+pub fn py_export_questions(db_path: &str, out_path: &str, categories: Option<Vec<String>>) -> PyResult<usize> {
+    export_questions(db_path, out_path, categories)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
@@ -4,7 +4,11 @@ use pyo3::prelude::*;
 use rand::seq::SliceRandom;
 use rand::Rng;
 use rusqlite::Connection;
-use crate::db::{Word, get_all_words, get_word_by_id};
+use std::borrow::Cow;
+use crate::content_filter::ContentFilter;
+use crate::db::{open_database, Word, get_all_words, get_word_by_id, ignored_word_ids};
+use crate::ruby::word_reading;
+use crate::srs::LearningPhase;
 
 /// MCQ Question with 4 options
 #[pyclass]
@@ -22,6 +26,22 @@ pub struct MCQQuestion {
     pub correct_index: usize,
     #[pyo3(get)]
     pub correct_answer: String,
+    /// Suggested countdown for this question in milliseconds
+    #[pyo3(get)]
+    pub recommended_time_ms: Option<i64>,
+    /// Id of the authored question in `custom_questions` (None for generated questions)
+    #[pyo3(get)]
+    pub question_id: Option<i64>,
+    /// Prompt phrased for screen readers (no quote marks or blanks, option count announced)
+    #[pyo3(get)]
+    pub accessible_prompt: String,
+    /// The target word spelled out letter by letter, for dictation-style read-outs; only
+    /// set for question types whose prompt shows the word
+    #[pyo3(get)]
+    pub spelled_word: Option<String>,
+    /// Alternative text for an image shown with the question
+    #[pyo3(get)]
+    pub alt_text: Option<String>,
 }
 
 #[pymethods]
@@ -32,6 +52,261 @@ impl MCQQuestion {
     }
 }
 
+/// Spell a word out for screen readers: "Ice-cream" -> "capital I, c, e, hyphen, c, r, e, a, m"
+pub fn spell_out(word: &str) -> String {
+    word.trim()
+        .chars()
+        .map(|c| match c {
+            ' ' => "space".to_string(),
+            '-' => "hyphen".to_string(),
+            '\'' | '’' => "apostrophe".to_string(),
+            '.' => "period".to_string(),
+            c if c.is_uppercase() => format!("capital {}", c),
+            c => c.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Rephrase a prompt for screen readers: drop quote marks used for quoting (apostrophes
+/// inside words are kept), read blanks as "blank" and announce the number of options
+pub fn accessible_prompt(question_text: &str, option_count: usize) -> String {
+    let chars: Vec<char> = question_text.chars().collect();
+    let mut out = String::new();
+    let mut underscores = 0;
+    
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' {
+            underscores += 1;
+            continue;
+        }
+        if underscores > 0 {
+            out.push_str(if underscores >= 3 { "blank" } else { "_" });
+            underscores = 0;
+        }
+        let is_quote = matches!(c, '\'' | '"' | '‘' | '’' | '“' | '”');
+        let inside_word = i > 0 && chars[i - 1].is_alphanumeric() && chars.get(i + 1).is_some_and(|n| n.is_alphanumeric());
+        if !is_quote || inside_word {
+            out.push(c);
+        }
+    }
+    if underscores > 0 {
+        out.push_str(if underscores >= 3 { "blank" } else { "_" });
+    }
+    
+    format!("{} {} options.", out.trim(), option_count)
+}
+
+/// Word field a generated question shows or asks for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuestionField {
+    Word,
+    Meaning,
+    Synonym,
+    Antonym,
+    Reading,
+}
+
+impl QuestionField {
+    pub fn name(self) -> &'static str {
+        match self {
+            QuestionField::Word => "word",
+            QuestionField::Meaning => "meaning",
+            QuestionField::Synonym => "synonym",
+            QuestionField::Antonym => "antonym",
+            QuestionField::Reading => "reading",
+        }
+    }
+    
+    /// The field's value for a word; one of its synonyms/antonyms picked at random
+    fn value(self, word: &Word) -> String {
+        match self {
+            QuestionField::Word => word.word.clone(),
+            QuestionField::Meaning => word.meaning.clone(),
+            QuestionField::Synonym => get_random_item(&word.synonyms),
+            QuestionField::Antonym => get_random_item(&word.antonyms),
+            QuestionField::Reading => word_reading(word),
+        }
+    }
+}
+
+/// What a question type needs from a word and how it is built, for picking the types
+/// a deck can be quizzed with
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct QuestionTypeInfo {
+    #[pyo3(get)]
+    pub question_type: String,
+    /// Field shown in the prompt
+    #[pyo3(get)]
+    pub prompt_field: String,
+    /// Field the options are drawn from
+    #[pyo3(get)]
+    pub answer_field: String,
+    /// Shows the word and asks about it; the only kind asked about words still in the
+    /// recognition phase (see `LearningPhase`)
+    #[pyo3(get)]
+    pub recognition: bool,
+    #[pyo3(get)]
+    pub requires_synonyms: bool,
+    #[pyo3(get)]
+    pub requires_antonyms: bool,
+    #[pyo3(get)]
+    pub requires_reading: bool,
+    /// Needs an example sentence in `examples`
+    #[pyo3(get)]
+    pub requires_example: bool,
+    /// Needs the word's `audio_path`
+    #[pyo3(get)]
+    pub requires_audio: bool,
+    /// Thinking time before reading time is added; see `recommended_time_ms`
+    #[pyo3(get)]
+    pub base_time_ms: i64,
+}
+
+#[pymethods]
+impl QuestionTypeInfo {
+    fn __repr__(&self) -> String {
+        format!("QuestionTypeInfo(question_type='{}', prompt_field='{}', answer_field='{}')",
+                self.question_type, self.prompt_field, self.answer_field)
+    }
+}
+
+/// Builds the questions of one question type. `build_mcq` looks generators up in
+/// `GENERATORS` by question type and handles distractor selection, filtering and timing,
+/// so a new type only needs a generator added there.
+pub trait QuestionGenerator: Sync {
+    fn question_type(&self) -> &'static str;
+    
+    fn info(&self) -> QuestionTypeInfo;
+    
+    /// Whether the question shows the word and asks about it
+    fn recognition(&self) -> bool;
+    
+    /// Question text and correct answer for `target` (None if the word lacks the data)
+    fn prompt(&self, conn: &Connection, target: &Word) -> Option<(String, String)>;
+    
+    /// What another word contributes as a distractor (empty if nothing)
+    fn distractor(&self, word: &Word) -> String;
+    
+    /// Thinking time for the question before reading time; indirect types need more
+    fn base_time_ms(&self) -> f64;
+}
+
+/// Question showing one field of a word and asking for another
+struct FieldQuestion {
+    question_type: &'static str,
+    prompt: QuestionField,
+    answer: QuestionField,
+    /// Question text, with "{}" standing for the prompt value
+    template: &'static str,
+    /// Prompt values are cut to this many characters
+    prompt_chars: usize,
+    base_time_ms: f64,
+}
+
+impl FieldQuestion {
+    const fn new(question_type: &'static str, prompt: QuestionField, answer: QuestionField, template: &'static str, base_time_ms: f64) -> Self {
+        FieldQuestion { question_type, prompt, answer, template, prompt_chars: usize::MAX, base_time_ms }
+    }
+    
+    const fn prompt_chars(mut self, prompt_chars: usize) -> Self {
+        self.prompt_chars = prompt_chars;
+        self
+    }
+    
+    fn uses(&self, field: QuestionField) -> bool {
+        self.prompt == field || self.answer == field
+    }
+}
+
+impl QuestionGenerator for FieldQuestion {
+    fn question_type(&self) -> &'static str {
+        self.question_type
+    }
+    
+    fn info(&self) -> QuestionTypeInfo {
+        QuestionTypeInfo {
+            question_type: self.question_type.to_string(),
+            prompt_field: self.prompt.name().to_string(),
+            answer_field: self.answer.name().to_string(),
+            recognition: self.recognition(),
+            requires_synonyms: self.uses(QuestionField::Synonym),
+            requires_antonyms: self.uses(QuestionField::Antonym),
+            requires_reading: self.uses(QuestionField::Reading),
+            requires_example: false,
+            requires_audio: false,
+            base_time_ms: self.base_time_ms as i64,
+        }
+    }
+    
+    fn recognition(&self) -> bool {
+        self.prompt == QuestionField::Word
+    }
+    
+    fn prompt(&self, _conn: &Connection, target: &Word) -> Option<(String, String)> {
+        let shown: String = self.prompt.value(target).chars().take(self.prompt_chars).collect();
+        let answer = self.answer.value(target);
+        if shown.is_empty() || answer.is_empty() {
+            return None;
+        }
+        Some((self.template.replacen("{}", &shown, 1), answer))
+    }
+    
+    fn distractor(&self, word: &Word) -> String {
+        self.answer.value(word)
+    }
+    
+    fn base_time_ms(&self) -> f64 {
+        self.base_time_ms
+    }
+}
+
+use QuestionField::{Antonym, Meaning, Reading, Synonym, Word as WordField};
+
+/// Generators of all supported question types, in the order quizzes draw them
+static GENERATORS: &[&dyn QuestionGenerator] = &[
+    &FieldQuestion::new("word_to_meaning", WordField, Meaning, "What is the meaning of '{}'?", 4_000.0),
+    &FieldQuestion::new("meaning_to_word", Meaning, WordField, "Which word means: '{}'?", 4_000.0).prompt_chars(100),
+    &FieldQuestion::new("word_to_synonym", WordField, Synonym, "Which is a synonym of '{}'?", 5_000.0),
+    &FieldQuestion::new("word_to_antonym", WordField, Antonym, "Which is an antonym of '{}'?", 5_000.0),
+    &FieldQuestion::new("synonym_to_word", Synonym, WordField, "Which word has the synonym '{}'?", 5_000.0),
+    &FieldQuestion::new("antonym_to_word", Antonym, WordField, "Which word has the antonym '{}'?", 5_000.0),
+    &FieldQuestion::new("synonym_to_meaning", Synonym, Meaning, "What is the meaning of the word with synonym '{}'?", 7_000.0),
+    &FieldQuestion::new("antonym_to_meaning", Antonym, Meaning, "What is the meaning of the word with antonym '{}'?", 7_000.0),
+    &FieldQuestion::new("meaning_to_synonym", Meaning, Synonym, "Which is a synonym of the word meaning: '{}'?", 7_000.0).prompt_chars(80),
+    &FieldQuestion::new("meaning_to_antonym", Meaning, Antonym, "Which is an antonym of the word meaning: '{}'?", 7_000.0).prompt_chars(80),
+    &FieldQuestion::new("synonym_to_antonym", Synonym, Antonym, "Which is an antonym of the word with synonym '{}'?", 7_000.0),
+    &FieldQuestion::new("antonym_to_synonym", Antonym, Synonym, "Which is a synonym of the word with antonym '{}'?", 7_000.0),
+    &FieldQuestion::new("word_to_reading", WordField, Reading, "How is '{}' read?", 4_000.0),
+    &FieldQuestion::new("reading_to_word", Reading, WordField, "Which word is read '{}'?", 4_000.0),
+];
+
+/// The generator for a question type
+pub fn question_generator(question_type: &str) -> Option<&'static dyn QuestionGenerator> {
+    GENERATORS.iter().copied().find(|g| g.question_type() == question_type)
+}
+
+/// All supported generated question types
+pub fn all_question_types() -> Vec<&'static str> {
+    GENERATORS.iter().map(|g| g.question_type()).collect()
+}
+
+/// The question types of `types` a word in `phase` may be asked
+pub fn types_for_phase<'a>(types: &[&'a str], phase: LearningPhase) -> Vec<&'a str> {
+    match phase {
+        LearningPhase::Recall => types.to_vec(),
+        LearningPhase::Recognition => types.iter().copied()
+            .filter(|t| question_generator(t).is_some_and(|g| g.recognition()))
+            .collect(),
+    }
+}
+
+/// Capabilities of every supported question type
+pub fn list_question_types() -> Vec<QuestionTypeInfo> {
+    GENERATORS.iter().map(|g| g.info()).collect()
+}
+
 /// Get random item from comma-separated list
 fn get_random_item(csv: &str) -> String {
     let items: Vec<&str> = csv.split(',')
@@ -48,101 +323,127 @@ fn get_random_item(csv: &str) -> String {
     items[idx].to_string()
 }
 
-/// Get the field value for creating distractors
-fn get_field_for_type(word: &Word, q_type: &str) -> String {
-    match q_type {
-        "word_to_meaning" | "synonym_to_meaning" | "antonym_to_meaning" => word.meaning.clone(),
-        "meaning_to_word" | "synonym_to_word" | "antonym_to_word" => word.word.clone(),
-        "word_to_synonym" | "meaning_to_synonym" | "antonym_to_synonym" => get_random_item(&word.synonyms),
-        "word_to_antonym" | "meaning_to_antonym" | "synonym_to_antonym" => get_random_item(&word.antonyms),
-        _ => word.meaning.clone(),
+/// Reading speed used for time limits (roughly 20 characters per second)
+const MS_PER_CHAR: f64 = 50.0;
+const MIN_TIME_MS: i64 = 5_000;
+const MAX_TIME_MS: i64 = 60_000;
+/// Thinking time for question types without a generator (e.g. authored questions)
+const DEFAULT_BASE_TIME_MS: f64 = 7_000.0;
+
+/// Recommend a time limit from question type, text/option lengths and how often the word was missed
+pub fn recommended_time_ms(q_type: &str, question_text: &str, options: &[String], error_rate: f64) -> i64 {
+    let chars = question_text.chars().count() + options.iter().map(|o| o.chars().count()).sum::<usize>();
+    let difficulty = 1.0 + 0.5 * error_rate.clamp(0.0, 1.0);
+    let base = question_generator(q_type).map_or(DEFAULT_BASE_TIME_MS, |g| g.base_time_ms());
+    let ms = (base + chars as f64 * MS_PER_CHAR) * difficulty;
+    
+    // Round to whole seconds so countdowns look tidy
+    (((ms / 1000.0).round() as i64) * 1000).clamp(MIN_TIME_MS, MAX_TIME_MS)
+}
+
+/// Share of incorrect answers for a word (0.0 until it has been attempted a few times)
+pub(crate) fn word_error_rate(conn: &Connection, word_id: i64) -> f64 {
+    conn.query_row(
+        "SELECT COUNT(*), COALESCE(SUM(is_correct), 0) FROM attempts WHERE word_id = ?1",
+        [word_id],
+        |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)),
+    )
+    .map(|(total, correct)| if total >= 3 { (total - correct) as f64 / total as f64 } else { 0.0 })
+    .unwrap_or(0.0)
+}
+
+/// Which words distractors may be drawn from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DistractorScope {
+    /// Only words from the target word's category
+    SameCategory,
+    /// Words from the selected categories (all categories if none are selected)
+    SelectedCategories,
+    /// Any word in the database
+    All,
+}
+
+impl DistractorScope {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "same_category" => Ok(DistractorScope::SameCategory),
+            "selected_categories" => Ok(DistractorScope::SelectedCategories),
+            "" | "all" => Ok(DistractorScope::All),
+            _ => Err(format!("Unknown distractor scope: {}", name)),
+        }
+    }
+    
+    /// Narrow a word pool to the words allowed as distractors for `target`
+    pub fn filter_pool<'a>(self, pool: &'a [Word], target: &Word, selected: Option<&[String]>) -> Cow<'a, [Word]> {
+        match (self, selected) {
+            (DistractorScope::SameCategory, _) => {
+                Cow::Owned(pool.iter().filter(|w| w.category == target.category).cloned().collect())
+            }
+            (DistractorScope::SelectedCategories, Some(selected)) if !selected.is_empty() => {
+                Cow::Owned(pool.iter().filter(|w| selected.contains(&w.category)).cloned().collect())
+            }
+            _ => Cow::Borrowed(pool),
+        }
     }
 }
 
-/// Generate an MCQ question for a given word
-pub fn generate_mcq(db_path: &str, word_id: i64, question_type: &str) -> Result<MCQQuestion, String> {
-    let conn = Connection::open(db_path)
+/// Generate an MCQ question for a given word, with distractors limited to `scope`
+pub fn generate_mcq(
+    db_path: &str,
+    word_id: i64,
+    question_type: &str,
+    scope: DistractorScope,
+    categories: Option<&[String]>,
+) -> Result<MCQQuestion, String> {
+    let conn = open_database(db_path)
         .map_err(|e| format!("Failed to open database: {}", e))?;
     
     let target = get_word_by_id(&conn, word_id)
         .map_err(|e| format!("Failed to get word: {}", e))?
         .ok_or("Word not found")?;
     
-    let all_words = get_all_words(&conn)
+    let mut all_words = get_all_words(&conn)
         .map_err(|e| format!("Failed to get all words: {}", e))?;
+    let ignored = ignored_word_ids(&conn)
+        .map_err(|e| format!("Failed to get ignored words: {}", e))?;
+    all_words.retain(|w| !ignored.contains(&w.id));
     
-    if all_words.len() < 4 {
+    let pool = scope.filter_pool(&all_words, &target, categories);
+    build_mcq(&conn, &target, &pool, question_type)
+}
+
+/// Build an MCQ question for a target word, drawing distractors from a pre-loaded word pool
+pub fn build_mcq(conn: &Connection, target: &Word, pool: &[Word], question_type: &str) -> Result<MCQQuestion, String> {
+    if pool.len() < 4 {
         return Err("Not enough words for MCQ generation (need at least 4)".to_string());
     }
     
+    let generator = question_generator(question_type)
+        .ok_or_else(|| format!("Unknown question type: {}", question_type))?;
     let mut rng = rand::thread_rng();
     
-    // Build question text and get correct answer
-    let (question_text, correct_answer) = match question_type {
-        "word_to_meaning" => (
-            format!("What is the meaning of '{}'?", target.word),
-            target.meaning.clone(),
-        ),
-        "meaning_to_word" => (
-            format!("Which word means: '{}'?", &target.meaning.chars().take(100).collect::<String>()),
-            target.word.clone(),
-        ),
-        "word_to_synonym" => (
-            format!("Which is a synonym of '{}'?", target.word),
-            get_random_item(&target.synonyms),
-        ),
-        "word_to_antonym" => (
-            format!("Which is an antonym of '{}'?", target.word),
-            get_random_item(&target.antonyms),
-        ),
-        "synonym_to_word" => (
-            format!("Which word has the synonym '{}'?", get_random_item(&target.synonyms)),
-            target.word.clone(),
-        ),
-        "antonym_to_word" => (
-            format!("Which word has the antonym '{}'?", get_random_item(&target.antonyms)),
-            target.word.clone(),
-        ),
-        "synonym_to_meaning" => (
-            format!("What is the meaning of the word with synonym '{}'?", get_random_item(&target.synonyms)),
-            target.meaning.clone(),
-        ),
-        "antonym_to_meaning" => (
-            format!("What is the meaning of the word with antonym '{}'?", get_random_item(&target.antonyms)),
-            target.meaning.clone(),
-        ),
-        "meaning_to_synonym" => (
-            format!("Which is a synonym of the word meaning: '{}'?", &target.meaning.chars().take(80).collect::<String>()),
-            get_random_item(&target.synonyms),
-        ),
-        "meaning_to_antonym" => (
-            format!("Which is an antonym of the word meaning: '{}'?", &target.meaning.chars().take(80).collect::<String>()),
-            get_random_item(&target.antonyms),
-        ),
-        "synonym_to_antonym" => (
-            format!("Which is an antonym of the word with synonym '{}'?", get_random_item(&target.synonyms)),
-            get_random_item(&target.antonyms),
-        ),
-        "antonym_to_synonym" => (
-            format!("Which is a synonym of the word with antonym '{}'?", get_random_item(&target.antonyms)),
-            get_random_item(&target.synonyms),
-        ),
-        _ => return Err(format!("Unknown question type: {}", question_type)),
-    };
-    
-    if correct_answer.is_empty() {
-        return Err(format!("Missing data for question type: {}", question_type));
+    let filter = ContentFilter::load(conn);
+    if filter.excludes_word(target) {
+        return Err("Word is excluded by the content filter".to_string());
     }
     
+    let (question_text, correct_answer) = generator.prompt(conn, target)
+        .ok_or_else(|| format!("Missing data for question type: {}", question_type))?;
+    
     // Collect distractors from other words
-    let mut distractors: Vec<String> = all_words
+    let mut distractors: Vec<String> = pool
         .iter()
-        .filter(|w| w.id != target.id)
-        .map(|w| get_field_for_type(w, question_type))
+        .filter(|w| w.id != target.id && !filter.excludes_word(w))
+        .map(|w| generator.distractor(w))
         .filter(|s| !s.is_empty() && s != &correct_answer)
         .collect();
     
     distractors.shuffle(&mut rng);
+    
+    // Prefer distractors that weren't part of a question the user flagged as bad
+    if let Ok(flagged) = crate::feedback::flagged_distractors(conn, target.id, question_type) {
+        distractors.sort_by_key(|d| flagged.contains(d));
+    }
     distractors.truncate(3);
     
     if distractors.len() < 3 {
@@ -154,6 +455,9 @@ pub fn generate_mcq(db_path: &str, word_id: i64, question_type: &str) -> Result<
     let mut options = distractors;
     options.insert(correct_index, correct_answer.clone());
     
+    let recommended = recommended_time_ms(question_type, &question_text, &options, word_error_rate(conn, target.id));
+    let accessible = accessible_prompt(&question_text, options.len());
+    
     Ok(MCQQuestion {
         word_id: target.id,
         question_type: question_type.to_string(),
@@ -161,14 +465,33 @@ pub fn generate_mcq(db_path: &str, word_id: i64, question_type: &str) -> Result<
         options,
         correct_index,
         correct_answer,
+        recommended_time_ms: Some(recommended),
+        question_id: None,
+        accessible_prompt: accessible,
+        spelled_word: generator.recognition().then(|| spell_out(&target.word)),
+        alt_text: None,
     })
 }
 
 // ============= Python Binding =============
 
 #[pyfunction]
-#[pyo3(name = "generate_mcq")]
-pub fn py_generate_mcq(db_path: &str, word_id: i64, question_type: &str) -> PyResult<MCQQuestion> {
-    generate_mcq(db_path, word_id, question_type)
-        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e))
+#[pyo3(name = "generate_mcq", signature = (db_path, word_id, question_type, distractor_scope="all", categories=None))]
+pub fn py_generate_mcq(
+    db_path: &str,
+    word_id: i64,
+    question_type: &str,
+    distractor_scope: &str,
+    categories: Option<Vec<String>>,
+) -> PyResult<MCQQuestion> {
+    let scope = DistractorScope::parse(distractor_scope)
+        .map_err(pyo3::exceptions::PyValueError::new_err)?;
+    generate_mcq(db_path, word_id, question_type, scope, categories.as_deref())
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "list_question_types")]
+pub fn py_list_question_types() -> Vec<QuestionTypeInfo> {
+    list_question_types()
 }
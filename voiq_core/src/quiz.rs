@@ -0,0 +1,420 @@
+//! Quiz building - selects words and mixes generated and authored questions
+
+use pyo3::prelude::*;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rusqlite::Connection;
+use std::collections::HashSet;
+use std::time::Duration;
+use crate::composer::{compose, interleave, CompositionConstraints, DifficultyCurve, QuizItem};
+use crate::content_filter::ContentFilter;
+use crate::db::{open_database, get_all_words, get_pinned_words, get_recently_added_words, get_word_by_id, get_words, ignored_word_ids, unpin_word, Word};
+use crate::feedback::{flagged_custom_questions, flagged_type_counts};
+use crate::progress::{get_known_word_ids, get_session_misses};
+use crate::question_bank::{get_custom_questions, CustomQuestion};
+use crate::questions::{all_question_types, build_mcq, types_for_phase, word_error_rate, DistractorScope, MCQQuestion};
+use crate::srs::{learning_phases, LearningPhase};
+
+/// Options controlling how a quiz is assembled
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct QuizOptions {
+    /// Number of questions to build, including pinned, warm-up and cool-down questions
+    #[pyo3(get, set)]
+    pub count: usize,
+    /// Categories to draw words and authored questions from (None = all)
+    #[pyo3(get, set)]
+    pub categories: Option<Vec<String>>,
+    /// Question types to generate (empty = all supported types)
+    #[pyo3(get, set)]
+    pub question_types: Vec<String>,
+    /// Fraction of the quiz taken from authored questions when available (0.0 - 1.0)
+    #[pyo3(get, set)]
+    pub custom_share: f64,
+    /// Search selections/orderings for category coverage, difficulty curve and confusable spacing
+    #[pyo3(get, set)]
+    pub optimize: bool,
+    /// Difficulty shape used when optimizing: "flat", "ascending" or "peak"
+    #[pyo3(get, set)]
+    pub difficulty_curve: String,
+    /// Time the optimizer may spend searching
+    #[pyo3(get, set)]
+    pub time_budget_ms: u64,
+    /// Space out questions from the same category and about related words
+    #[pyo3(get, set)]
+    pub interleave: bool,
+    /// Number of easy, already-known words to open the quiz with (0 disables warm-up)
+    #[pyo3(get, set)]
+    pub warmup_count: usize,
+    /// End with a recap of words missed earlier in `session_id`
+    #[pyo3(get, set)]
+    pub cooldown: bool,
+    /// Session the quiz belongs to (used by cool-down)
+    #[pyo3(get, set)]
+    pub session_id: Option<String>,
+    /// Where distractors come from: "same_category", "selected_categories" or "all"
+    #[pyo3(get, set)]
+    pub distractor_scope: String,
+    /// Only ask about words added in the last this many days (None = any age);
+    /// distractors still come from the whole vocabulary
+    #[pyo3(get, set)]
+    pub added_within_days: Option<f64>,
+    /// Only ask about words with these parts of speech, e.g. ["verb"] (None = any)
+    #[pyo3(get, set)]
+    pub parts_of_speech: Option<Vec<String>>,
+    /// Only ask about starred words (pinned words are still asked)
+    #[pyo3(get, set)]
+    pub starred_only: bool,
+}
+
+impl Default for QuizOptions {
+    fn default() -> Self {
+        QuizOptions {
+            count: 20,
+            categories: None,
+            question_types: Vec::new(),
+            custom_share: 0.5,
+            optimize: false,
+            difficulty_curve: "flat".to_string(),
+            time_budget_ms: 250,
+            interleave: false,
+            warmup_count: 0,
+            cooldown: false,
+            session_id: None,
+            distractor_scope: "all".to_string(),
+            added_within_days: None,
+            parts_of_speech: None,
+            starred_only: false,
+        }
+    }
+}
+
+#[pymethods]
+impl QuizOptions {
+    #[new]
+    #[pyo3(signature = (count=20, categories=None, question_types=Vec::new(), custom_share=0.5,
+                        optimize=false, difficulty_curve="flat".to_string(), time_budget_ms=250, interleave=false,
+                        warmup_count=0, cooldown=false, session_id=None,
+                        distractor_scope="all".to_string(), added_within_days=None,
+                        parts_of_speech=None, starred_only=false))]
+    #[allow(clippy::too_many_arguments)]
+    fn py_new(
+        count: usize,
+        categories: Option<Vec<String>>,
+        question_types: Vec<String>,
+        custom_share: f64,
+        optimize: bool,
+        difficulty_curve: String,
+        time_budget_ms: u64,
+        interleave: bool,
+        warmup_count: usize,
+        cooldown: bool,
+        session_id: Option<String>,
+        distractor_scope: String,
+        added_within_days: Option<f64>,
+        parts_of_speech: Option<Vec<String>>,
+        starred_only: bool,
+    ) -> Self {
+        QuizOptions {
+            count, categories, question_types, custom_share,
+            optimize, difficulty_curve, time_budget_ms, interleave,
+            warmup_count, cooldown, session_id, distractor_scope, added_within_days,
+            parts_of_speech, starred_only,
+        }
+    }
+    
+    fn __repr__(&self) -> String {
+        format!("QuizOptions(count={}, categories={:?})", self.count, self.categories)
+    }
+}
+
+/// A word counts as known for warm-up after this many attempts at this accuracy
+const WARMUP_MIN_ATTEMPTS: i64 = 2;
+const WARMUP_MIN_ACCURACY: f64 = 0.8;
+
+/// Question types used for warm-up: plain recognition
+const WARMUP_TYPES: &[&str] = &["word_to_meaning", "meaning_to_word"];
+
+/// When optimizing, generate this many candidates per slot for the composer to choose from
+const OPTIMIZER_POOL_FACTOR: usize = 3;
+
+/// Build a quiz: pinned words first, authored questions up to `custom_share`, generated MCQs for the rest.
+/// Words in the recognition phase are only asked recognition types.
+pub fn build_quiz(db_path: &str, options: &QuizOptions) -> Result<Vec<MCQQuestion>, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    
+    let curve = DifficultyCurve::parse(&options.difficulty_curve)?;
+    let scope = DistractorScope::parse(&options.distractor_scope)?;
+    let selected = options.categories.as_deref();
+    
+    let mut rng = rand::thread_rng();
+    
+    // Ignored words are neither asked nor offered as options
+    let ignored = ignored_word_ids(&conn)
+        .map_err(|e| format!("Failed to get ignored words: {}", e))?;
+    let mut words = get_words(&conn, "random", None, options.categories.clone(), None, options.parts_of_speech.as_deref(), None, options.starred_only)
+        .map_err(|e| format!("Failed to get words: {}", e))?;
+    words.retain(|w| !ignored.contains(&w.id));
+    let mut pool = get_all_words(&conn)
+        .map_err(|e| format!("Failed to get all words: {}", e))?;
+    pool.retain(|w| !ignored.contains(&w.id));
+    
+    // "Learn what I just imported": only recent words are asked about
+    let recent: Option<HashSet<i64>> = match options.added_within_days {
+        Some(days) => {
+            let recent = get_recently_added_words(&conn, days, None)
+                .map_err(|e| format!("Failed to get recently added words: {}", e))?;
+            Some(recent.iter().map(|w| w.id).collect())
+        }
+        None => None,
+    };
+    if let Some(recent) = &recent {
+        words.retain(|w| recent.contains(&w.id));
+    }
+    
+    let types: Vec<&str> = if options.question_types.is_empty() {
+        all_question_types()
+    } else {
+        options.question_types.iter().map(|t| t.as_str()).collect()
+    };
+    
+    // Pinned words are always asked; they're kept out of the selection below so
+    // neither the optimizer nor the count can drop them
+    let pinned_words = get_pinned_words(&conn, selected)
+        .map_err(|e| format!("Failed to get pinned words: {}", e))?;
+    
+    // New words only get recognition questions until they graduate
+    let word_ids: Vec<i64> = words.iter().chain(&pinned_words).map(|w| w.id).collect();
+    let phases = learning_phases(&conn, &word_ids)
+        .map_err(|e| format!("Failed to get learning phases: {}", e))?;
+    let types_for = |word_id: i64| types_for_phase(&types, phases.get(&word_id).copied().unwrap_or(LearningPhase::Recall));
+    
+    let pinned: Vec<MCQQuestion> = pinned_words.iter()
+        .filter(|w| !ignored.contains(&w.id))
+        .filter_map(|w| {
+            let distractors = scope.filter_pool(&pool, w, selected);
+            let mut candidates = types_for(w.id);
+            candidates.shuffle(&mut rng);
+            candidates.iter().find_map(|t| build_mcq(&conn, w, &distractors, t).ok())
+        })
+        .take(options.count)
+        .collect();
+    
+    // Warm-up and cool-down questions are part of `count`, reserved before the main selection.
+    // Warm-up: a few easy, already-known words
+    let mut warmup = Vec::new();
+    if options.warmup_count > 0 {
+        let known = get_known_word_ids(&conn, WARMUP_MIN_ATTEMPTS, WARMUP_MIN_ACCURACY)
+            .map_err(|e| format!("Failed to get known words: {}", e))?;
+        warmup = known.iter()
+            .filter(|id| !pinned.iter().any(|q| q.word_id == **id))
+            .filter_map(|id| words.iter().find(|w| w.id == *id))
+            .filter_map(|w| {
+                let distractors = scope.filter_pool(&pool, w, selected);
+                let phase = phases.get(&w.id).copied().unwrap_or(LearningPhase::Recall);
+                types_for_phase(WARMUP_TYPES, phase).iter().find_map(|t| build_mcq(&conn, w, &distractors, t).ok())
+            })
+            .take(options.warmup_count.min(options.count - pinned.len()))
+            .collect();
+    }
+    
+    // Cool-down: recap words missed earlier in this session with a fresh question
+    let mut cooldown = Vec::new();
+    if let (true, Some(session_id)) = (options.cooldown, options.session_id.as_deref()) {
+        cooldown = missed_variants(&conn, session_id, &types, &pool, scope, selected)?;
+        cooldown.retain(|q| !pinned.iter().chain(&warmup).any(|p| p.word_id == q.word_id));
+        cooldown.truncate(options.count - pinned.len() - warmup.len());
+    }
+    
+    let count = options.count - pinned.len() - warmup.len() - cooldown.len();
+    let target = if options.optimize { count * OPTIMIZER_POOL_FACTOR } else { count };
+    
+    let mut custom = get_custom_questions(&conn, options.categories.as_deref())
+        .map_err(|e| format!("Failed to get authored questions: {}", e))?;
+    custom.shuffle(&mut rng);
+    
+    let filter = ContentFilter::load(&conn);
+    custom.retain(|q| !filter.excludes_question(q) && !q.word_id.is_some_and(|id| ignored.contains(&id)));
+    if let Some(recent) = &recent {
+        custom.retain(|q| q.word_id.is_some_and(|id| recent.contains(&id)));
+    }
+    if options.starred_only || options.parts_of_speech.as_ref().is_some_and(|p| !p.is_empty()) {
+        custom.retain(|q| q.word_id.is_some_and(|id| words.iter().any(|w| w.id == id)));
+    }
+    
+    // Flagged authored questions are only used when nothing better is left
+    let flagged_custom = flagged_custom_questions(&conn)
+        .map_err(|e| format!("Failed to get question ratings: {}", e))?;
+    custom.sort_by_key(|q| flagged_custom.contains(&q.id));
+    
+    let custom_target = ((count as f64) * options.custom_share.clamp(0.0, 1.0)).round() as usize;
+    let mut custom = custom.into_iter();
+    let custom_item = |q: CustomQuestion| -> QuizItem {
+        let word = q.word_id.and_then(|id| words.iter().find(|w| w.id == id));
+        QuizItem {
+            word: word.map(|w| w.word.clone()).unwrap_or_default(),
+            difficulty: q.word_id.map(|id| word_error_rate(&conn, id)).unwrap_or(0.0),
+            category: q.category.clone(),
+            question: q.to_mcq(),
+        }
+    };
+    let mut items: Vec<QuizItem> = custom.by_ref().take(custom_target).map(custom_item).collect();
+    
+    // Words already covered by a pinned, authored, warm-up or cool-down question aren't asked again
+    let covered: HashSet<i64> = items.iter().map(|i| i.question.word_id)
+        .chain(pinned.iter().map(|q| q.word_id))
+        .chain(warmup.iter().map(|q| q.word_id))
+        .chain(cooldown.iter().map(|q| q.word_id))
+        .collect();
+    
+    for word in words.iter().filter(|w| !covered.contains(&w.id)) {
+        if items.len() >= target {
+            break;
+        }
+        
+        // Try types in random order; words missing synonyms/antonyms fall back to other types,
+        // and types the user flagged for this word are tried last
+        let mut candidates = types_for(word.id);
+        candidates.shuffle(&mut rng);
+        let flagged_types = flagged_type_counts(&conn, word.id)
+            .map_err(|e| format!("Failed to get question ratings: {}", e))?;
+        candidates.sort_by_key(|t| flagged_types.get(*t).copied().unwrap_or(0));
+        let distractors = scope.filter_pool(&pool, word, selected);
+        if let Some(question) = candidates.iter().find_map(|t| build_mcq(&conn, word, &distractors, t).ok()) {
+            items.push(QuizItem {
+                word: word.word.clone(),
+                category: word.category.clone(),
+                difficulty: word_error_rate(&conn, word.id),
+                question,
+            });
+        }
+    }
+    
+    // Top up with remaining authored questions if there weren't enough words
+    let missing = target.saturating_sub(items.len());
+    items.extend(custom.take(missing).map(custom_item));
+    
+    let items = if options.optimize {
+        let constraints = CompositionConstraints {
+            difficulty_curve: curve,
+            interleave_categories: options.interleave,
+            ..Default::default()
+        };
+        compose(items, count, &constraints, Duration::from_millis(options.time_budget_ms))
+    } else {
+        items.shuffle(&mut rng);
+        if options.interleave {
+            interleave(items, CompositionConstraints::default().confusable_threshold)
+        } else {
+            items
+        }
+    };
+    
+    let mut quiz: Vec<MCQQuestion> = items.into_iter().map(|i| i.question).collect();
+    
+    // Pinned questions go to random positions, and their words are unpinned once included
+    for question in pinned {
+        unpin_word(&conn, question.word_id)
+            .map_err(|e| format!("Failed to unpin word: {}", e))?;
+        let position = rng.gen_range(0..=quiz.len());
+        quiz.insert(position, question);
+    }
+    
+    // Warm-up questions open the quiz
+    quiz.splice(0..0, warmup);
+    
+    // Cool-down questions close it
+    quiz.extend(cooldown);
+    
+    Ok(quiz)
+}
+
+/// Fresh questions for words missed in a session: a different question type than the
+/// one that was missed where the word's data allows it, always with newly drawn options
+fn missed_variants(
+    conn: &Connection,
+    session_id: &str,
+    types: &[&str],
+    pool: &[Word],
+    scope: DistractorScope,
+    selected: Option<&[String]>,
+) -> Result<Vec<MCQQuestion>, String> {
+    let misses = get_session_misses(conn, session_id)
+        .map_err(|e| format!("Failed to get session attempts: {}", e))?;
+    
+    let word_ids: Vec<i64> = misses.iter().map(|(id, _)| *id).collect();
+    let phases = learning_phases(conn, &word_ids)
+        .map_err(|e| format!("Failed to get learning phases: {}", e))?;
+    
+    let mut rng = rand::thread_rng();
+    let mut questions = Vec::new();
+    
+    for (word_id, missed_type) in misses {
+        let Some(word) = get_word_by_id(conn, word_id).map_err(|e| format!("Failed to get word: {}", e))? else {
+            continue;
+        };
+        // The pool leaves out ignored words
+        if !pool.iter().any(|w| w.id == word_id) {
+            continue;
+        }
+        
+        let mut candidates = types_for_phase(types, phases.get(&word_id).copied().unwrap_or(LearningPhase::Recall));
+        candidates.shuffle(&mut rng);
+        candidates.sort_by_key(|t| *t == missed_type);
+        
+        let distractors = scope.filter_pool(pool, &word, selected);
+        if let Some(question) = candidates.iter().find_map(|t| build_mcq(conn, &word, &distractors, t).ok()) {
+            questions.push(question);
+        }
+    }
+    
+    Ok(questions)
+}
+
+/// Generate fresh variants of the words answered wrong so far in a session
+pub fn requeue_missed(
+    db_path: &str,
+    session_id: &str,
+    question_types: Option<Vec<String>>,
+    scope: DistractorScope,
+) -> Result<Vec<MCQQuestion>, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    
+    let ignored = ignored_word_ids(&conn)
+        .map_err(|e| format!("Failed to get ignored words: {}", e))?;
+    let mut pool = get_all_words(&conn)
+        .map_err(|e| format!("Failed to get all words: {}", e))?;
+    pool.retain(|w| !ignored.contains(&w.id));
+    
+    let types: Vec<&str> = match &question_types {
+        Some(t) if !t.is_empty() => t.iter().map(|t| t.as_str()).collect(),
+        _ => all_question_types(),
+    };
+    
+    missed_variants(&conn, session_id, &types, &pool, scope, None)
+}
+
+// ============= Python Binding =============
+
+#[pyfunction]
+#[pyo3(name = "build_quiz", signature = (db_path, options=None))]
+pub fn py_build_quiz(db_path: &str, options: Option<QuizOptions>) -> PyResult<Vec<MCQQuestion>> {
+    build_quiz(db_path, &options.unwrap_or_default())
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "requeue_missed", signature = (db_path, session_id, question_types=None, distractor_scope="all"))]
+pub fn py_requeue_missed(
+    db_path: &str,
+    session_id: &str,
+    question_types: Option<Vec<String>>,
+    distractor_scope: &str,
+) -> PyResult<Vec<MCQQuestion>> {
+    let scope = DistractorScope::parse(distractor_scope)
+        .map_err(pyo3::exceptions::PyValueError::new_err)?;
+    requeue_missed(db_path, session_id, question_types, scope)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
@@ -0,0 +1,138 @@
+//! Weekly progress report as self-contained HTML (inline CSS and SVG charts)
+
+use pyo3::prelude::*;
+use chrono::{Duration, Local};
+use rusqlite::{Connection, params};
+use std::collections::HashMap;
+use crate::charts::{bar_chart_svg, escape_xml};
+use crate::progress::{get_daily_attempt_counts, summarize_times, DEFAULT_MAX_TIME_MS};
+use crate::sessions::get_daily_study_time;
+use crate::db::{open_database, DELETED_WORD_IDS, IGNORED_WORD_IDS};
+
+/// Number of failed words listed in the report
+const TOP_FAILED_LIMIT: usize = 10;
+
+/// Most-missed words in the report window as (word, meaning, misses)
+fn top_failed(conn: &Connection, since: &str) -> rusqlite::Result<Vec<(String, String, i64)>> {
+    let mut stmt = conn.prepare(
+        "SELECT v.word, v.meaning, COUNT(*) AS misses
+         FROM attempts a JOIN vocabulary v ON v.id = a.word_id
+         WHERE a.is_correct = 0 AND v.ignored = 0 AND v.deleted_at IS NULL AND date(a.attempted_at, 'localtime') >= ?1
+         GROUP BY v.id ORDER BY misses DESC, v.word LIMIT ?2"
+    )?;
+    let rows = stmt.query_map(params![since, TOP_FAILED_LIMIT as i64], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+    Ok(rows.filter_map(|r| r.ok()).collect())
+}
+
+/// Response times in the report window
+fn response_times(conn: &Connection, since: &str) -> rusqlite::Result<Vec<i64>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT time_taken_ms FROM attempts
+         WHERE time_taken_ms IS NOT NULL AND date(attempted_at, 'localtime') >= ?1
+           AND word_id NOT IN ({}) AND word_id NOT IN ({})",
+        IGNORED_WORD_IDS, DELETED_WORD_IDS
+    ))?;
+    let rows = stmt.query_map(params![since], |row| row.get(0))?;
+    Ok(rows.filter_map(|r| r.ok()).collect())
+}
+
+/// Render the last 7 days of activity as a self-contained HTML document suitable for email
+pub fn render_weekly_report_html(db_path: &str) -> Result<String, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    
+    let today = Local::now().date_naive();
+    let days: Vec<_> = (0..7).rev().map(|i| today - Duration::days(i)).collect();
+    let since = days[0].format("%Y-%m-%d").to_string();
+    
+    let attempts_by_day = get_daily_attempt_counts(&conn, &since)
+        .map_err(|e| format!("Failed to get attempts: {}", e))?;
+    let study_by_day: HashMap<String, i64> = get_daily_study_time(db_path, 7)?
+        .into_iter()
+        .map(|d| (d.date, d.total_seconds))
+        .collect();
+    let failed = top_failed(&conn, &since)
+        .map_err(|e| format!("Failed to get failed words: {}", e))?;
+    let times = response_times(&conn, &since)
+        .map_err(|e| format!("Failed to get response times: {}", e))?;
+    let time_summary = summarize_times(times, &Default::default());
+    
+    let keys: Vec<String> = days.iter().map(|d| d.format("%Y-%m-%d").to_string()).collect();
+    let labels: Vec<String> = days.iter().map(|d| d.format("%a").to_string()).collect();
+    
+    let study_minutes: Vec<f64> = keys.iter()
+        .map(|k| (study_by_day.get(k).copied().unwrap_or(0) as f64 / 60.0).round())
+        .collect();
+    let accuracy: Vec<f64> = keys.iter()
+        .map(|k| match attempts_by_day.get(k) {
+            Some(&(total, correct)) if total > 0 => (correct as f64 / total as f64 * 100.0).round(),
+            _ => 0.0,
+        })
+        .collect();
+    
+    let total_attempts: i64 = attempts_by_day.values().map(|(t, _)| t).sum();
+    let total_correct: i64 = attempts_by_day.values().map(|(_, c)| c).sum();
+    let week_accuracy = if total_attempts > 0 { total_correct as f64 / total_attempts as f64 * 100.0 } else { 0.0 };
+    let total_minutes: f64 = study_minutes.iter().sum();
+    let active_days = keys.iter()
+        .filter(|k| attempts_by_day.contains_key(*k) || study_by_day.contains_key(*k))
+        .count();
+    
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>VoIQ Weekly Report</title>");
+    html.push_str("<style>body{font-family:sans-serif;color:#333;max-width:640px;margin:auto;padding:16px}\
+                   h1{font-size:22px}.stats{display:flex;gap:12px;flex-wrap:wrap}\
+                   .stat{background:#f3f5fa;border-radius:6px;padding:10px 14px}\
+                   .stat b{display:block;font-size:20px}table{border-collapse:collapse;width:100%}\
+                   td,th{border-bottom:1px solid #e5e5e5;padding:6px;text-align:left}</style></head><body>");
+    html.push_str(&format!(
+        "<h1>VoIQ Weekly Report</h1><p>{} &ndash; {}</p>",
+        days[0].format("%b %d"), today.format("%b %d, %Y"),
+    ));
+    
+    html.push_str("<div class=\"stats\">");
+    for (label, value) in [
+        ("Questions answered", total_attempts.to_string()),
+        ("Accuracy", format!("{:.0}%", week_accuracy)),
+        ("Study time", format!("{:.0} min", total_minutes)),
+        ("Active days", format!("{}/7", active_days)),
+        ("Avg response", format!("{:.1} s", time_summary.mean_ms / 1000.0)),
+    ] {
+        html.push_str(&format!("<div class=\"stat\"><b>{}</b>{}</div>", escape_xml(&value), label));
+    }
+    html.push_str("</div>");
+    
+    html.push_str(&bar_chart_svg("Study time per day (minutes)", &labels, &study_minutes, None, ""));
+    html.push_str(&bar_chart_svg("Accuracy per day", &labels, &accuracy, Some(100.0), "%"));
+    
+    html.push_str("<h2>Words to review</h2>");
+    if failed.is_empty() {
+        html.push_str("<p>No missed words this week. Great job!</p>");
+    } else {
+        html.push_str("<table><tr><th>Word</th><th>Meaning</th><th>Misses</th></tr>");
+        for (word, meaning, misses) in &failed {
+            html.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape_xml(word), escape_xml(meaning), misses,
+            ));
+        }
+        html.push_str("</table>");
+    }
+    
+    html.push_str(&format!(
+        "<p style=\"color:#888;font-size:12px\">Response times over {} s are capped. Generated {}.</p>",
+        DEFAULT_MAX_TIME_MS / 1000, Local::now().format("%Y-%m-%d %H:%M"),
+    ));
+    html.push_str("</body></html>");
+    
+    Ok(html)
+}
+
+// ============= Python Binding =============
+
+#[pyfunction]
+#[pyo3(name = "render_weekly_report_html")]
+pub fn py_render_weekly_report_html(db_path: &str) -> PyResult<String> {
+    render_weekly_report_html(db_path)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
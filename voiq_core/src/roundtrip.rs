@@ -0,0 +1,227 @@
+//! Bulk editing in a spreadsheet.
+//!
+//! `export_for_editing` writes a category to a CSV or XLSX file with each word's id in a
+//! hidden "VoIQ ID" column. After the file has been edited (cells changed, rows added or
+//! removed), `reimport_edited_file` matches rows back to words by that id: changed rows
+//! update their word, rows without a known id become new words and words whose row is
+//! gone move to the trash. Columns removed from the file leave their field untouched.
+//! New rows only get ids in a fresh export, so export again before the next round.
+
+use pyo3::prelude::*;
+use std::collections::{HashMap, HashSet};
+use crate::db::{
+    get_words, load_vocabulary_for_import, open_database, set_difficulty, soft_delete_words, update_word,
+    LoadMode, Word, WordUpdate, DIFFICULTY_LEVELS,
+};
+use crate::excel::{read_words, ColumnMapping};
+use crate::journal::{begin_operation, commit_operation, OP_IMPORT};
+use crate::spreadsheet::{write_table, SheetFormat, Table, Validation};
+
+/// Header of the column holding word ids
+pub const ID_COLUMN: &str = "VoIQ ID";
+
+/// Columns written by `export_for_editing` after the id
+const EDIT_COLUMNS: &[&str] = &["Word", "Meaning", "Synonyms", "Antonyms", "Reading", "Notes", "POS", "Difficulty"];
+
+/// A word whose row was edited
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct WordChange {
+    #[pyo3(get)]
+    pub before: Word,
+    #[pyo3(get)]
+    pub after: Word,
+    /// Changed columns, e.g. ["meaning", "difficulty"]
+    #[pyo3(get)]
+    pub fields: Vec<String>,
+}
+
+#[pymethods]
+impl WordChange {
+    fn __repr__(&self) -> String {
+        format!("WordChange(word='{}', fields={:?})", self.before.word, self.fields)
+    }
+}
+
+/// Differences between an edited file and its category
+#[pyclass]
+#[derive(Debug, Clone, Default)]
+pub struct ReimportDiff {
+    /// Rows without the id of a word in the category
+    #[pyo3(get)]
+    pub added: Vec<Word>,
+    #[pyo3(get)]
+    pub updated: Vec<WordChange>,
+    /// Words whose row was removed
+    #[pyo3(get)]
+    pub deleted: Vec<Word>,
+    /// False for a preview
+    #[pyo3(get)]
+    pub applied: bool,
+}
+
+#[pymethods]
+impl ReimportDiff {
+    /// True if the file matches the category
+    #[getter]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.updated.is_empty() && self.deleted.is_empty()
+    }
+    
+    fn __repr__(&self) -> String {
+        format!("ReimportDiff(added={}, updated={}, deleted={}, applied={})",
+                self.added.len(), self.updated.len(), self.deleted.len(), self.applied)
+    }
+}
+
+/// Write a category's words to `out_path` for editing in a spreadsheet program.
+/// Returns the number of words written.
+pub fn export_for_editing(db_path: &str, category: &str, out_path: &str, format: SheetFormat) -> Result<usize, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let words = get_words(&conn, "a_to_z", None, Some(vec![category.to_string()]), None, None, None, false)
+        .map_err(|e| format!("Failed to get words: {}", e))?;
+    if words.is_empty() {
+        return Err(format!("Category '{}' is empty", category));
+    }
+    
+    let headers = std::iter::once(ID_COLUMN).chain(EDIT_COLUMNS.iter().copied()).map(String::from).collect();
+    let rows = words.iter().map(|w| vec![
+        w.id.to_string(), w.word.clone(), w.meaning.clone(), w.synonyms.clone(), w.antonyms.clone(),
+        w.reading.clone(), w.notes.clone(), w.pos.clone(), w.difficulty.map(|d| d.to_string()).unwrap_or_default(),
+    ]).collect();
+    let table = Table {
+        headers,
+        rows,
+        hidden: vec![0],
+        validations: vec![Validation::WholeNumber {
+            column: EDIT_COLUMNS.len(),
+            min: *DIFFICULTY_LEVELS.start(),
+            max: *DIFFICULTY_LEVELS.end(),
+            message: format!("Difficulty is a whole number from {} to {}", DIFFICULTY_LEVELS.start(), DIFFICULTY_LEVELS.end()),
+        }],
+    };
+    write_table(out_path, format, &table)?;
+    Ok(words.len())
+}
+
+/// Columns present in the file whose value differs between `before` and `after`
+fn changed_fields(mapping: &ColumnMapping, before: &Word, after: &Word) -> Vec<String> {
+    let text = |column: Option<usize>, a: &str, b: &str| column.is_some() && a.trim() != b.trim();
+    [
+        ("word", text(Some(mapping.word), &before.word, &after.word)),
+        ("meaning", text(mapping.meaning, &before.meaning, &after.meaning)),
+        ("synonyms", text(mapping.synonyms, &before.synonyms, &after.synonyms)),
+        ("antonyms", text(mapping.antonyms, &before.antonyms, &after.antonyms)),
+        ("reading", text(mapping.reading, &before.reading, &after.reading)),
+        ("notes", text(mapping.notes, &before.notes, &after.notes)),
+        ("pos", text(mapping.pos, &before.pos, &after.pos)),
+        ("difficulty", mapping.difficulty.is_some() && before.difficulty != after.difficulty),
+    ]
+    .into_iter()
+    .filter(|(_, changed)| *changed)
+    .map(|(field, _)| field.to_string())
+    .collect()
+}
+
+/// Compare an edited file with the category it was exported from
+fn diff_edited_file(conn: &rusqlite::Connection, file_path: &str, category: &str) -> Result<ReimportDiff, String> {
+    let (mapping, rows) = read_words(file_path)?;
+    if mapping.id.is_none() {
+        return Err(format!("File has no '{}' column; export the category with export_for_editing first", ID_COLUMN));
+    }
+    let words = get_words(conn, "a_to_z", None, Some(vec![category.to_string()]), None, None, None, false)
+        .map_err(|e| format!("Failed to get words: {}", e))?;
+    let mut by_id: HashMap<i64, Word> = words.into_iter().map(|w| (w.id, w)).collect();
+    
+    let mut diff = ReimportDiff::default();
+    let mut matched = HashSet::new();
+    for mut row in rows {
+        // A copied row keeps its id; only the first row with an id edits that word
+        match by_id.get(&row.id).filter(|_| matched.insert(row.id)) {
+            Some(before) => {
+                let fields = changed_fields(&mapping, before, &row);
+                if !fields.is_empty() {
+                    diff.updated.push(WordChange { before: before.clone(), after: row, fields });
+                }
+            }
+            None => {
+                row.id = 0;
+                row.category = category.to_string();
+                diff.added.push(row);
+            }
+        }
+    }
+    by_id.retain(|id, _| !matched.contains(id));
+    diff.deleted = by_id.into_values().collect();
+    diff.deleted.sort_by_key(|w| w.id);
+    Ok(diff)
+}
+
+/// Apply the edits in a file written by `export_for_editing` to `category` in one
+/// transaction, or with `preview` only report them. Deleted words go to the trash. The
+/// reimport is journaled as an import, so `undo_import` removes the words it added; edits
+/// stay and trashed words are restored from the trash.
+pub fn reimport_edited_file(file_path: &str, db_path: &str, category: &str, preview: bool) -> Result<ReimportDiff, String> {
+    let mut conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let mut diff = diff_edited_file(&conn, file_path, category)?;
+    if preview || diff.is_empty() {
+        return Ok(diff);
+    }
+    
+    let tx = conn.transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let journal_id = begin_operation(&tx, OP_IMPORT, Some(category), Some(file_path))
+        .map_err(|e| format!("Failed to write import journal: {}", e))?;
+    load_vocabulary_for_import(&tx, diff.added.clone(), category, Some(journal_id), LoadMode::Append)
+        .map_err(|e| format!("Failed to add words: {}", e))?;
+    for change in &diff.updated {
+        let has = |field: &str| change.fields.iter().any(|f| f == field);
+        let pick = |field: &str, value: &str| has(field).then(|| value.trim().to_string());
+        let update = WordUpdate {
+            word: pick("word", &change.after.word),
+            meaning: pick("meaning", &change.after.meaning),
+            synonyms: pick("synonyms", &change.after.synonyms),
+            antonyms: pick("antonyms", &change.after.antonyms),
+            reading: pick("reading", &change.after.reading),
+            notes: pick("notes", &change.after.notes),
+            pos: pick("pos", &change.after.pos),
+            ..Default::default()
+        };
+        update_word(&tx, change.before.id, &update)
+            .map_err(|e| format!("Failed to update '{}': {}", change.before.word, e))?;
+        if has("difficulty") {
+            set_difficulty(&tx, change.before.id, change.after.difficulty)
+                .map_err(|e| format!("Failed to update '{}': {}", change.before.word, e))?;
+        }
+    }
+    let deleted: Vec<i64> = diff.deleted.iter().map(|w| w.id).collect();
+    soft_delete_words(&tx, &deleted)
+        .map_err(|e| format!("Failed to delete words: {}", e))?;
+    let rows = diff.added.len() + diff.updated.len() + diff.deleted.len();
+    commit_operation(&tx, journal_id, rows)
+        .map_err(|e| format!("Failed to write import journal: {}", e))?;
+    tx.commit().map_err(|e| format!("Failed to commit changes: {}", e))?;
+    
+    diff.applied = true;
+    Ok(diff)
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "export_for_editing", signature = (db_path, category, out_path, format=None))]
+pub fn py_export_for_editing(db_path: &str, category: &str, out_path: &str, format: Option<&str>) -> PyResult<usize> {
+    let format = format.map_or_else(|| SheetFormat::from_path(out_path), SheetFormat::parse)
+        .map_err(pyo3::exceptions::PyValueError::new_err)?;
+    export_for_editing(db_path, category, out_path, format)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "reimport_edited_file", signature = (file_path, db_path, category, preview=false))]
+pub fn py_reimport_edited_file(file_path: &str, db_path: &str, category: &str, preview: bool) -> PyResult<ReimportDiff> {
+    reimport_edited_file(file_path, db_path, category, preview)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
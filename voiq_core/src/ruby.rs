@@ -0,0 +1,167 @@
+//! Ruby (furigana) annotations in bracket notation, e.g. "日本[にほん]語[ご]"
+
+use pyo3::prelude::*;
+use crate::db::{open_database, get_word_by_id, Word};
+
+/// A run of text with its optional reading
+#[pyclass]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RubySegment {
+    #[pyo3(get)]
+    pub base: String,
+    /// Reading shown above the base; None for plain text (kana, punctuation)
+    #[pyo3(get)]
+    pub reading: Option<String>,
+}
+
+#[pymethods]
+impl RubySegment {
+    fn __repr__(&self) -> String {
+        match &self.reading {
+            Some(reading) => format!("RubySegment(base='{}', reading='{}')", self.base, reading),
+            None => format!("RubySegment(base='{}')", self.base),
+        }
+    }
+}
+
+/// CJK ideographs (and iteration marks) that take a reading when not space-delimited
+fn is_kanji(c: char) -> bool {
+    matches!(c, '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' | '\u{F900}'..='\u{FAFF}' | '々' | '〆' | 'ヶ')
+}
+
+/// Append plain text, merging with a preceding plain segment
+fn push_plain(segments: &mut Vec<RubySegment>, text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    match segments.last_mut() {
+        Some(last) if last.reading.is_none() => last.base.push_str(text),
+        _ => segments.push(RubySegment { base: text.to_string(), reading: None }),
+    }
+}
+
+/// Parse bracket notation into base/reading segments.
+///
+/// The base of a `[reading]` is the run of kanji right before it, or everything since
+/// the previous space when one is used as a separator ("お 茶[ちゃ]" is equivalent to
+/// "お茶[ちゃ]"; " お茶[おちゃ]" needs the space so the base covers the kana). Separator spaces are
+/// dropped, other spaces kept; unmatched brackets are kept as text.
+pub fn parse_ruby(text: &str) -> Vec<RubySegment> {
+    let mut segments = Vec::new();
+    let mut pending = String::new();
+    let mut after_space = false;
+    let mut chars = text.chars();
+    
+    while let Some(c) = chars.next() {
+        match c {
+            '[' => {
+                let rest: String = chars.clone().collect();
+                let Some(end) = rest.find(']') else {
+                    pending.push(c);
+                    continue;
+                };
+                let reading = rest[..end].trim().to_string();
+                for _ in rest[..=end].chars() {
+                    chars.next();
+                }
+                
+                let split_at = if after_space {
+                    0
+                } else {
+                    pending.char_indices()
+                        .rev()
+                        .take_while(|(_, ch)| is_kanji(*ch))
+                        .last()
+                        .map(|(i, _)| i)
+                        .unwrap_or(pending.len())
+                };
+                let base = pending.split_off(split_at);
+                
+                if after_space && base.is_empty() {
+                    push_plain(&mut segments, " ");
+                }
+                push_plain(&mut segments, &pending);
+                if base.is_empty() || reading.is_empty() {
+                    push_plain(&mut segments, &format!("{}[{}]", base, reading));
+                } else {
+                    segments.push(RubySegment { base, reading: Some(reading) });
+                }
+                pending.clear();
+                after_space = false;
+            }
+            ' ' => {
+                if after_space {
+                    push_plain(&mut segments, " ");
+                }
+                push_plain(&mut segments, &pending);
+                pending.clear();
+                after_space = true;
+            }
+            _ => pending.push(c),
+        }
+    }
+    
+    if after_space {
+        push_plain(&mut segments, " ");
+    }
+    push_plain(&mut segments, &pending);
+    segments
+}
+
+/// Plain text of bracket notation (the bases only): "日本[にほん]語[ご]" -> "日本語"
+pub fn ruby_base(text: &str) -> String {
+    parse_ruby(text).into_iter().map(|s| s.base).collect()
+}
+
+/// Reading of bracket notation (bases replaced by readings): "日本[にほん]語[ご]" -> "にほんご"
+pub fn ruby_reading(text: &str) -> String {
+    parse_ruby(text).into_iter().map(|s| s.reading.unwrap_or(s.base)).collect()
+}
+
+/// Segments for a word: its `reading` in bracket notation, or the whole word annotated
+/// with a plain reading. Words without a reading give a single plain segment.
+pub fn word_ruby(word: &Word) -> Vec<RubySegment> {
+    if word.reading.is_empty() {
+        vec![RubySegment { base: word.word.clone(), reading: None }]
+    } else if word.reading.contains('[') {
+        parse_ruby(&word.reading)
+    } else {
+        vec![RubySegment { base: word.word.clone(), reading: Some(word.reading.clone()) }]
+    }
+}
+
+/// Full reading of a word (empty when it has none)
+pub fn word_reading(word: &Word) -> String {
+    if word.reading.contains('[') {
+        ruby_reading(&word.reading)
+    } else {
+        word.reading.clone()
+    }
+}
+
+/// Furigana segments for a stored word
+pub fn get_furigana(db_path: &str, word_id: i64) -> Result<Vec<RubySegment>, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    
+    let word = get_word_by_id(&conn, word_id)
+        .map_err(|e| format!("Failed to get word: {}", e))?
+        .ok_or("Word not found")?;
+    
+    Ok(word_ruby(&word))
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "parse_ruby")]
+pub fn py_parse_ruby(text: &str) -> Vec<RubySegment> {
+    parse_ruby(text)
+}
+
+#[pyfunction]
+#[pyo3(name = "get_furigana")]
+pub fn py_get_furigana(db_path: &str, word_id: i64) -> PyResult<Vec<RubySegment>> {
+    get_furigana(db_path, word_id)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
@@ -0,0 +1,223 @@
+//! Synthetic sample databases for benchmarks and frontend integration tests.
+//!
+//! `create_sample_database` fills a new database with made-up words spread over a few
+//! decks and, depending on the attempts profile, a history of past study sessions.
+//! Everything is drawn from a seeded generator, so the same arguments always produce
+//! the same file contents (apart from timestamps, which are relative to today).
+
+use pyo3::prelude::*;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rusqlite::params;
+use std::collections::HashSet;
+use std::path::Path;
+use crate::collation::word_key;
+use crate::db::{init_database, DIFFICULTY_LEVELS};
+use crate::progress::{insert_attempt, AttemptRecord};
+use crate::srs::read_state;
+
+/// Decks sample words are spread over, in turn
+pub const SAMPLE_DECKS: &[&str] = &["Basics", "Travel", "Business", "Academic"];
+
+const SYLLABLES: &[&str] = &[
+    "ka", "lo", "mi", "ten", "ra", "vo", "sul", "ne", "dar", "pi", "quo", "bel",
+    "tor", "fi", "gan", "ze", "mur", "sa", "lin", "ok", "pre", "vis", "um", "cor",
+];
+const ADJECTIVES: &[&str] = &[
+    "small", "bright", "quiet", "ancient", "sharp", "gentle", "hollow", "rapid", "bitter", "loyal",
+];
+const NOUNS: &[&str] = &[
+    "river", "lantern", "promise", "harbor", "ledger", "meadow", "journey", "contract", "window", "theory",
+];
+
+/// How much study history a sample database gets
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AttemptsProfile {
+    /// Words only
+    None,
+    /// A couple of weeks of occasional short sessions
+    Beginner,
+    /// Two months of near-daily sessions
+    Regular,
+    /// Two months of sessions with low accuracy, for failed-word and cram views
+    Struggling,
+}
+
+impl AttemptsProfile {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "none" => Ok(AttemptsProfile::None),
+            "beginner" => Ok(AttemptsProfile::Beginner),
+            "" | "regular" => Ok(AttemptsProfile::Regular),
+            "struggling" => Ok(AttemptsProfile::Struggling),
+            _ => Err(format!("Unknown attempts profile: {}", name)),
+        }
+    }
+    
+    /// (days of history, chance of studying on a day, most answers per session, base accuracy)
+    fn shape(self) -> (u32, f64, usize, f64) {
+        match self {
+            AttemptsProfile::None => (0, 0.0, 0, 0.0),
+            AttemptsProfile::Beginner => (14, 0.5, 10, 0.6),
+            AttemptsProfile::Regular => (60, 0.85, 25, 0.75),
+            AttemptsProfile::Struggling => (60, 0.7, 20, 0.45),
+        }
+    }
+}
+
+/// What `create_sample_database` generated
+#[pyclass]
+#[derive(Debug, Clone, Default)]
+pub struct SampleDatabase {
+    #[pyo3(get)]
+    pub words: usize,
+    #[pyo3(get)]
+    pub attempts: usize,
+    #[pyo3(get)]
+    pub sessions: usize,
+}
+
+#[pymethods]
+impl SampleDatabase {
+    fn __repr__(&self) -> String {
+        format!("SampleDatabase(words={}, attempts={}, sessions={})", self.words, self.attempts, self.sessions)
+    }
+}
+
+/// A made-up word of two or three syllables not in `taken`
+fn sample_word(rng: &mut StdRng, taken: &mut HashSet<String>) -> String {
+    loop {
+        let syllables = rng.gen_range(2..=3);
+        let word: String = (0..syllables).map(|_| *SYLLABLES.choose(rng).unwrap_or(&"a")).collect();
+        if taken.insert(word.clone()) {
+            return word;
+        }
+        // Fall back to numbered words once the syllable space gets crowded
+        let numbered = format!("{}{}", word, taken.len());
+        if taken.insert(numbered.clone()) {
+            return numbered;
+        }
+    }
+}
+
+/// Create a new database at `path` with `words` synthetic words and the study history of
+/// `profile`, generated from `seed`. Refuses to touch an existing file.
+pub fn create_sample_database(path: &str, words: usize, profile: AttemptsProfile, seed: u64) -> Result<SampleDatabase, String> {
+    if Path::new(path).exists() {
+        return Err(format!("Database already exists: {}", path));
+    }
+    let mut conn = init_database(path)
+        .map_err(|e| format!("Failed to create database: {}", e))?;
+    let tx = conn.transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut summary = SampleDatabase { words, ..Default::default() };
+    
+    // (id, word, meaning, difficulty)
+    let mut vocabulary: Vec<(i64, String, String, i64)> = Vec::with_capacity(words);
+    let mut taken = HashSet::new();
+    for i in 0..words {
+        let word = sample_word(&mut rng, &mut taken);
+        let meaning = format!("{} {}", ADJECTIVES.choose(&mut rng).unwrap_or(&""), NOUNS.choose(&mut rng).unwrap_or(&""));
+        let synonyms = match vocabulary.choose(&mut rng) {
+            Some((_, other, _, _)) if rng.gen_bool(0.3) => other.clone(),
+            _ => String::new(),
+        };
+        let difficulty = rng.gen_range(DIFFICULTY_LEVELS);
+        tx.execute(
+            "INSERT INTO vocabulary (word, meaning, synonyms, antonyms, category, difficulty, frequency_rank, word_key)
+             VALUES (?1, ?2, ?3, '', ?4, ?5, ?6, ?7)",
+            params![word, meaning, synonyms, SAMPLE_DECKS[i % SAMPLE_DECKS.len()], difficulty, i as i64 + 1, word_key(&word)],
+        ).map_err(|e| format!("Failed to add word: {}", e))?;
+        vocabulary.push((tx.last_insert_rowid(), word, meaning, difficulty));
+    }
+    
+    let (history_days, study_chance, session_length, base_accuracy) = profile.shape();
+    let now = chrono::Utc::now();
+    let mut seen = vec![0usize; vocabulary.len()];
+    // Day of the history each word falls due on, as scheduled by its last answer
+    let mut due_day = vec![0u32; vocabulary.len()];
+    for days_ago in (1..=history_days).rev() {
+        if vocabulary.is_empty() || !rng.gen_bool(study_chance) {
+            continue;
+        }
+        // New words are introduced gradually over the history; sessions only take
+        // words that are due, like a learner following the schedule would
+        let day = history_days - days_ago;
+        let introduced = (vocabulary.len() * (day as usize + 1)).div_ceil(history_days as usize).max(1);
+        let mut queue: Vec<usize> = (0..introduced).filter(|&i| due_day[i] <= day).collect();
+        queue.shuffle(&mut rng);
+        queue.truncate(session_length);
+        if queue.is_empty() {
+            continue;
+        }
+        let session_id = format!("sample-{}", days_ago);
+        let mut at = now - chrono::Duration::days(days_ago as i64)
+            + chrono::Duration::minutes(rng.gen_range(-180..180));
+        
+        for index in queue {
+            let (word_id, word, meaning, difficulty) = &vocabulary[index];
+            // Harder words are missed more often; practice helps
+            let accuracy = base_accuracy - 0.08 * (*difficulty - 3) as f64 + (0.04 * seen[index] as f64).min(0.2);
+            let is_correct = rng.gen_bool(accuracy.clamp(0.05, 0.98));
+            let (question_type, expected) = if rng.gen_bool(0.5) {
+                ("word_to_meaning", meaning.clone())
+            } else {
+                ("meaning_to_word", word.clone())
+            };
+            let user_answer = if is_correct {
+                expected.clone()
+            } else {
+                let (_, other_word, other_meaning, _) = &vocabulary[rng.gen_range(0..vocabulary.len())];
+                if question_type == "word_to_meaning" { other_meaning.clone() } else { other_word.clone() }
+            };
+            let time_taken_ms = rng.gen_range(1_500..6_000) + 1_000 * *difficulty + if is_correct { 0 } else { 3_000 };
+            at += chrono::Duration::milliseconds(time_taken_ms + rng.gen_range(500..3_000));
+            
+            insert_attempt(&tx, &AttemptRecord {
+                word_id: *word_id,
+                mode: if rng.gen_bool(0.6) { "mcq" } else { "typing" }.to_string(),
+                question_type: question_type.to_string(),
+                is_correct,
+                user_answer,
+                expected_answer: expected,
+                time_taken_ms: Some(time_taken_ms),
+                client_context: None,
+                session_id: Some(session_id.clone()),
+                first_answer: None,
+                profile_id: None,
+                attempted_at: Some(at.format("%Y-%m-%d %H:%M:%S").to_string()),
+            }).map_err(|e| format!("Failed to add attempt: {}", e))?;
+            let interval = read_state(&tx, *word_id)
+                .map_err(|e| format!("Failed to read schedule: {}", e))?
+                .map_or(0.0, |s| s.interval_days);
+            due_day[index] = day + (interval.round() as u32).max(1);
+            seen[index] += 1;
+            summary.attempts += 1;
+        }
+        summary.sessions += 1;
+    }
+    
+    // Reviews were scheduled as if taken today; move them back to their attempt times
+    tx.execute(
+        "UPDATE srs_state SET
+            last_review_at = (SELECT MAX(a.attempted_at) FROM attempts a WHERE a.word_id = srs_state.word_id),
+            due_at = datetime((SELECT MAX(a.attempted_at) FROM attempts a WHERE a.word_id = srs_state.word_id),
+                              '+' || interval_days || ' days')",
+        [],
+    ).map_err(|e| format!("Failed to backdate schedule: {}", e))?;
+    
+    tx.commit().map_err(|e| format!("Failed to commit sample database: {}", e))?;
+    Ok(summary)
+}
+
+// ============= Python Binding =============
+
+#[pyfunction]
+#[pyo3(name = "create_sample_database", signature = (path, words=200, attempts_profile="regular", seed=42))]
+pub fn py_create_sample_database(path: &str, words: usize, attempts_profile: &str, seed: u64) -> PyResult<SampleDatabase> {
+    let profile = AttemptsProfile::parse(attempts_profile).map_err(pyo3::exceptions::PyValueError::new_err)?;
+    create_sample_database(path, words, profile, seed)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
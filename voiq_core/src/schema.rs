@@ -0,0 +1,210 @@
+//! Schema introspection so frontends can adapt to older database files
+
+use pyo3::prelude::*;
+use rusqlite::{Connection, OpenFlags, params};
+use std::path::Path;
+use crate::db::{open_database, open_database_with_flags};
+
+/// Optional features and the table whose presence enables each of them
+pub const FEATURE_TABLES: &[(&str, &str)] = &[
+    ("tags", "word_tags"),
+    ("senses", "senses"),
+    ("srs", "srs_state"),
+];
+
+/// Column description from `PRAGMA table_info`
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct ColumnInfo {
+    #[pyo3(get)]
+    pub name: String,
+    #[pyo3(get)]
+    pub data_type: String,
+    #[pyo3(get)]
+    pub not_null: bool,
+    #[pyo3(get)]
+    pub default_value: Option<String>,
+    #[pyo3(get)]
+    pub primary_key: bool,
+}
+
+/// Table description with its columns
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct TableInfo {
+    #[pyo3(get)]
+    pub name: String,
+    #[pyo3(get)]
+    pub columns: Vec<ColumnInfo>,
+}
+
+#[pymethods]
+impl TableInfo {
+    /// Check whether the table has a column with the given name
+    pub fn has_column(&self, column: &str) -> bool {
+        self.columns.iter().any(|c| c.name == column)
+    }
+    
+    fn __repr__(&self) -> String {
+        format!("TableInfo(name='{}', columns={})", self.name, self.columns.len())
+    }
+}
+
+/// Full schema description of a database file
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct SchemaInfo {
+    #[pyo3(get)]
+    pub tables: Vec<TableInfo>,
+    /// Names of optional features whose tables exist (see `FEATURE_TABLES`)
+    #[pyo3(get)]
+    pub features: Vec<String>,
+}
+
+#[pymethods]
+impl SchemaInfo {
+    /// Check whether an optional feature (e.g. "tags", "srs") is available
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.features.iter().any(|f| f == feature)
+    }
+    
+    /// Look up a table by name
+    pub fn table(&self, name: &str) -> Option<TableInfo> {
+        self.tables.iter().find(|t| t.name == name).cloned()
+    }
+    
+    fn __repr__(&self) -> String {
+        format!("SchemaInfo(tables={}, features={:?})", self.tables.len(), self.features)
+    }
+}
+
+/// What `open_with_upgrade` changed in a database file
+#[pyclass]
+#[derive(Debug, Clone, Default)]
+pub struct UpgradeReport {
+    /// Path of the pre-upgrade backup, if one was made
+    #[pyo3(get)]
+    pub backup_path: Option<String>,
+    #[pyo3(get)]
+    pub created_tables: Vec<String>,
+    /// Added columns as "table.column"
+    #[pyo3(get)]
+    pub added_columns: Vec<String>,
+}
+
+#[pymethods]
+impl UpgradeReport {
+    /// True if any table or column was added
+    #[getter]
+    pub fn changed(&self) -> bool {
+        !self.created_tables.is_empty() || !self.added_columns.is_empty()
+    }
+    
+    fn __repr__(&self) -> String {
+        format!("UpgradeReport(created_tables={:?}, added_columns={:?})", 
+                self.created_tables, self.added_columns)
+    }
+}
+
+/// Read the schema of an open connection
+pub fn read_schema(conn: &Connection) -> rusqlite::Result<SchemaInfo> {
+    let mut stmt = conn.prepare(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name"
+    )?;
+    let names: Vec<String> = stmt.query_map([], |row| row.get(0))?
+        .filter_map(|n| n.ok())
+        .collect();
+    
+    let mut tables = Vec::new();
+    for name in names {
+        let mut col_stmt = conn.prepare(&format!("PRAGMA table_info(\"{}\")", name.replace('"', "\"\"")))?;
+        let columns: Vec<ColumnInfo> = col_stmt.query_map([], |row| {
+            Ok(ColumnInfo {
+                name: row.get(1)?,
+                data_type: row.get(2)?,
+                not_null: row.get::<_, i64>(3)? != 0,
+                default_value: row.get(4)?,
+                primary_key: row.get::<_, i64>(5)? != 0,
+            })
+        })?
+        .filter_map(|c| c.ok())
+        .collect();
+        
+        tables.push(TableInfo { name, columns });
+    }
+    
+    let features = FEATURE_TABLES.iter()
+        .filter(|(_, table)| tables.iter().any(|t| t.name == *table))
+        .map(|(feature, _)| feature.to_string())
+        .collect();
+    
+    Ok(SchemaInfo { tables, features })
+}
+
+/// Describe tables, columns and available optional features of a database file
+pub fn describe_schema(db_path: &str) -> Result<SchemaInfo, String> {
+    // Open read-only so describing a missing file doesn't create it
+    let conn = open_database_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_URI)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    
+    read_schema(&conn).map_err(|e| format!("Failed to read schema: {}", e))
+}
+
+/// Open a (possibly legacy) database, optionally back it up, and bring its schema up to date.
+/// Reports exactly which tables and columns were added.
+pub fn open_with_upgrade(db_path: &str, backup_first: bool) -> Result<UpgradeReport, String> {
+    let existed = Path::new(db_path).exists();
+    
+    let before = if existed {
+        Some(describe_schema(db_path)?)
+    } else {
+        None
+    };
+    
+    let mut report = UpgradeReport::default();
+    
+    if backup_first && existed {
+        let backup_path = format!("{}.{}.bak", db_path, chrono::Local::now().format("%Y%m%d%H%M%S"));
+        let conn = open_database(db_path)
+            .map_err(|e| format!("Failed to open database: {}", e))?;
+        conn.execute("VACUUM INTO ?1", params![backup_path])
+            .map_err(|e| format!("Failed to back up database: {}", e))?;
+        report.backup_path = Some(backup_path);
+    }
+    
+    let conn = crate::db::init_database(db_path)
+        .map_err(|e| format!("Failed to upgrade database: {}", e))?;
+    let after = read_schema(&conn)
+        .map_err(|e| format!("Failed to read schema: {}", e))?;
+    
+    for table in &after.tables {
+        match before.as_ref().and_then(|b| b.table(&table.name)) {
+            None => report.created_tables.push(table.name.clone()),
+            Some(old) => {
+                for column in &table.columns {
+                    if !old.has_column(&column.name) {
+                        report.added_columns.push(format!("{}.{}", table.name, column.name));
+                    }
+                }
+            }
+        }
+    }
+    
+    Ok(report)
+}
+
+// ============= Python Binding =============
+
+#[pyfunction]
+#[pyo3(name = "describe_schema")]
+pub fn py_describe_schema(db_path: &str) -> PyResult<SchemaInfo> {
+    describe_schema(db_path)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "open_with_upgrade", signature = (db_path, backup_first=true))]
+pub fn py_open_with_upgrade(db_path: &str, backup_first: bool) -> PyResult<UpgradeReport> {
+    open_with_upgrade(db_path, backup_first)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
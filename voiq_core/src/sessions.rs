@@ -0,0 +1,259 @@
+//! Study sessions - passive study time logging and daily aggregation
+
+use pyo3::prelude::*;
+use rusqlite::params;
+use crate::progress::DEFAULT_MAX_TIME_MS;
+use crate::db::open_database;
+
+/// Study time for one calendar day (local time)
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct DailyStudyTime {
+    /// Date as YYYY-MM-DD
+    #[pyo3(get)]
+    pub date: String,
+    /// Time logged for passive activities (browsing flashcards, reading examples)
+    #[pyo3(get)]
+    pub logged_seconds: i64,
+    /// Time spent answering questions, from attempt response times
+    #[pyo3(get)]
+    pub answering_seconds: i64,
+    #[pyo3(get)]
+    pub total_seconds: i64,
+}
+
+#[pymethods]
+impl DailyStudyTime {
+    fn __repr__(&self) -> String {
+        format!("DailyStudyTime(date='{}', total={}s)", self.date, self.total_seconds)
+    }
+}
+
+/// Study time per activity
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct ActivityTime {
+    #[pyo3(get)]
+    pub activity: String,
+    #[pyo3(get)]
+    pub seconds: i64,
+}
+
+#[pymethods]
+impl ActivityTime {
+    fn __repr__(&self) -> String {
+        format!("ActivityTime(activity='{}', seconds={})", self.activity, self.seconds)
+    }
+}
+
+/// Gaps between answers longer than this are treated as idle time by default
+pub const DEFAULT_IDLE_THRESHOLD_SECS: i64 = 300;
+
+/// Summary of one quiz session computed from its attempts
+#[pyclass]
+#[derive(Debug, Clone, Default)]
+pub struct SessionSummary {
+    #[pyo3(get)]
+    pub session_id: String,
+    #[pyo3(get)]
+    pub attempts: i64,
+    #[pyo3(get)]
+    pub correct_count: i64,
+    #[pyo3(get)]
+    pub accuracy_percent: f64,
+    #[pyo3(get)]
+    pub started_at: Option<String>,
+    #[pyo3(get)]
+    pub ended_at: Option<String>,
+    /// Time between the first and last attempt, including idle gaps
+    #[pyo3(get)]
+    pub wall_seconds: i64,
+    /// Time actually spent studying, with idle gaps removed
+    #[pyo3(get)]
+    pub active_seconds: i64,
+    /// Number of gaps longer than the idle threshold
+    #[pyo3(get)]
+    pub idle_gaps: i64,
+    /// Passive study time logged for this session
+    #[pyo3(get)]
+    pub logged_seconds: i64,
+}
+
+#[pymethods]
+impl SessionSummary {
+    fn __repr__(&self) -> String {
+        format!("SessionSummary(session_id='{}', attempts={}, active={}s, wall={}s)", 
+                self.session_id, self.attempts, self.active_seconds, self.wall_seconds)
+    }
+}
+
+/// Active study time from (timestamp secs, answer time ms) pairs sorted by time.
+/// Gaps up to the threshold count fully; after a longer gap only the answer itself
+/// (capped at the threshold) counts, since the user was away in between.
+/// Returns (active seconds, idle gap count).
+pub fn active_time(events: &[(i64, Option<i64>)], idle_threshold_secs: i64) -> (i64, i64) {
+    let answer_secs = |ms: Option<i64>| (ms.unwrap_or(0).max(0) / 1000).min(idle_threshold_secs);
+    
+    let Some(&(_, first_ms)) = events.first() else {
+        return (0, 0);
+    };
+    
+    let mut active = answer_secs(first_ms);
+    let mut idle_gaps = 0;
+    for pair in events.windows(2) {
+        let gap = pair[1].0 - pair[0].0;
+        if gap <= idle_threshold_secs {
+            active += gap.max(0);
+        } else {
+            active += answer_secs(pair[1].1);
+            idle_gaps += 1;
+        }
+    }
+    
+    (active, idle_gaps)
+}
+
+/// Summarize a session from its attempts, excluding idle gaps longer than the threshold
+pub fn get_session_summary(db_path: &str, session_id: &str, idle_threshold_secs: i64) -> Result<SessionSummary, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    
+    let mut stmt = conn.prepare(
+        "SELECT CAST(strftime('%s', attempted_at) AS INTEGER), time_taken_ms, is_correct, attempted_at
+         FROM attempts WHERE session_id = ?1 ORDER BY attempted_at, id"
+    ).map_err(|e| format!("Failed to prepare query: {}", e))?;
+    
+    let rows: Vec<(i64, Option<i64>, bool, String)> = stmt.query_map(params![session_id], |row| {
+        Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+    }).map_err(|e| format!("Failed to execute query: {}", e))?
+    .filter_map(|r| r.ok())
+    .collect();
+    
+    let logged_seconds: i64 = conn.query_row(
+        "SELECT COALESCE(SUM(seconds), 0) FROM study_time WHERE session_id = ?1",
+        params![session_id],
+        |row| row.get(0),
+    ).map_err(|e| format!("Failed to get study time: {}", e))?;
+    
+    let events: Vec<(i64, Option<i64>)> = rows.iter().map(|r| (r.0, r.1)).collect();
+    let (active_seconds, idle_gaps) = active_time(&events, idle_threshold_secs);
+    
+    let attempts = rows.len() as i64;
+    let correct_count = rows.iter().filter(|r| r.2).count() as i64;
+    
+    Ok(SessionSummary {
+        session_id: session_id.to_string(),
+        attempts,
+        correct_count,
+        accuracy_percent: if attempts > 0 { correct_count as f64 / attempts as f64 * 100.0 } else { 0.0 },
+        started_at: rows.first().map(|r| r.3.clone()),
+        ended_at: rows.last().map(|r| r.3.clone()),
+        wall_seconds: match (rows.first(), rows.last()) {
+            (Some(first), Some(last)) => last.0 - first.0,
+            _ => 0,
+        },
+        active_seconds,
+        idle_gaps,
+        logged_seconds,
+    })
+}
+
+/// Record time spent on a study activity that doesn't produce attempts
+pub fn log_study_time(db_path: &str, session_id: Option<&str>, seconds: i64, activity: &str) -> Result<i64, String> {
+    if seconds <= 0 {
+        return Err(format!("Study time must be positive, got {} seconds", seconds));
+    }
+    
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    
+    conn.execute(
+        "INSERT INTO study_time (session_id, activity, seconds) VALUES (?1, ?2, ?3)",
+        params![session_id, activity, seconds],
+    ).map_err(|e| format!("Failed to log study time: {}", e))?;
+    
+    Ok(conn.last_insert_rowid())
+}
+
+/// Daily study totals for the last `days` days (including today), combining logged
+/// passive time with answering time. Days without any activity are omitted.
+pub fn get_daily_study_time(db_path: &str, days: i64) -> Result<Vec<DailyStudyTime>, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    
+    let since = format!("-{} days", days.max(1) - 1);
+    
+    // Response times are capped so a question left open doesn't count as study
+    let mut stmt = conn.prepare(
+        "SELECT day, SUM(logged), SUM(answering) FROM (
+             SELECT date(logged_at, 'localtime') AS day, seconds AS logged, 0 AS answering
+             FROM study_time
+             UNION ALL
+             SELECT date(attempted_at, 'localtime') AS day, 0, MIN(COALESCE(time_taken_ms, 0), ?2) / 1000
+             FROM attempts
+         )
+         WHERE day >= date('now', 'localtime', ?1)
+         GROUP BY day ORDER BY day"
+    ).map_err(|e| format!("Failed to prepare query: {}", e))?;
+    
+    let rows = stmt.query_map(params![since, DEFAULT_MAX_TIME_MS], |row| {
+        let logged: i64 = row.get(1)?;
+        let answering: i64 = row.get(2)?;
+        Ok(DailyStudyTime {
+            date: row.get(0)?,
+            logged_seconds: logged,
+            answering_seconds: answering,
+            total_seconds: logged + answering,
+        })
+    }).map_err(|e| format!("Failed to execute query: {}", e))?;
+    
+    Ok(rows.filter_map(|r| r.ok()).collect())
+}
+
+/// Logged study time per activity, optionally limited to one session
+pub fn get_study_time_by_activity(db_path: &str, session_id: Option<&str>) -> Result<Vec<ActivityTime>, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    
+    let mut stmt = conn.prepare(
+        "SELECT activity, SUM(seconds) FROM study_time
+         WHERE ?1 IS NULL OR session_id = ?1
+         GROUP BY activity ORDER BY SUM(seconds) DESC"
+    ).map_err(|e| format!("Failed to prepare query: {}", e))?;
+    
+    let rows = stmt.query_map(params![session_id], |row| {
+        Ok(ActivityTime { activity: row.get(0)?, seconds: row.get(1)? })
+    }).map_err(|e| format!("Failed to execute query: {}", e))?;
+    
+    Ok(rows.filter_map(|r| r.ok()).collect())
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "log_study_time", signature = (db_path, session_id, seconds, activity))]
+pub fn py_log_study_time(db_path: &str, session_id: Option<&str>, seconds: i64, activity: &str) -> PyResult<i64> {
+    log_study_time(db_path, session_id, seconds, activity)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "get_daily_study_time", signature = (db_path, days=7))]
+pub fn py_get_daily_study_time(db_path: &str, days: i64) -> PyResult<Vec<DailyStudyTime>> {
+    get_daily_study_time(db_path, days)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "get_study_time_by_activity", signature = (db_path, session_id=None))]
+pub fn py_get_study_time_by_activity(db_path: &str, session_id: Option<&str>) -> PyResult<Vec<ActivityTime>> {
+    get_study_time_by_activity(db_path, session_id)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "get_session_summary", signature = (db_path, session_id, idle_threshold_secs=DEFAULT_IDLE_THRESHOLD_SECS))]
+pub fn py_get_session_summary(db_path: &str, session_id: &str, idle_threshold_secs: i64) -> PyResult<SessionSummary> {
+    get_session_summary(db_path, session_id, idle_threshold_secs)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
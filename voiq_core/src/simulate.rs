@@ -0,0 +1,308 @@
+//! Simulated learner for sizing decks and testing scheduler settings.
+//!
+//! A virtual learner studies a deck day by day under a scheduler configuration. Each
+//! word has a memory stability; recall odds decay with the time since the last review
+//! following the chosen forgetting model, and successful reviews make memories sturdier.
+//! The run is seeded, so the same inputs always give the same projection.
+
+use pyo3::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use crate::db::{open_database, get_words, DIFFICULTY_LEVELS};
+use crate::srs::{get_deck_srs_params, SrsParams, SrsState, MASTERED_INTERVAL_DAYS};
+
+/// How recall odds fall with time since the last review
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ForgettingModel {
+    /// exp(-t / S)
+    Exponential,
+    /// (1 + t / 9S)^-1, a slower long tail
+    PowerLaw,
+}
+
+impl ForgettingModel {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().as_str() {
+            "" | "exponential" => Ok(ForgettingModel::Exponential),
+            "power" | "power_law" => Ok(ForgettingModel::PowerLaw),
+            _ => Err(format!("Unknown forgetting model: {}", name)),
+        }
+    }
+    
+    /// Chance of recalling a memory of stability `stability` after `days`
+    pub fn recall(self, days: f64, stability: f64) -> f64 {
+        let stability = stability.max(f64::EPSILON);
+        match self {
+            ForgettingModel::Exponential => (-days / stability).exp(),
+            ForgettingModel::PowerLaw => 1.0 / (1.0 + days / (9.0 * stability)),
+        }
+    }
+}
+
+/// Virtual learner and study plan
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct SimulationConfig {
+    /// Days to simulate
+    #[pyo3(get, set)]
+    pub days: usize,
+    /// New words introduced per day
+    #[pyo3(get, set)]
+    pub new_per_day: usize,
+    /// "exponential" or "power_law"
+    #[pyo3(get, set)]
+    pub forgetting_model: String,
+    /// Chance of knowing a word at its first showing
+    #[pyo3(get, set)]
+    pub first_recall: f64,
+    /// Memory stability in days after the first showing of an average word
+    #[pyo3(get, set)]
+    pub initial_stability: f64,
+    /// Factor a successful review multiplies stability by
+    #[pyo3(get, set)]
+    pub stability_growth: f64,
+    /// Factor a failed review multiplies stability by
+    #[pyo3(get, set)]
+    pub lapse_factor: f64,
+    /// Time one review takes, for the projected minutes per day
+    #[pyo3(get, set)]
+    pub seconds_per_review: f64,
+    #[pyo3(get, set)]
+    pub seed: u64,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        SimulationConfig {
+            days: 60,
+            new_per_day: 10,
+            forgetting_model: "exponential".to_string(),
+            first_recall: 0.3,
+            initial_stability: 4.0,
+            stability_growth: 3.5,
+            lapse_factor: 0.5,
+            seconds_per_review: 8.0,
+            seed: 42,
+        }
+    }
+}
+
+#[pymethods]
+impl SimulationConfig {
+    #[new]
+    #[pyo3(signature = (days=60, new_per_day=10, forgetting_model="exponential".to_string(), first_recall=0.3,
+                        initial_stability=4.0, stability_growth=3.5, lapse_factor=0.5,
+                        seconds_per_review=8.0, seed=42))]
+    #[allow(clippy::too_many_arguments)]
+    fn py_new(
+        days: usize,
+        new_per_day: usize,
+        forgetting_model: String,
+        first_recall: f64,
+        initial_stability: f64,
+        stability_growth: f64,
+        lapse_factor: f64,
+        seconds_per_review: f64,
+        seed: u64,
+    ) -> Self {
+        SimulationConfig {
+            days, new_per_day, forgetting_model, first_recall, initial_stability,
+            stability_growth, lapse_factor, seconds_per_review, seed,
+        }
+    }
+    
+    fn __repr__(&self) -> String {
+        format!("SimulationConfig(days={}, new_per_day={}, forgetting_model='{}')",
+                self.days, self.new_per_day, self.forgetting_model)
+    }
+}
+
+/// One simulated day
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct SimulatedDay {
+    /// Day number, starting at 0
+    #[pyo3(get)]
+    pub day: usize,
+    /// Reviews of already-introduced words
+    #[pyo3(get)]
+    pub reviews: usize,
+    #[pyo3(get)]
+    pub new_words: usize,
+    /// Correct answers among reviews and first showings
+    #[pyo3(get)]
+    pub correct: usize,
+    /// Projected study time
+    #[pyo3(get)]
+    pub minutes: f64,
+    /// Words whose interval reached `MASTERED_INTERVAL_DAYS` by the end of the day
+    #[pyo3(get)]
+    pub mastered: usize,
+}
+
+#[pymethods]
+impl SimulatedDay {
+    fn __repr__(&self) -> String {
+        format!("SimulatedDay(day={}, reviews={}, new_words={}, mastered={})",
+                self.day, self.reviews, self.new_words, self.mastered)
+    }
+}
+
+/// Projected workload and progress for a deck
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct SimulationResult {
+    #[pyo3(get)]
+    pub word_count: usize,
+    #[pyo3(get)]
+    pub daily: Vec<SimulatedDay>,
+    /// First day by the end of which every word was mastered (None if not within the run)
+    #[pyo3(get)]
+    pub days_to_mastery: Option<usize>,
+}
+
+#[pymethods]
+impl SimulationResult {
+    /// Average study minutes per simulated day
+    #[getter]
+    pub fn average_minutes(&self) -> f64 {
+        if self.daily.is_empty() {
+            return 0.0;
+        }
+        self.daily.iter().map(|d| d.minutes).sum::<f64>() / self.daily.len() as f64
+    }
+    
+    /// Busiest day's number of reviews and new words
+    #[getter]
+    pub fn peak_workload(&self) -> usize {
+        self.daily.iter().map(|d| d.reviews + d.new_words).max().unwrap_or(0)
+    }
+    
+    fn __repr__(&self) -> String {
+        let days_to_mastery = self.days_to_mastery.map_or("None".to_string(), |d| d.to_string());
+        format!("SimulationResult(word_count={}, days={}, days_to_mastery={})",
+                self.word_count, self.daily.len(), days_to_mastery)
+    }
+}
+
+/// The learner's memory of one word next to the scheduler's view of it
+struct SimWord {
+    /// Harder words start with less stable memories
+    stability_scale: f64,
+    stability: f64,
+    last_seen: usize,
+    due: usize,
+    state: SrsState,
+}
+
+/// Stability scale of a difficulty level: 1.0 for unset or middle difficulty,
+/// from 1.5 (easiest) down to 0.5 (hardest)
+fn stability_scale(difficulty: Option<i64>) -> f64 {
+    let (low, high) = (*DIFFICULTY_LEVELS.start() as f64, *DIFFICULTY_LEVELS.end() as f64);
+    match difficulty {
+        Some(level) => 1.5 - (level as f64 - low) / (high - low),
+        None => 1.0,
+    }
+}
+
+/// Run the virtual learner over words of the given difficulties (None = average).
+/// Due reviews come before new words and a deck's `daily_limit` caps reviews per day;
+/// reviews over the limit wait for the next day.
+pub fn simulate(difficulties: &[Option<i64>], params: &SrsParams, config: &SimulationConfig) -> Result<SimulationResult, String> {
+    let model = ForgettingModel::parse(&config.forgetting_model)?;
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    
+    let mut pending = difficulties.iter().map(|d| stability_scale(*d));
+    let mut words: Vec<SimWord> = Vec::new();
+    let mut daily = Vec::with_capacity(config.days);
+    let mut days_to_mastery = None;
+    
+    for day in 0..config.days {
+        let (mut reviews, mut correct) = (0, 0);
+        
+        let mut due: Vec<usize> = (0..words.len()).filter(|&i| words[i].due <= day).collect();
+        due.sort_by_key(|&i| (words[i].due, i));
+        if let Some(limit) = params.daily_limit {
+            due.truncate(limit);
+        }
+        for i in due {
+            let word = &mut words[i];
+            let p = model.recall((day - word.last_seen) as f64, word.stability);
+            let recalled = rng.gen_bool(p.clamp(0.0, 1.0));
+            word.stability *= if recalled { config.stability_growth } else { config.lapse_factor };
+            // A relearned word is never weaker than a new one
+            word.stability = word.stability.max(config.initial_stability * word.stability_scale);
+            word.state = word.state.review(recalled, params);
+            // A failed word comes back the next day; intervals are rounded to whole days
+            word.due = day + (word.state.interval_days.round() as usize).max(1);
+            word.last_seen = day;
+            reviews += 1;
+            correct += recalled as usize;
+        }
+        
+        let mut new_words = 0;
+        for scale in pending.by_ref().take(config.new_per_day) {
+            let recalled = rng.gen_bool(config.first_recall.clamp(0.0, 1.0));
+            let state = SrsState::new(params).review(recalled, params);
+            words.push(SimWord {
+                stability_scale: scale,
+                stability: config.initial_stability * scale,
+                last_seen: day,
+                due: day + (state.interval_days.round() as usize).max(1),
+                state,
+            });
+            new_words += 1;
+            correct += recalled as usize;
+        }
+        
+        let mastered = words.iter().filter(|w| w.state.interval_days >= MASTERED_INTERVAL_DAYS).count();
+        if days_to_mastery.is_none() && !difficulties.is_empty() && mastered == difficulties.len() {
+            days_to_mastery = Some(day);
+        }
+        daily.push(SimulatedDay {
+            day,
+            reviews,
+            new_words,
+            correct,
+            minutes: (reviews + new_words) as f64 * config.seconds_per_review / 60.0,
+            mastered,
+        });
+    }
+    
+    Ok(SimulationResult { word_count: difficulties.len(), daily, days_to_mastery })
+}
+
+/// Simulate studying a deck from scratch with its words' difficulties and its scheduler parameters
+pub fn simulate_deck(db_path: &str, category: &str, config: &SimulationConfig) -> Result<SimulationResult, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    let words = get_words(&conn, "a_to_z", None, Some(vec![category.to_string()]), None, None, None, false)
+        .map_err(|e| format!("Failed to get words: {}", e))?;
+    if words.is_empty() {
+        return Err(format!("Category not found: {}", category));
+    }
+    let params = get_deck_srs_params(&conn, category)
+        .map_err(|e| format!("Failed to get scheduler parameters: {}", e))?;
+    let difficulties: Vec<Option<i64>> = words.iter().map(|w| w.difficulty).collect();
+    simulate(&difficulties, &params, config)
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "simulate_deck", signature = (db_path, category, config=None))]
+pub fn py_simulate_deck(db_path: &str, category: &str, config: Option<SimulationConfig>) -> PyResult<SimulationResult> {
+    let config = config.unwrap_or_default();
+    ForgettingModel::parse(&config.forgetting_model).map_err(pyo3::exceptions::PyValueError::new_err)?;
+    simulate_deck(db_path, category, &config)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "simulate", signature = (word_count, config=None, params=None))]
+pub fn py_simulate(word_count: usize, config: Option<SimulationConfig>, params: Option<SrsParams>) -> PyResult<SimulationResult> {
+    let params = params.unwrap_or_default();
+    params.validate().map_err(pyo3::exceptions::PyValueError::new_err)?;
+    simulate(&vec![None; word_count], &params, &config.unwrap_or_default())
+        .map_err(pyo3::exceptions::PyValueError::new_err)
+}
@@ -0,0 +1,255 @@
+//! Writing spreadsheets the importer reads back (CSV and XLSX).
+//!
+//! XLSX files are assembled from a minimal set of SpreadsheetML parts: one worksheet with
+//! inline strings, a frozen header row, and optionally hidden columns and data
+//! validation. That is all the importer needs, and Excel, LibreOffice and Numbers open it.
+
+use pyo3::prelude::*;
+use std::io::Write;
+use std::path::Path;
+use zip::write::SimpleFileOptions;
+use crate::db::DIFFICULTY_LEVELS;
+use crate::excel::detect_columns;
+
+/// Columns of a new template, in order
+pub const TEMPLATE_COLUMNS: &[&str] = &["Word", "Meaning", "Synonyms", "Antonyms", "Example", "Notes", "POS", "Difficulty"];
+
+/// Sheet name of written workbooks
+const SHEET_NAME: &str = "Words";
+
+/// File format of a written spreadsheet
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SheetFormat {
+    Csv,
+    Xlsx,
+}
+
+impl SheetFormat {
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name.to_lowercase().trim_start_matches('.') {
+            "csv" => Ok(SheetFormat::Csv),
+            "xlsx" => Ok(SheetFormat::Xlsx),
+            _ => Err(format!("Unsupported spreadsheet format: {}", name)),
+        }
+    }
+    
+    /// Format named by a file's extension
+    pub fn from_path(path: &str) -> Result<Self, String> {
+        let extension = Path::new(path).extension().and_then(|e| e.to_str()).unwrap_or("");
+        SheetFormat::parse(extension)
+    }
+}
+
+/// Check on the data cells of a column, enforced by spreadsheet programs (XLSX only)
+#[derive(Debug, Clone)]
+pub(crate) enum Validation {
+    WholeNumber { column: usize, min: i64, max: i64, message: String },
+}
+
+/// A sheet to write: a header row, data rows and per-column settings
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Table {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    /// Columns hidden in XLSX files (CSV has no way to hide them)
+    pub hidden: Vec<usize>,
+    pub validations: Vec<Validation>,
+}
+
+/// Spreadsheet column name of a zero-based index: 0 is "A", 26 is "AA"
+fn column_name(mut index: usize) -> String {
+    let mut name = Vec::new();
+    loop {
+        name.push(b'A' + (index % 26) as u8);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    name.iter().rev().map(|&b| b as char).collect()
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// One cell; whole numbers are stored as numbers so validation and sorting treat them as such
+fn cell_xml(reference: &str, value: &str) -> String {
+    if !value.is_empty() && value.len() < 16 && value.parse::<i64>().is_ok() {
+        format!("<c r=\"{}\"><v>{}</v></c>", reference, value)
+    } else {
+        format!("<c r=\"{}\" t=\"inlineStr\"><is><t xml:space=\"preserve\">{}</t></is></c>", reference, escape_xml(value))
+    }
+}
+
+fn worksheet_xml(table: &Table) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+         <worksheet xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\">\
+         <sheetViews><sheetView workbookViewId=\"0\">\
+         <pane ySplit=\"1\" topLeftCell=\"A2\" activePane=\"bottomLeft\" state=\"frozen\"/>\
+         </sheetView></sheetViews>",
+    );
+    if !table.hidden.is_empty() {
+        xml.push_str("<cols>");
+        let mut hidden = table.hidden.clone();
+        hidden.sort_unstable();
+        hidden.dedup();
+        for column in hidden {
+            xml.push_str(&format!("<col min=\"{0}\" max=\"{0}\" width=\"9\" hidden=\"1\" customWidth=\"1\"/>", column + 1));
+        }
+        xml.push_str("</cols>");
+    }
+    
+    xml.push_str("<sheetData>");
+    for (r, row) in std::iter::once(&table.headers).chain(&table.rows).enumerate() {
+        xml.push_str(&format!("<row r=\"{}\">", r + 1));
+        for (c, value) in row.iter().enumerate() {
+            xml.push_str(&cell_xml(&format!("{}{}", column_name(c), r + 1), value));
+        }
+        xml.push_str("</row>");
+    }
+    xml.push_str("</sheetData>");
+    
+    if !table.validations.is_empty() {
+        xml.push_str(&format!("<dataValidations count=\"{}\">", table.validations.len()));
+        for validation in &table.validations {
+            let range = |column: usize| format!("{0}2:{0}1048576", column_name(column));
+            xml.push_str(&match validation {
+                Validation::WholeNumber { column, min, max, message } => format!(
+                    "<dataValidation type=\"whole\" allowBlank=\"1\" showErrorMessage=\"1\" error=\"{}\" sqref=\"{}\">\
+                     <formula1>{}</formula1><formula2>{}</formula2></dataValidation>",
+                    escape_xml(message), range(*column), min, max
+                ),
+            });
+        }
+        xml.push_str("</dataValidations>");
+    }
+    xml.push_str("</worksheet>");
+    xml
+}
+
+fn write_xlsx(out_path: &str, table: &Table) -> Result<(), String> {
+    let file = std::fs::File::create(out_path)
+        .map_err(|e| format!("Failed to create file: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let parts = [
+        ("[Content_Types].xml", String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+             <Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\">\
+             <Default Extension=\"rels\" ContentType=\"application/vnd.openxmlformats-package.relationships+xml\"/>\
+             <Default Extension=\"xml\" ContentType=\"application/xml\"/>\
+             <Override PartName=\"/xl/workbook.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml\"/>\
+             <Override PartName=\"/xl/worksheets/sheet1.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml\"/>\
+             </Types>",
+        )),
+        ("_rels/.rels", String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+             <Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\
+             <Relationship Id=\"rId1\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument\" Target=\"xl/workbook.xml\"/>\
+             </Relationships>",
+        )),
+        ("xl/workbook.xml", format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+             <workbook xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\" \
+             xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\">\
+             <sheets><sheet name=\"{}\" sheetId=\"1\" r:id=\"rId1\"/></sheets></workbook>",
+            SHEET_NAME
+        )),
+        ("xl/_rels/workbook.xml.rels", String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+             <Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\
+             <Relationship Id=\"rId1\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet\" Target=\"worksheets/sheet1.xml\"/>\
+             </Relationships>",
+        )),
+        ("xl/worksheets/sheet1.xml", worksheet_xml(table)),
+    ];
+    for (name, content) in parts {
+        zip.start_file(name, SimpleFileOptions::default())
+            .and_then(|_| zip.write_all(content.as_bytes()).map_err(Into::into))
+            .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+    }
+    zip.finish().map_err(|e| format!("Failed to finish workbook: {}", e))?;
+    Ok(())
+}
+
+fn write_csv(out_path: &str, table: &Table) -> Result<(), String> {
+    let mut writer = csv::Writer::from_path(out_path)
+        .map_err(|e| format!("Failed to create file: {}", e))?;
+    for row in std::iter::once(&table.headers).chain(&table.rows) {
+        writer.write_record(row)
+            .map_err(|e| format!("Failed to write row: {}", e))?;
+    }
+    writer.flush().map_err(|e| format!("Failed to write file: {}", e))
+}
+
+/// Write a table as CSV or XLSX, replacing any file at `out_path`
+pub(crate) fn write_table(out_path: &str, format: SheetFormat, table: &Table) -> Result<(), String> {
+    match format {
+        SheetFormat::Csv => write_csv(out_path, table),
+        SheetFormat::Xlsx => write_xlsx(out_path, table),
+    }
+}
+
+/// Sample value of a template column, for example rows
+fn example_value(column: &str, row: usize) -> &'static str {
+    let samples: [&str; 2] = match column.to_lowercase().trim() {
+        "word" | "words" | "vocabulary" => ["abundant", "ephemeral"],
+        "meaning" | "meanings" | "definition" | "definitions" => ["existing in large quantities", "lasting a very short time"],
+        "synonym" | "synonyms" => ["plentiful, ample", "fleeting, transient"],
+        "antonym" | "antonyms" => ["scarce", "permanent"],
+        "example" | "examples" | "sentence" | "example sentence" => ["Fresh water is abundant here.", "Fame on the internet is often ephemeral."],
+        "pos" | "part of speech" | "part_of_speech" | "word class" => ["adjective", "adjective"],
+        "difficulty" | "level" => ["2", "4"],
+        _ => ["", ""],
+    };
+    samples[row]
+}
+
+/// Write an empty import file with the given column headers (`TEMPLATE_COLUMNS` when
+/// empty). Fails for columns the importer wouldn't accept, so a filled-in template
+/// always imports. `example_rows` adds two sample words (delete them before importing);
+/// `validation` makes spreadsheet programs reject out-of-range difficulties (XLSX only).
+pub fn write_import_template(out_path: &str, format: SheetFormat, columns: &[String], example_rows: bool, validation: bool) -> Result<(), String> {
+    let headers: Vec<String> = if columns.is_empty() {
+        TEMPLATE_COLUMNS.iter().map(|c| c.to_string()).collect()
+    } else {
+        columns.iter().map(|c| c.trim().to_string()).collect()
+    };
+    detect_columns(&headers)?;
+    
+    let mut table = Table { headers, ..Default::default() };
+    if example_rows {
+        table.rows = (0..2)
+            .map(|row| table.headers.iter().map(|h| example_value(h, row).to_string()).collect())
+            .collect();
+    }
+    if validation {
+        let difficulty = table.headers.iter()
+            .position(|h| matches!(h.to_lowercase().as_str(), "difficulty" | "level"));
+        if let Some(column) = difficulty {
+            table.validations.push(Validation::WholeNumber {
+                column,
+                min: *DIFFICULTY_LEVELS.start(),
+                max: *DIFFICULTY_LEVELS.end(),
+                message: format!("Difficulty is a whole number from {} to {}", DIFFICULTY_LEVELS.start(), DIFFICULTY_LEVELS.end()),
+            });
+        }
+    }
+    write_table(out_path, format, &table)
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "write_import_template", signature = (out_path, format=None, columns=None, example_rows=false, validation=true))]
+pub fn py_write_import_template(out_path: &str, format: Option<&str>, columns: Option<Vec<String>>, example_rows: bool, validation: bool) -> PyResult<()> {
+    let format = format.map_or_else(|| SheetFormat::from_path(out_path), SheetFormat::parse)
+        .map_err(pyo3::exceptions::PyValueError::new_err)?;
+    let columns = columns.unwrap_or_default();
+    if !columns.is_empty() {
+        detect_columns(&columns).map_err(pyo3::exceptions::PyValueError::new_err)?;
+    }
+    write_import_template(out_path, format, &columns, example_rows, validation)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
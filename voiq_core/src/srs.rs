@@ -0,0 +1,551 @@
+//! Spaced repetition scheduling (a binary-grade variant of SM-2).
+//!
+//! Every saved attempt reviews its word: a correct answer grows the interval (1 day,
+//! then 6, then by the word's ease factor), a wrong one resets it and lowers the ease.
+//! State lives in `srs_state`, one row per reviewed word.
+//!
+//! New words start in a recognition phase (multiple choice only) and graduate to
+//! recall questions (dictation) after a per-deck number of correct recognitions.
+
+use pyo3::prelude::*;
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use crate::db::{open_database, Word, WORD_COLUMNS, IGNORED_WORD_IDS, category_condition, category_or_default, word_from_row};
+use crate::cram::CRAM_MODE;
+
+/// Ids of words snoozed until a time still in the future (expired snoozes don't count)
+pub(crate) const SNOOZED_WORD_IDS: &str =
+    "SELECT id FROM vocabulary WHERE snoozed_until > CURRENT_TIMESTAMP";
+
+/// A word excluded from reviews until `snoozed_until`
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct SnoozedWord {
+    #[pyo3(get)]
+    pub word: Word,
+    /// UTC time the snooze expires ("YYYY-MM-DD HH:MM:SS")
+    #[pyo3(get)]
+    pub snoozed_until: String,
+}
+
+#[pymethods]
+impl SnoozedWord {
+    fn __repr__(&self) -> String {
+        format!("SnoozedWord(word='{}', snoozed_until='{}')", self.word.word, self.snoozed_until)
+    }
+}
+
+/// Scheduler parameters, overridable per deck (see `set_deck_srs_params`)
+#[pyclass]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SrsParams {
+    /// Intervals in days after the first and second correct answer in a row
+    #[pyo3(get, set)]
+    pub initial_intervals: [f64; 2],
+    #[pyo3(get, set)]
+    pub starting_ease: f64,
+    #[pyo3(get, set)]
+    pub min_ease: f64,
+    #[pyo3(get, set)]
+    pub max_ease: f64,
+    /// Ease gained on a correct answer and lost on a wrong one
+    #[pyo3(get, set)]
+    pub ease_bonus: f64,
+    #[pyo3(get, set)]
+    pub ease_penalty: f64,
+    /// Most words of the deck reviewed per (UTC) day; further due words wait (None = unlimited)
+    #[pyo3(get, set)]
+    pub daily_limit: Option<usize>,
+}
+
+impl Default for SrsParams {
+    fn default() -> Self {
+        SrsParams {
+            initial_intervals: [1.0, 6.0],
+            starting_ease: 2.5,
+            min_ease: 1.3,
+            max_ease: 3.0,
+            ease_bonus: 0.05,
+            ease_penalty: 0.2,
+            daily_limit: None,
+        }
+    }
+}
+
+impl SrsParams {
+    /// Check that the parameters make a working schedule
+    pub fn validate(&self) -> Result<(), String> {
+        let [first, second] = self.initial_intervals;
+        if !(first > 0.0 && second >= first) {
+            return Err("initial_intervals must be positive and non-decreasing".to_string());
+        }
+        if !(self.min_ease > 1.0 && self.min_ease <= self.starting_ease && self.starting_ease <= self.max_ease) {
+            return Err("ease bounds must satisfy 1.0 < min_ease <= starting_ease <= max_ease".to_string());
+        }
+        if self.ease_bonus < 0.0 || self.ease_penalty < 0.0 {
+            return Err("ease_bonus and ease_penalty must not be negative".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[pymethods]
+impl SrsParams {
+    #[new]
+    #[pyo3(signature = (initial_intervals=[1.0, 6.0], starting_ease=2.5, min_ease=1.3, max_ease=3.0,
+                        ease_bonus=0.05, ease_penalty=0.2, daily_limit=None))]
+    fn py_new(
+        initial_intervals: [f64; 2],
+        starting_ease: f64,
+        min_ease: f64,
+        max_ease: f64,
+        ease_bonus: f64,
+        ease_penalty: f64,
+        daily_limit: Option<usize>,
+    ) -> Self {
+        SrsParams { initial_intervals, starting_ease, min_ease, max_ease, ease_bonus, ease_penalty, daily_limit }
+    }
+    
+    fn __repr__(&self) -> String {
+        let daily_limit = self.daily_limit.map_or("None".to_string(), |l| l.to_string());
+        format!("SrsParams(initial_intervals={:?}, starting_ease={}, daily_limit={})",
+                self.initial_intervals, self.starting_ease, daily_limit)
+    }
+}
+
+/// Scheduling state of a word
+#[derive(Debug, Clone, PartialEq)]
+pub struct SrsState {
+    pub interval_days: f64,
+    pub ease: f64,
+    /// Correct answers in a row
+    pub reps: i64,
+    pub lapses: i64,
+}
+
+impl SrsState {
+    pub fn new(params: &SrsParams) -> Self {
+        SrsState { interval_days: 0.0, ease: params.starting_ease, reps: 0, lapses: 0 }
+    }
+    
+    /// State after one more answer; a wrong answer makes the word due again right away
+    pub fn review(&self, correct: bool, params: &SrsParams) -> SrsState {
+        if correct {
+            let reps = self.reps + 1;
+            let interval_days = match reps {
+                1 => params.initial_intervals[0],
+                2 => params.initial_intervals[1],
+                _ => self.interval_days * self.ease,
+            };
+            SrsState {
+                interval_days,
+                ease: (self.ease + params.ease_bonus).min(params.max_ease),
+                reps,
+                lapses: self.lapses,
+            }
+        } else {
+            SrsState {
+                interval_days: 0.0,
+                ease: (self.ease - params.ease_penalty).max(params.min_ease),
+                reps: 0,
+                lapses: self.lapses + 1,
+            }
+        }
+    }
+}
+
+/// Current state of a word (None if it was never reviewed)
+pub fn read_state(conn: &Connection, word_id: i64) -> rusqlite::Result<Option<SrsState>> {
+    conn.query_row(
+        "SELECT interval_days, ease, reps, lapses FROM srs_state WHERE word_id = ?1",
+        params![word_id],
+        |row| Ok(SrsState {
+            interval_days: row.get(0)?,
+            ease: row.get(1)?,
+            reps: row.get(2)?,
+            lapses: row.get(3)?,
+        }),
+    ).optional()
+}
+
+/// Store a word's state, due `interval_days` from now
+pub fn write_state(conn: &Connection, word_id: i64, state: &SrsState) -> rusqlite::Result<()> {
+    write_state_at(conn, word_id, state, "now")
+}
+
+/// Store a word's state as reviewed at `reviewed_at` (UTC "YYYY-MM-DD HH:MM:SS" or
+/// "now"), due `interval_days` after that
+pub fn write_state_at(conn: &Connection, word_id: i64, state: &SrsState, reviewed_at: &str) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO srs_state (word_id, interval_days, ease, reps, lapses, due_at, last_review_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, datetime(?7, ?6), datetime(?7))
+         ON CONFLICT(word_id) DO UPDATE SET
+            interval_days = excluded.interval_days, ease = excluded.ease, reps = excluded.reps,
+            lapses = excluded.lapses, due_at = excluded.due_at, last_review_at = excluded.last_review_at",
+        params![word_id, state.interval_days, state.ease, state.reps, state.lapses, format!("+{} days", state.interval_days), reviewed_at],
+    )?;
+    Ok(())
+}
+
+/// Scheduler parameters of a deck: its stored overrides, or the defaults
+pub fn get_deck_srs_params(conn: &Connection, category: &str) -> rusqlite::Result<SrsParams> {
+    let stored: Option<String> = conn.query_row(
+        "SELECT srs_params FROM category_meta WHERE category = ?1",
+        params![category],
+        |row| row.get(0),
+    ).optional()?.flatten();
+    Ok(stored.and_then(|json| serde_json::from_str(&json).ok()).unwrap_or_default())
+}
+
+/// Store a deck's scheduler parameters (None goes back to the defaults)
+pub fn set_deck_srs_params(conn: &Connection, category: &str, srs_params: Option<&SrsParams>) -> rusqlite::Result<()> {
+    let json = srs_params.map(|p| serde_json::to_string(p).unwrap_or_default());
+    conn.execute(
+        "INSERT INTO category_meta (category, srs_params) VALUES (?1, ?2)
+         ON CONFLICT(category) DO UPDATE SET srs_params = excluded.srs_params, updated_at = CURRENT_TIMESTAMP",
+        params![category, json],
+    )?;
+    Ok(())
+}
+
+/// Scheduler parameters of the deck a word belongs to
+pub(crate) fn word_srs_params(conn: &Connection, word_id: i64) -> rusqlite::Result<SrsParams> {
+    let category: Option<String> = conn.query_row(
+        concat!("SELECT ", category_or_default!("category"), " FROM vocabulary WHERE id = ?1"),
+        params![word_id],
+        |row| row.get(0),
+    ).optional()?;
+    match category {
+        Some(category) => get_deck_srs_params(conn, &category),
+        None => Ok(SrsParams::default()),
+    }
+}
+
+/// Reviews a deck has left today under its daily limit (None when unlimited)
+fn remaining_reviews_today(conn: &Connection, category: &str) -> rusqlite::Result<Option<usize>> {
+    let Some(daily_limit) = get_deck_srs_params(conn, category)?.daily_limit else {
+        return Ok(None);
+    };
+    let reviewed: i64 = conn.query_row(
+        concat!(
+            "SELECT COUNT(*) FROM srs_state s JOIN vocabulary v ON v.id = s.word_id
+             WHERE s.last_review_at >= datetime('now', 'start of day') AND ",
+            category_or_default!("v.category"), " = ?1"
+        ),
+        params![category],
+        |row| row.get(0),
+    )?;
+    Ok(Some(daily_limit.saturating_sub(reviewed as usize)))
+}
+
+/// Reschedule a word after an answer, using its deck's parameters
+pub fn record_review(conn: &Connection, word_id: i64, correct: bool) -> rusqlite::Result<SrsState> {
+    let params = word_srs_params(conn, word_id)?;
+    let state = read_state(conn, word_id)?
+        .unwrap_or_else(|| SrsState::new(&params))
+        .review(correct, &params);
+    write_state(conn, word_id, &state)?;
+    Ok(state)
+}
+
+/// Recompute a word's schedule by replaying its answers oldest first (cram answers
+/// excepted), as of its latest answer. Words without answers keep their state. Returns
+/// the new state.
+pub fn rebuild_state(conn: &Connection, word_id: i64) -> rusqlite::Result<Option<SrsState>> {
+    let params = word_srs_params(conn, word_id)?;
+    let mut stmt = conn.prepare(
+        "SELECT is_correct, attempted_at FROM attempts WHERE word_id = ?1 AND mode != ?2
+         ORDER BY attempted_at, id"
+    )?;
+    let answers = stmt.query_map(params![word_id, CRAM_MODE], |row| Ok((row.get::<_, bool>(0)?, row.get::<_, String>(1)?)))?;
+    
+    let mut replayed: Option<(SrsState, String)> = None;
+    for (correct, attempted_at) in answers.filter_map(|a| a.ok()) {
+        let state = replayed.map_or_else(|| SrsState::new(&params), |(state, _)| state);
+        replayed = Some((state.review(correct, &params), attempted_at));
+    }
+    match replayed {
+        Some((state, reviewed_at)) => {
+            write_state_at(conn, word_id, &state, &reviewed_at)?;
+            Ok(Some(state))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Review interval (days) from which a word counts as mastered
+pub const MASTERED_INTERVAL_DAYS: f64 = 21.0;
+
+/// Give words added by an import the review schedule of a mastered word with the same
+/// text (ignoring case) from outside that import, so known words aren't drilled as new.
+/// Attempts stay with the original word. Returns how many words took over a schedule.
+pub fn copy_known_progress(conn: &Connection, import_id: i64) -> rusqlite::Result<usize> {
+    // OR IGNORE keeps the first match per word, which is the longest interval
+    conn.execute(
+        "INSERT OR IGNORE INTO srs_state (word_id, interval_days, ease, reps, lapses, due_at, last_review_at)
+         SELECT n.id, s.interval_days, s.ease, s.reps, s.lapses, s.due_at, s.last_review_at
+         FROM vocabulary n
+         JOIN vocabulary o ON LOWER(TRIM(o.word)) = LOWER(TRIM(n.word)) AND o.import_id IS NOT n.import_id
+                          AND o.deleted_at IS NULL
+         JOIN srs_state s ON s.word_id = o.id
+         WHERE n.import_id = ?1 AND s.interval_days >= ?2
+         ORDER BY n.id, s.interval_days DESC",
+        params![import_id, MASTERED_INTERVAL_DAYS],
+    )
+}
+
+/// Keep a word out of due queues and failed-word lists for `days` days from now.
+/// Snoozing again replaces the expiry. Returns false if the word doesn't exist.
+pub fn snooze_word(conn: &Connection, word_id: i64, days: f64) -> rusqlite::Result<bool> {
+    let updated = conn.execute(
+        "UPDATE vocabulary SET snoozed_until = datetime('now', ?2) WHERE id = ?1",
+        params![word_id, format!("+{} days", days)],
+    )?;
+    Ok(updated > 0)
+}
+
+/// End a word's snooze early. Returns false if it wasn't snoozed.
+pub fn unsnooze_word(conn: &Connection, word_id: i64) -> rusqlite::Result<bool> {
+    let updated = conn.execute(
+        "UPDATE vocabulary SET snoozed_until = NULL WHERE id = ?1 AND snoozed_until > CURRENT_TIMESTAMP",
+        params![word_id],
+    )?;
+    Ok(updated > 0)
+}
+
+/// Words whose snooze hasn't expired yet, soonest expiry first
+pub fn get_snoozed_words(db_path: &str) -> Result<Vec<SnoozedWord>, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {}, v.snoozed_until FROM vocabulary v
+         WHERE v.snoozed_until > CURRENT_TIMESTAMP AND v.deleted_at IS NULL ORDER BY v.snoozed_until",
+        WORD_COLUMNS
+    )).map_err(|e| format!("Failed to prepare query: {}", e))?;
+    
+    let words = stmt.query_map([], |row| Ok(SnoozedWord {
+        word: word_from_row(row)?,
+        snoozed_until: row.get("snoozed_until")?,
+    }))
+    .map_err(|e| format!("Failed to execute query: {}", e))?
+    .filter_map(|w| w.ok())
+    .collect();
+    Ok(words)
+}
+
+/// Reviewed words that are due now (except snoozed and ignored ones), most overdue first.
+/// Decks with a daily limit contribute only what's left of it today.
+pub fn get_due_words(db_path: &str, categories: Option<Vec<String>>, limit: Option<usize>) -> Result<Vec<Word>, String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    
+    let category_clause = category_condition(categories.as_deref())
+        .map(|c| format!(" AND {}", c))
+        .unwrap_or_default();
+    
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM vocabulary v JOIN srs_state s ON s.word_id = v.id
+         WHERE s.due_at <= CURRENT_TIMESTAMP AND v.deleted_at IS NULL
+           AND v.id NOT IN ({}) AND v.id NOT IN ({}){} ORDER BY s.due_at",
+        WORD_COLUMNS, SNOOZED_WORD_IDS, IGNORED_WORD_IDS, category_clause
+    )).map_err(|e| format!("Failed to prepare query: {}", e))?;
+    
+    let candidates: Vec<Word> = stmt.query_map([], word_from_row)
+        .map_err(|e| format!("Failed to execute query: {}", e))?
+        .filter_map(|w| w.ok())
+        .collect();
+    
+    let mut budgets: HashMap<String, Option<usize>> = HashMap::new();
+    let mut words = Vec::new();
+    for word in candidates {
+        if limit.is_some_and(|l| words.len() >= l) {
+            break;
+        }
+        if !budgets.contains_key(&word.category) {
+            let budget = remaining_reviews_today(&conn, &word.category)
+                .map_err(|e| format!("Failed to get daily limit: {}", e))?;
+            budgets.insert(word.category.clone(), budget);
+        }
+        match budgets.get_mut(&word.category) {
+            Some(Some(0)) => continue,
+            Some(Some(left)) => *left -= 1,
+            _ => {}
+        }
+        words.push(word);
+    }
+    Ok(words)
+}
+
+/// Attempt mode counting as a recognition answer
+pub const RECOGNITION_MODE: &str = "mcq";
+
+/// Correct recognitions before a word graduates, for decks without their own setting
+pub const DEFAULT_RECOGNITIONS_TO_GRADUATE: i64 = 2;
+
+/// Which kind of question a word is ready for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LearningPhase {
+    /// Still new: only multiple-choice questions
+    Recognition,
+    /// Graduated: recall questions such as dictation are allowed too
+    Recall,
+}
+
+impl LearningPhase {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LearningPhase::Recognition => "recognition",
+            LearningPhase::Recall => "recall",
+        }
+    }
+}
+
+/// Correct recognitions a deck's words need before recall questions
+pub fn get_recognitions_to_graduate(conn: &Connection, category: &str) -> rusqlite::Result<i64> {
+    let count: Option<i64> = conn.query_row(
+        "SELECT recognitions_to_graduate FROM category_meta WHERE category = ?1",
+        params![category],
+        |row| row.get(0),
+    ).optional()?.flatten();
+    Ok(count.unwrap_or(DEFAULT_RECOGNITIONS_TO_GRADUATE))
+}
+
+/// Set a deck's graduation threshold (None restores the default; 0 skips the recognition phase)
+pub fn set_recognitions_to_graduate(conn: &Connection, category: &str, count: Option<i64>) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO category_meta (category, recognitions_to_graduate) VALUES (?1, ?2)
+         ON CONFLICT(category) DO UPDATE SET
+            recognitions_to_graduate = excluded.recognitions_to_graduate, updated_at = CURRENT_TIMESTAMP",
+        params![category, count],
+    )?;
+    Ok(())
+}
+
+/// Learning phase of each given word that exists
+pub fn learning_phases(conn: &Connection, word_ids: &[i64]) -> rusqlite::Result<HashMap<i64, LearningPhase>> {
+    let mut stmt = conn.prepare(concat!(
+        "SELECT v.id,
+                (SELECT COUNT(*) FROM attempts a WHERE a.word_id = v.id AND a.mode = ?2 AND a.is_correct = 1),
+                (SELECT m.recognitions_to_graduate FROM category_meta m
+                 WHERE m.category = ", category_or_default!("v.category"), ")
+         FROM vocabulary v WHERE v.id IN (SELECT value FROM json_each(?1))"
+    ))?;
+    let ids = Value::from(word_ids.to_vec()).to_string();
+    let phases = stmt.query_map(params![ids, RECOGNITION_MODE], |row| {
+        let recognitions: i64 = row.get(1)?;
+        let required = row.get::<_, Option<i64>>(2)?.unwrap_or(DEFAULT_RECOGNITIONS_TO_GRADUATE);
+        let phase = if recognitions >= required { LearningPhase::Recall } else { LearningPhase::Recognition };
+        Ok((row.get(0)?, phase))
+    })?
+    .filter_map(|p| p.ok())
+    .collect();
+    Ok(phases)
+}
+
+/// Learning phase of a word (None if it doesn't exist)
+pub fn learning_phase(conn: &Connection, word_id: i64) -> rusqlite::Result<Option<LearningPhase>> {
+    Ok(learning_phases(conn, &[word_id])?.remove(&word_id))
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "get_due_words", signature = (db_path, categories=None, limit=None))]
+pub fn py_get_due_words(db_path: &str, categories: Option<Vec<String>>, limit: Option<usize>) -> PyResult<Vec<Word>> {
+    get_due_words(db_path, categories, limit)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "snooze_word")]
+pub fn py_snooze_word(db_path: &str, word_id: i64, days: f64) -> PyResult<bool> {
+    if !(days.is_finite() && days > 0.0) {
+        return Err(pyo3::exceptions::PyValueError::new_err("days must be positive"));
+    }
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+    snooze_word(&conn, word_id, days)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "unsnooze_word")]
+pub fn py_unsnooze_word(db_path: &str, word_id: i64) -> PyResult<bool> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+    unsnooze_word(&conn, word_id)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "get_snoozed_words")]
+pub fn py_get_snoozed_words(db_path: &str) -> PyResult<Vec<SnoozedWord>> {
+    get_snoozed_words(db_path)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
+
+#[pyfunction]
+#[pyo3(name = "get_learning_phase")]
+pub fn py_get_learning_phase(db_path: &str, word_id: i64) -> PyResult<Option<&'static str>> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+    let phase = learning_phase(&conn, word_id)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    Ok(phase.map(LearningPhase::as_str))
+}
+
+#[pyfunction]
+#[pyo3(name = "get_learning_phases")]
+pub fn py_get_learning_phases(db_path: &str, word_ids: Vec<i64>) -> PyResult<HashMap<i64, &'static str>> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+    let phases = learning_phases(&conn, &word_ids)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))?;
+    Ok(phases.into_iter().map(|(id, phase)| (id, phase.as_str())).collect())
+}
+
+#[pyfunction]
+#[pyo3(name = "get_recognitions_to_graduate")]
+pub fn py_get_recognitions_to_graduate(db_path: &str, category: &str) -> PyResult<i64> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+    get_recognitions_to_graduate(&conn, category)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "set_recognitions_to_graduate", signature = (db_path, category, count=None))]
+pub fn py_set_recognitions_to_graduate(db_path: &str, category: &str, count: Option<i64>) -> PyResult<()> {
+    if count.is_some_and(|c| c < 0) {
+        return Err(pyo3::exceptions::PyValueError::new_err("count must not be negative"));
+    }
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+    set_recognitions_to_graduate(&conn, category, count)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "get_deck_srs_params")]
+pub fn py_get_deck_srs_params(db_path: &str, category: &str) -> PyResult<SrsParams> {
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+    get_deck_srs_params(&conn, category)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+#[pyo3(name = "set_deck_srs_params", signature = (db_path, category, params=None))]
+pub fn py_set_deck_srs_params(db_path: &str, category: &str, params: Option<SrsParams>) -> PyResult<()> {
+    if let Some(params) = &params {
+        params.validate().map_err(pyo3::exceptions::PyValueError::new_err)?;
+    }
+    let conn = open_database(db_path)
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to open database: {}", e)))?;
+    set_deck_srs_params(&conn, category, params.as_ref())
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+}
@@ -0,0 +1,414 @@
+//! Transliteration between scripts so answers typed in a convenient script still match
+
+use pyo3::prelude::*;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Supported transliteration schemes
+pub const SCHEMES: &[&str] = &[
+    "kana_to_romaji",
+    "romaji_to_kana",
+    "pinyin_to_marks",
+    "pinyin_to_numbers",
+    "cyrillic_to_latin",
+    "latin_to_cyrillic",
+];
+
+/// Hiragana (Hepburn romanization); katakana is mapped onto hiragana first
+const KANA_ROMAJI: &[(&str, &str)] = &[
+    ("きゃ", "kya"), ("きゅ", "kyu"), ("きょ", "kyo"), ("しゃ", "sha"), ("しゅ", "shu"), ("しょ", "sho"),
+    ("ちゃ", "cha"), ("ちゅ", "chu"), ("ちょ", "cho"), ("にゃ", "nya"), ("にゅ", "nyu"), ("にょ", "nyo"),
+    ("ひゃ", "hya"), ("ひゅ", "hyu"), ("ひょ", "hyo"), ("みゃ", "mya"), ("みゅ", "myu"), ("みょ", "myo"),
+    ("りゃ", "rya"), ("りゅ", "ryu"), ("りょ", "ryo"), ("ぎゃ", "gya"), ("ぎゅ", "gyu"), ("ぎょ", "gyo"),
+    ("じゃ", "ja"), ("じゅ", "ju"), ("じょ", "jo"), ("びゃ", "bya"), ("びゅ", "byu"), ("びょ", "byo"),
+    ("ぴゃ", "pya"), ("ぴゅ", "pyu"), ("ぴょ", "pyo"),
+    ("あ", "a"), ("い", "i"), ("う", "u"), ("え", "e"), ("お", "o"),
+    ("か", "ka"), ("き", "ki"), ("く", "ku"), ("け", "ke"), ("こ", "ko"),
+    ("さ", "sa"), ("し", "shi"), ("す", "su"), ("せ", "se"), ("そ", "so"),
+    ("た", "ta"), ("ち", "chi"), ("つ", "tsu"), ("て", "te"), ("と", "to"),
+    ("な", "na"), ("に", "ni"), ("ぬ", "nu"), ("ね", "ne"), ("の", "no"),
+    ("は", "ha"), ("ひ", "hi"), ("ふ", "fu"), ("へ", "he"), ("ほ", "ho"),
+    ("ま", "ma"), ("み", "mi"), ("む", "mu"), ("め", "me"), ("も", "mo"),
+    ("や", "ya"), ("ゆ", "yu"), ("よ", "yo"),
+    ("ら", "ra"), ("り", "ri"), ("る", "ru"), ("れ", "re"), ("ろ", "ro"),
+    ("わ", "wa"), ("を", "wo"), ("ん", "n"),
+    ("が", "ga"), ("ぎ", "gi"), ("ぐ", "gu"), ("げ", "ge"), ("ご", "go"),
+    ("ざ", "za"), ("じ", "ji"), ("ず", "zu"), ("ぜ", "ze"), ("ぞ", "zo"),
+    ("だ", "da"), ("ぢ", "ji"), ("づ", "zu"), ("で", "de"), ("ど", "do"),
+    ("ば", "ba"), ("び", "bi"), ("ぶ", "bu"), ("べ", "be"), ("ぼ", "bo"),
+    ("ぱ", "pa"), ("ぴ", "pi"), ("ぷ", "pu"), ("ぺ", "pe"), ("ぽ", "po"),
+    ("ぁ", "a"), ("ぃ", "i"), ("ぅ", "u"), ("ぇ", "e"), ("ぉ", "o"),
+    ("ゃ", "ya"), ("ゅ", "yu"), ("ょ", "yo"), ("ゎ", "wa"),
+];
+
+/// Non-Hepburn spellings accepted when converting romaji to kana
+const ROMAJI_ALIASES: &[(&str, &str)] = &[
+    ("si", "し"), ("ti", "ち"), ("tu", "つ"), ("hu", "ふ"), ("zi", "じ"), ("di", "ぢ"), ("du", "づ"),
+    ("sya", "しゃ"), ("syu", "しゅ"), ("syo", "しょ"), ("tya", "ちゃ"), ("tyu", "ちゅ"), ("tyo", "ちょ"),
+    ("jya", "じゃ"), ("jyu", "じゅ"), ("jyo", "じょ"), ("zya", "じゃ"), ("zyu", "じゅ"), ("zyo", "じょ"),
+    ("ji", "じ"), ("zu", "ず"), ("wo", "を"),
+];
+
+/// Russian/Ukrainian Cyrillic to Latin (lowercase)
+const CYRILLIC_LATIN: &[(char, &str)] = &[
+    ('а', "a"), ('б', "b"), ('в', "v"), ('г', "g"), ('д', "d"), ('е', "e"), ('ё', "yo"), ('ж', "zh"),
+    ('з', "z"), ('и', "i"), ('й', "y"), ('к', "k"), ('л', "l"), ('м', "m"), ('н', "n"), ('о', "o"),
+    ('п', "p"), ('р', "r"), ('с', "s"), ('т', "t"), ('у', "u"), ('ф', "f"), ('х', "kh"), ('ц', "ts"),
+    ('ч', "ch"), ('ш', "sh"), ('щ', "shch"), ('ъ', ""), ('ы', "y"), ('ь', ""), ('э', "e"), ('ю', "yu"),
+    ('я', "ya"), ('і', "i"), ('ї', "yi"), ('є', "ye"), ('ґ', "g"),
+];
+
+/// Latin to Russian Cyrillic, longest sequences first
+const LATIN_CYRILLIC: &[(&str, &str)] = &[
+    ("shch", "щ"), ("zh", "ж"), ("kh", "х"), ("ts", "ц"), ("ch", "ч"), ("sh", "ш"),
+    ("yo", "ё"), ("yu", "ю"), ("ya", "я"), ("ye", "е"),
+    ("a", "а"), ("b", "б"), ("v", "в"), ("g", "г"), ("d", "д"), ("e", "е"), ("z", "з"), ("i", "и"),
+    ("y", "й"), ("k", "к"), ("l", "л"), ("m", "м"), ("n", "н"), ("o", "о"), ("p", "п"), ("r", "р"),
+    ("s", "с"), ("t", "т"), ("u", "у"), ("f", "ф"), ("h", "х"), ("c", "ц"), ("w", "в"), ("x", "кс"),
+    ("j", "й"), ("q", "к"),
+];
+
+/// Pinyin vowels with tone marks 1-4
+const PINYIN_TONES: &[(char, [char; 4])] = &[
+    ('a', ['ā', 'á', 'ǎ', 'à']),
+    ('e', ['ē', 'é', 'ě', 'è']),
+    ('i', ['ī', 'í', 'ǐ', 'ì']),
+    ('o', ['ō', 'ó', 'ǒ', 'ò']),
+    ('u', ['ū', 'ú', 'ǔ', 'ù']),
+    ('ü', ['ǖ', 'ǘ', 'ǚ', 'ǜ']),
+];
+
+fn romaji_to_kana_table() -> &'static HashMap<&'static str, &'static str> {
+    static TABLE: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table: HashMap<&str, &str> = HashMap::new();
+        // Small kana and ぢ/づ share romaji with other kana; map to the common ones
+        for (kana, romaji) in KANA_ROMAJI {
+            if !matches!(*kana, "ぁ" | "ぃ" | "ぅ" | "ぇ" | "ぉ" | "ゃ" | "ゅ" | "ょ" | "ゎ" | "ぢ" | "づ") {
+                table.insert(romaji, kana);
+            }
+        }
+        for (romaji, kana) in ROMAJI_ALIASES {
+            table.insert(romaji, kana);
+        }
+        table
+    })
+}
+
+/// Map katakana onto the equivalent hiragana (the blocks are 0x60 apart)
+fn katakana_to_hiragana(c: char) -> char {
+    if ('\u{30A1}'..='\u{30F6}').contains(&c) {
+        char::from_u32(c as u32 - 0x60).unwrap_or(c)
+    } else {
+        c
+    }
+}
+
+/// Convert hiragana/katakana to Hepburn romaji. Small っ doubles the next consonant
+/// and the long-vowel mark ー repeats the previous vowel.
+pub fn kana_to_romaji(text: &str) -> String {
+    let chars: Vec<char> = text.chars().map(katakana_to_hiragana).collect();
+    let mut out = String::new();
+    let mut geminate = false;
+    let mut i = 0;
+    
+    while i < chars.len() {
+        let c = chars[i];
+        if c == 'っ' {
+            geminate = true;
+            i += 1;
+            continue;
+        }
+        if c == 'ー' {
+            if let Some(vowel) = out.chars().last().filter(|v| "aeiou".contains(*v)) {
+                out.push(vowel);
+            }
+            i += 1;
+            continue;
+        }
+        
+        let pair: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+        let single = c.to_string();
+        let found = KANA_ROMAJI.iter()
+            .find(|(kana, _)| *kana == pair)
+            .map(|(_, romaji)| (*romaji, 2))
+            .or_else(|| KANA_ROMAJI.iter().find(|(kana, _)| *kana == single).map(|(_, romaji)| (*romaji, 1)));
+        
+        match found {
+            Some((romaji, len)) => {
+                if geminate {
+                    // Hepburn writes doubled "ch" as "tch"
+                    if romaji.starts_with("ch") {
+                        out.push('t');
+                    } else if let Some(first) = romaji.chars().next() {
+                        out.push(first);
+                    }
+                }
+                out.push_str(romaji);
+                i += len;
+            }
+            None => {
+                if geminate {
+                    out.push_str("tsu");
+                }
+                out.push(c);
+                i += 1;
+            }
+        }
+        geminate = false;
+    }
+    if geminate {
+        out.push_str("tsu");
+    }
+    out
+}
+
+/// Convert romaji to hiragana. Accepts common Kunrei spellings (si, tu, zya, ...),
+/// doubled consonants for っ and "n'"/"nn" for ん. Unrecognized characters pass through.
+pub fn romaji_to_kana(text: &str) -> String {
+    let table = romaji_to_kana_table();
+    let chars: Vec<char> = text.to_lowercase().chars().collect();
+    let is_vowel = |c: char| "aeiou".contains(c);
+    let mut out = String::new();
+    let mut i = 0;
+    
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        
+        if c == 'n' {
+            match next {
+                Some('\'') => {
+                    out.push('ん');
+                    i += 2;
+                    continue;
+                }
+                // "nn" before a vowel is ん plus the next syllable's n (konnichiwa)
+                Some('n') => {
+                    let before_vowel = chars.get(i + 2).is_some_and(|c| is_vowel(*c) || *c == 'y');
+                    out.push('ん');
+                    i += if before_vowel { 1 } else { 2 };
+                    continue;
+                }
+                Some(n) if is_vowel(n) || n == 'y' => {}
+                _ => {
+                    out.push('ん');
+                    i += 1;
+                    continue;
+                }
+            }
+        }
+        
+        if c.is_ascii_alphabetic() && !is_vowel(c) && (next == Some(c) || (c == 't' && next == Some('c'))) {
+            out.push('っ');
+            i += 1;
+            continue;
+        }
+        
+        let matched = (1..=3).rev().find_map(|len| {
+            if i + len > chars.len() {
+                return None;
+            }
+            let key: String = chars[i..i + len].iter().collect();
+            table.get(key.as_str()).map(|kana| (*kana, len))
+        });
+        match matched {
+            Some((kana, len)) => {
+                out.push_str(kana);
+                i += len;
+            }
+            None => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// Apply a tone (1-4) to the vowel that carries it in a pinyin syllable:
+/// "a" or "e" if present, the "o" of "ou", otherwise the last vowel
+fn mark_syllable(syllable: &str, tone: u32) -> Option<String> {
+    let chars: Vec<char> = syllable.chars().map(|c| if c == 'v' { 'ü' } else { c }).collect();
+    let lower: Vec<char> = chars.iter().flat_map(|c| c.to_lowercase()).collect();
+    if lower.len() != chars.len() {
+        return None;
+    }
+    let is_vowel = |c: &char| "aeiouü".contains(*c);
+    
+    let target = lower.iter().position(|c| *c == 'a' || *c == 'e')
+        .or_else(|| lower.windows(2).position(|w| w == ['o', 'u']))
+        .or_else(|| lower.iter().rposition(is_vowel))?;
+    
+    if !(1..=4).contains(&tone) {
+        return Some(chars.iter().collect());
+    }
+    let marks = PINYIN_TONES.iter().find(|(v, _)| *v == lower[target])?.1;
+    let mut marked = marks[tone as usize - 1];
+    if chars[target].is_uppercase() {
+        marked = marked.to_uppercase().next().unwrap_or(marked);
+    }
+    
+    let mut out: Vec<char> = chars.clone();
+    out[target] = marked;
+    Some(out.into_iter().collect())
+}
+
+/// Convert tone-number pinyin ("ni3 hao3", "lv4") to tone marks ("nǐ hǎo", "lǜ").
+/// Tone 5 (neutral) drops the digit; digits not following a syllable are kept.
+pub fn pinyin_to_marks(text: &str) -> String {
+    let mut out = String::new();
+    let mut syllable = String::new();
+    
+    for c in text.chars() {
+        if c.is_alphabetic() || c == 'ü' {
+            syllable.push(c);
+            continue;
+        }
+        match c.to_digit(10).filter(|d| (1..=5).contains(d)) {
+            Some(tone) if !syllable.is_empty() => match mark_syllable(&syllable, tone) {
+                Some(marked) => out.push_str(&marked),
+                None => {
+                    out.push_str(&syllable);
+                    out.push(c);
+                }
+            },
+            _ => {
+                out.push_str(&syllable);
+                out.push(c);
+            }
+        }
+        syllable.clear();
+    }
+    out.push_str(&syllable);
+    out
+}
+
+/// Split a tone-marked vowel into its base vowel and tone
+fn unmark(c: char) -> Option<(char, u32)> {
+    let lower = c.to_lowercase().next()?;
+    PINYIN_TONES.iter().find_map(|(base, marks)| {
+        marks.iter().position(|m| *m == lower).map(|tone| {
+            let base = if c.is_uppercase() { base.to_uppercase().next().unwrap_or(*base) } else { *base };
+            (base, tone as u32 + 1)
+        })
+    })
+}
+
+/// Convert tone-mark pinyin ("nǐhǎo") to tone numbers ("ni3hao3"). The number is placed
+/// after the syllable's final vowels and any -n, -ng or -r coda.
+pub fn pinyin_to_numbers(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let is_vowel = |c: char| "aeiouüAEIOUÜ".contains(c) || unmark(c).is_some();
+    let mut out = String::new();
+    let mut i = 0;
+    
+    while i < chars.len() {
+        let Some((base, tone)) = unmark(chars[i]) else {
+            out.push(chars[i]);
+            i += 1;
+            continue;
+        };
+        out.push(base);
+        i += 1;
+        
+        while i < chars.len() && is_vowel(chars[i]) && unmark(chars[i]).is_none() {
+            out.push(chars[i]);
+            i += 1;
+        }
+        let vowel_at = |j: usize| chars.get(j).is_some_and(|c| is_vowel(*c));
+        let lower_at = |j: usize| chars.get(j).map(|c| c.to_ascii_lowercase());
+        if lower_at(i) == Some('n') && lower_at(i + 1) == Some('g') && !vowel_at(i + 2) {
+            out.push(chars[i]);
+            out.push(chars[i + 1]);
+            i += 2;
+        } else if matches!(lower_at(i), Some('n') | Some('r')) && !vowel_at(i + 1) {
+            out.push(chars[i]);
+            i += 1;
+        }
+        out.push_str(&tone.to_string());
+    }
+    out
+}
+
+/// Convert Cyrillic to Latin, keeping the capitalization of the first letter
+pub fn cyrillic_to_latin(text: &str) -> String {
+    let mut out = String::new();
+    for c in text.chars() {
+        let lower = c.to_lowercase().next().unwrap_or(c);
+        match CYRILLIC_LATIN.iter().find(|(cyr, _)| *cyr == lower) {
+            Some((_, latin)) if c.is_uppercase() => {
+                let mut letters = latin.chars();
+                if let Some(first) = letters.next() {
+                    out.extend(first.to_uppercase());
+                    out.extend(letters);
+                }
+            }
+            Some((_, latin)) => out.push_str(latin),
+            None => out.push(c),
+        }
+    }
+    out
+}
+
+/// Convert Latin to Russian Cyrillic, matching multi-letter sequences (shch, zh, ya, ...) first
+pub fn latin_to_cyrillic(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+    
+    while i < chars.len() {
+        let matched = LATIN_CYRILLIC.iter().find(|(latin, _)| {
+            let len = latin.chars().count();
+            i + len <= chars.len()
+                && chars[i..i + len].iter().flat_map(|c| c.to_lowercase()).eq(latin.chars())
+        });
+        match matched {
+            Some((latin, cyr)) => {
+                if chars[i].is_uppercase() {
+                    let mut letters = cyr.chars();
+                    if let Some(first) = letters.next() {
+                        out.extend(first.to_uppercase());
+                        out.extend(letters);
+                    }
+                } else {
+                    out.push_str(cyr);
+                }
+                i += latin.chars().count();
+            }
+            None => {
+                out.push(chars[i]);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// Transliterate text with one of `SCHEMES`
+pub fn transliterate(text: &str, scheme: &str) -> Result<String, String> {
+    match scheme {
+        "kana_to_romaji" => Ok(kana_to_romaji(text)),
+        "romaji_to_kana" => Ok(romaji_to_kana(text)),
+        "pinyin_to_marks" => Ok(pinyin_to_marks(text)),
+        "pinyin_to_numbers" => Ok(pinyin_to_numbers(text)),
+        "cyrillic_to_latin" => Ok(cyrillic_to_latin(text)),
+        "latin_to_cyrillic" => Ok(latin_to_cyrillic(text)),
+        _ => Err(format!("Unknown transliteration scheme '{}'. Expected one of: {}", scheme, SCHEMES.join(", "))),
+    }
+}
+
+/// Fold text to a script-independent key for comparison: kana and Cyrillic become
+/// Latin, tone-number pinyin becomes tone marks
+pub fn fold_script(text: &str) -> String {
+    pinyin_to_marks(&cyrillic_to_latin(&kana_to_romaji(text)))
+}
+
+// ============= Python Binding =============
+
+#[pyfunction]
+#[pyo3(name = "transliterate")]
+pub fn py_transliterate(text: &str, scheme: &str) -> PyResult<String> {
+    transliterate(text, scheme)
+        .map_err(pyo3::exceptions::PyValueError::new_err)
+}
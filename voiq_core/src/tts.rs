@@ -0,0 +1,95 @@
+//! Text preparation for read-aloud (TTS) of meanings and examples
+
+use pyo3::prelude::*;
+use crate::charts::escape_xml;
+use crate::cloze::split_sentences;
+
+/// Default chunk length; most TTS engines handle a couple of hundred characters well
+pub const DEFAULT_TTS_CHUNK_CHARS: usize = 200;
+
+/// Characters after which a long sentence may be broken
+const CLAUSE_BREAKS: &[char] = &[',', ';', ':', '—', '–', '、', '，', '；', '：'];
+
+/// Split text at clause punctuation, keeping the punctuation with the preceding clause
+fn split_clauses(sentence: &str) -> Vec<String> {
+    let mut clauses = Vec::new();
+    let mut current = String::new();
+    for c in sentence.chars() {
+        current.push(c);
+        if CLAUSE_BREAKS.contains(&c) {
+            clauses.push(std::mem::take(&mut current));
+        }
+    }
+    clauses.push(current);
+    clauses.into_iter()
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .collect()
+}
+
+/// Break a piece that is still too long at spaces, and words longer than the limit anywhere
+fn split_words(piece: &str, max_chars: usize) -> Vec<String> {
+    let mut parts = Vec::new();
+    for word in piece.split_whitespace() {
+        let chars: Vec<char> = word.chars().collect();
+        for chunk in chars.chunks(max_chars) {
+            parts.push(chunk.iter().collect());
+        }
+    }
+    parts
+}
+
+/// Greedily join pieces with spaces into chunks of at most `max_chars` characters
+fn pack(pieces: Vec<String>, max_chars: usize, chunks: &mut Vec<String>) {
+    let mut current = String::new();
+    for piece in pieces {
+        let joined_len = current.chars().count() + usize::from(!current.is_empty()) + piece.chars().count();
+        if !current.is_empty() && joined_len > max_chars {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(&piece);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+}
+
+/// Split text into TTS-friendly chunks of at most `max_chars` characters (measured before
+/// escaping). Breaks prefer sentence ends, then clause punctuation, then spaces. Each chunk
+/// is escaped so it can be embedded in SSML.
+pub fn split_for_tts(text: &str, max_chars: usize) -> Vec<String> {
+    let max_chars = max_chars.max(1);
+    let mut pieces = Vec::new();
+    
+    for sentence in split_sentences(text) {
+        let sentence = sentence.split_whitespace().collect::<Vec<_>>().join(" ");
+        if sentence.chars().count() <= max_chars {
+            pieces.push(sentence);
+            continue;
+        }
+        for clause in split_clauses(&sentence) {
+            if clause.chars().count() <= max_chars {
+                pieces.push(clause);
+            } else {
+                let mut words = Vec::new();
+                pack(split_words(&clause, max_chars), max_chars, &mut words);
+                pieces.extend(words);
+            }
+        }
+    }
+    
+    let mut chunks = Vec::new();
+    pack(pieces, max_chars, &mut chunks);
+    chunks.iter().map(|c| escape_xml(c)).collect()
+}
+
+// ============= Python Binding =============
+
+#[pyfunction]
+#[pyo3(name = "split_for_tts", signature = (text, max_chars=DEFAULT_TTS_CHUNK_CHARS))]
+pub fn py_split_for_tts(text: &str, max_chars: usize) -> Vec<String> {
+    split_for_tts(text, max_chars)
+}
@@ -0,0 +1,118 @@
+//! Validation and clean-up of word fields passed to `add_word` / `update_word`
+
+use pyo3::prelude::*;
+use rusqlite::{Connection, OptionalExtension, params};
+use crate::collation::word_key;
+use crate::db::{category_or_default, uniqueness_mode, UniquenessMode, WordUpdate};
+
+pyo3::create_exception!(voiq_core, ValidationError, pyo3::exceptions::PyValueError,
+    "Invalid word fields; `issues` lists each failed rule as a ValidationIssue.");
+
+/// Maximum lengths in characters
+pub const MAX_WORD_LEN: usize = 200;
+pub const MAX_TEXT_LEN: usize = 2000;
+pub const MAX_CATEGORY_LEN: usize = 100;
+
+/// A failed validation rule
+#[pyclass]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationIssue {
+    #[pyo3(get)]
+    pub field: String,
+    /// "required", "max_length" or "duplicate"
+    #[pyo3(get)]
+    pub rule: String,
+    #[pyo3(get)]
+    pub message: String,
+}
+
+#[pymethods]
+impl ValidationIssue {
+    fn __repr__(&self) -> String {
+        format!("ValidationIssue(field='{}', rule='{}')", self.field, self.rule)
+    }
+}
+
+/// Remove control characters; multi-line fields keep line breaks and tabs
+pub fn strip_control(text: &str, multiline: bool) -> String {
+    text.chars()
+        .filter(|c| !c.is_control() || (multiline && matches!(c, '\n' | '\t')))
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// Clean the given fields and check them. `require_word` demands a word (adding rather
+/// than editing); a set word or category must never be empty.
+pub fn validate_word(update: &WordUpdate, require_word: bool) -> Result<WordUpdate, Vec<ValidationIssue>> {
+    let mut issues = Vec::new();
+    let mut check = |field: &str, value: &Option<String>, multiline: bool, max_len: usize, required: bool| {
+        let cleaned = value.as_deref().map(|v| strip_control(v, multiline));
+        match cleaned.as_deref() {
+            Some("") | None if required => issues.push(ValidationIssue {
+                field: field.to_string(),
+                rule: "required".to_string(),
+                message: format!("{} cannot be empty", field),
+            }),
+            Some(v) if v.chars().count() > max_len => issues.push(ValidationIssue {
+                field: field.to_string(),
+                rule: "max_length".to_string(),
+                message: format!("{} is longer than {} characters", field, max_len),
+            }),
+            _ => {}
+        }
+        cleaned
+    };
+    
+    let cleaned = WordUpdate {
+        word: check("word", &update.word, false, MAX_WORD_LEN, require_word || update.word.is_some()),
+        meaning: check("meaning", &update.meaning, true, MAX_TEXT_LEN, false),
+        synonyms: check("synonyms", &update.synonyms, true, MAX_TEXT_LEN, false),
+        antonyms: check("antonyms", &update.antonyms, true, MAX_TEXT_LEN, false),
+        category: check("category", &update.category, false, MAX_CATEGORY_LEN, update.category.is_some()),
+        reading: check("reading", &update.reading, false, MAX_WORD_LEN, false),
+        notes: check("notes", &update.notes, true, MAX_TEXT_LEN, false),
+        pos: check("pos", &update.pos, false, MAX_CATEGORY_LEN, false),
+    };
+    
+    if issues.is_empty() { Ok(cleaned) } else { Err(issues) }
+}
+
+/// Id of another word with the same text in a category: ignoring case, or also accents
+/// and spacing in normalized uniqueness mode
+pub fn find_duplicate(conn: &Connection, word: &str, category: &str, exclude_id: Option<i64>) -> rusqlite::Result<Option<i64>> {
+    let key = match uniqueness_mode(conn)? {
+        UniquenessMode::Exact => None,
+        UniquenessMode::Normalized => Some(word_key(word)),
+    };
+    conn.query_row(
+        concat!(
+            "SELECT id FROM vocabulary
+             WHERE CASE WHEN ?4 IS NULL THEN LOWER(TRIM(word)) = LOWER(?1) ELSE word_key = ?4 END
+               AND ", category_or_default!("category"), " = ?2 AND id != COALESCE(?3, -1)
+               AND deleted_at IS NULL
+             LIMIT 1"
+        ),
+        params![word.trim(), category, exclude_id, key],
+        |row| row.get(0),
+    ).optional()
+}
+
+/// Issue reported for a duplicate word
+pub fn duplicate_issue(word: &str, category: &str) -> ValidationIssue {
+    ValidationIssue {
+        field: "word".to_string(),
+        rule: "duplicate".to_string(),
+        message: format!("'{}' already exists in category '{}'", word, category),
+    }
+}
+
+/// Python `ValidationError` carrying the issues
+pub fn validation_error(py: Python<'_>, issues: Vec<ValidationIssue>) -> PyErr {
+    let message = issues.iter().map(|i| i.message.as_str()).collect::<Vec<_>>().join("; ");
+    let err = ValidationError::new_err(message);
+    if let Err(e) = err.value(py).setattr("issues", issues) {
+        return e;
+    }
+    err
+}
@@ -0,0 +1,81 @@
+//! Reporting views for external tools (DB Browser, Metabase, ...).
+//!
+//! The views are plain (not materialized) and recreated by `init_database`, so they
+//! always match the current schema and never need refreshing for new data.
+
+use pyo3::prelude::*;
+use rusqlite::Connection;
+use crate::db::{open_database, category_or_default};
+
+/// View names and their queries
+pub const VIEWS: &[(&str, &str)] = &[
+    (
+        "v_word_progress",
+        concat!(
+            "SELECT v.id AS word_id, v.word, v.meaning, ", category_or_default!("v.category"), " AS category,
+                COUNT(a.id) AS times_seen,
+                COALESCE(SUM(a.is_correct), 0) AS correct_count,
+                ROUND(AVG(a.is_correct) * 100, 1) AS accuracy_percent,
+                MAX(a.attempted_at) AS last_attempted_at,
+                s.due_at AS next_due,
+                s.interval_days
+             FROM vocabulary v
+             LEFT JOIN attempts a ON a.word_id = v.id
+             LEFT JOIN srs_state s ON s.word_id = v.id
+             WHERE v.ignored = 0 AND v.deleted_at IS NULL
+             GROUP BY v.id"
+        ),
+    ),
+    (
+        "v_daily_activity",
+        "SELECT date(a.attempted_at, 'localtime') AS day,
+                COUNT(*) AS attempts,
+                COALESCE(SUM(a.is_correct), 0) AS correct_count,
+                ROUND(AVG(a.is_correct) * 100, 1) AS accuracy_percent,
+                COUNT(DISTINCT a.word_id) AS words_practiced,
+                COALESCE(SUM(a.time_taken_ms), 0) AS answer_time_ms
+         FROM attempts a JOIN vocabulary w ON w.id = a.word_id
+         WHERE w.ignored = 0 AND w.deleted_at IS NULL
+         GROUP BY day",
+    ),
+    (
+        "v_category_stats",
+        concat!(
+            "SELECT ", category_or_default!("v.category"), " AS category,
+                COUNT(*) AS word_count,
+                SUM(EXISTS (SELECT 1 FROM attempts a WHERE a.word_id = v.id)) AS practiced_words,
+                (SELECT COUNT(*) FROM attempts a JOIN vocabulary w ON w.id = a.word_id
+                 WHERE w.ignored = 0 AND w.deleted_at IS NULL AND ", category_or_default!("w.category"), " = ", category_or_default!("v.category"), ") AS attempts,
+                (SELECT ROUND(AVG(a.is_correct) * 100, 1) FROM attempts a JOIN vocabulary w ON w.id = a.word_id
+                 WHERE w.ignored = 0 AND w.deleted_at IS NULL AND ", category_or_default!("w.category"), " = ", category_or_default!("v.category"), ") AS accuracy_percent,
+                SUM(EXISTS (SELECT 1 FROM srs_state s WHERE s.word_id = v.id AND s.due_at <= CURRENT_TIMESTAMP)) AS due_now
+             FROM vocabulary v
+             WHERE v.ignored = 0 AND v.deleted_at IS NULL
+             GROUP BY ", category_or_default!("v.category")
+        ),
+    ),
+];
+
+/// Drop and recreate the reporting views
+pub fn create_views(conn: &Connection) -> rusqlite::Result<()> {
+    for (name, query) in VIEWS {
+        conn.execute_batch(&format!("DROP VIEW IF EXISTS {name}; CREATE VIEW {name} AS {query};"))?;
+    }
+    Ok(())
+}
+
+/// Recreate the reporting views of a database
+pub fn refresh_views(db_path: &str) -> Result<(), String> {
+    let conn = open_database(db_path)
+        .map_err(|e| format!("Failed to open database: {}", e))?;
+    create_views(&conn).map_err(|e| format!("Failed to create views: {}", e))
+}
+
+// ============= Python Bindings =============
+
+#[pyfunction]
+#[pyo3(name = "refresh_views")]
+pub fn py_refresh_views(db_path: &str) -> PyResult<()> {
+    refresh_views(db_path)
+        .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+}
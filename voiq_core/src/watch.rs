@@ -0,0 +1,141 @@
+//! Watch-folder auto-import.
+//!
+//! `FolderWatcher` remembers the size and modification time of every supported file in
+//! a folder, and each `poll()` imports the files that appeared or changed since the last
+//! one. The app calls `poll` on a timer and passes a callback that receives every
+//! `ImportReport`, e.g. to show "Deck updated". Changed files are re-imported with
+//! upsert by default, so edited rows update their words instead of adding copies. A file
+//! caught half-written fails to import and is picked up again once it changes.
+
+use pyo3::prelude::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::SystemTime;
+use crate::db::LoadMode;
+use crate::excel::{import_path, supported_files, CategorySource, ImportOptions, ImportReport};
+
+/// Size and modification time a file had when last seen
+type FileStamp = (u64, Option<SystemTime>);
+
+/// Polls a folder and imports new or changed spreadsheets
+#[pyclass]
+pub struct FolderWatcher {
+    #[pyo3(get)]
+    dir_path: String,
+    #[pyo3(get)]
+    db_path: String,
+    /// Category by file name; other files go by `category_from`
+    categories: HashMap<String, String>,
+    category_from: CategorySource,
+    options: ImportOptions,
+    seen: HashMap<PathBuf, FileStamp>,
+}
+
+impl FolderWatcher {
+    /// Start watching `dir_path`. Files already there are only imported by the first
+    /// poll with `import_existing`; otherwise they count as seen.
+    pub fn new(
+        dir_path: &str,
+        db_path: &str,
+        categories: HashMap<String, String>,
+        category_from: CategorySource,
+        options: ImportOptions,
+        import_existing: bool,
+    ) -> Result<Self, String> {
+        let mut watcher = FolderWatcher {
+            dir_path: dir_path.to_string(),
+            db_path: db_path.to_string(),
+            categories,
+            category_from,
+            options,
+            seen: HashMap::new(),
+        };
+        if !import_existing {
+            watcher.seen = watcher.scan()?;
+        }
+        Ok(watcher)
+    }
+    
+    /// Current stamps of the supported files in the folder
+    fn scan(&self) -> Result<HashMap<PathBuf, FileStamp>, String> {
+        Ok(supported_files(&self.dir_path)?.into_iter()
+            .filter_map(|path| {
+                let metadata = std::fs::metadata(&path).ok()?;
+                Some((path, (metadata.len(), metadata.modified().ok())))
+            })
+            .collect())
+    }
+    
+    /// Import files added or changed since the last poll, in name order
+    pub fn poll(&mut self) -> Result<Vec<ImportReport>, String> {
+        let current = self.scan()?;
+        let mut changed: Vec<&PathBuf> = current.iter()
+            .filter(|(path, stamp)| self.seen.get(*path) != Some(stamp))
+            .map(|(path, _)| path)
+            .collect();
+        changed.sort();
+        
+        let mut reports = Vec::new();
+        for path in changed {
+            let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            let category = self.categories.get(&file_name).map(String::as_str);
+            reports.extend(import_path(path, &self.db_path, category, self.category_from, self.options));
+        }
+        self.seen = current;
+        Ok(reports)
+    }
+}
+
+#[pymethods]
+impl FolderWatcher {
+    #[new]
+    #[pyo3(signature = (dir_path, db_path, categories=None, category_from="filename", mode="upsert", import_existing=false))]
+    fn py_new(
+        dir_path: &str,
+        db_path: &str,
+        categories: Option<HashMap<String, String>>,
+        category_from: &str,
+        mode: &str,
+        import_existing: bool,
+    ) -> PyResult<Self> {
+        let category_from = CategorySource::parse(category_from).map_err(pyo3::exceptions::PyValueError::new_err)?;
+        let mode = LoadMode::parse(mode).map_err(pyo3::exceptions::PyValueError::new_err)?;
+        let options = ImportOptions { mode, ..Default::default() };
+        FolderWatcher::new(dir_path, db_path, categories.unwrap_or_default(), category_from, options, import_existing)
+            .map_err(pyo3::exceptions::PyRuntimeError::new_err)
+    }
+    
+    /// Import new and changed files, passing each report to `callback` as well as
+    /// returning them all
+    #[pyo3(name = "poll", signature = (callback=None))]
+    fn py_poll(&mut self, callback: Option<&Bound<'_, PyAny>>) -> PyResult<Vec<ImportReport>> {
+        let reports = self.poll().map_err(pyo3::exceptions::PyRuntimeError::new_err)?;
+        if let Some(callback) = callback {
+            for report in &reports {
+                callback.call1((report.clone(),))?;
+            }
+        }
+        Ok(reports)
+    }
+    
+    /// Map a file name in the folder to a category (None goes back to `category_from`)
+    #[pyo3(signature = (file_name, category))]
+    fn set_category(&mut self, file_name: &str, category: Option<&str>) {
+        match category {
+            Some(category) => self.categories.insert(file_name.to_string(), category.to_string()),
+            None => self.categories.remove(file_name),
+        };
+    }
+    
+    /// Files currently known to the watcher
+    #[getter]
+    fn watched_files(&self) -> Vec<String> {
+        let mut files: Vec<String> = self.seen.keys().map(|p| p.to_string_lossy().to_string()).collect();
+        files.sort();
+        files
+    }
+    
+    fn __repr__(&self) -> String {
+        format!("FolderWatcher(dir_path='{}', watched_files={})", self.dir_path, self.seen.len())
+    }
+}